@@ -3,21 +3,28 @@
 //! This module provides a high-level client for Redis Cluster with automatic
 //! slot-based routing, redirect handling, and topology management.
 
+use crate::core::command::{self, Cmd};
 use crate::core::connection::Connection;
 use crate::core::multiplexed::MultiplexedConnection;
-use crate::core::{Error, Result};
+use crate::core::pubsub::Connector;
+use crate::core::{Error, FlushMode, PubSub, Result};
 use crate::proto::frame::Frame;
 use bytes::Bytes;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, RwLock};
 
-use super::commands::{asking, cluster_info, cluster_nodes, cluster_slots};
+use super::commands::{
+    asking, cluster_countkeysinslot, cluster_getkeysinslot, cluster_info, cluster_keyslot,
+    cluster_nodes, cluster_shards, cluster_slots, flushall, wait,
+};
 use super::errors::parse_redis_error;
-use super::pool::{ConnectionPool, PoolConfig};
+use super::pipeline::ClusterPipeline;
+use super::pool::{ConnectionPool, PoolConfig, PoolStats};
 use super::slot::{key_slot, SLOT_COUNT};
-use super::topology::ClusterTopology;
+use super::topology::{ClusterTopology, NodeId, SlotRange};
 
 /// Default queue size for multiplexed connections.
 const DEFAULT_QUEUE_SIZE: usize = 1024;
@@ -31,6 +38,12 @@ const MAX_RETRIES_ON_IO: u8 = 3;
 /// Base delay for exponential backoff on IO errors (milliseconds).
 const RETRY_DELAY_MS: u64 = 100;
 
+/// Maximum number of TRYAGAIN retries before giving up.
+const MAX_TRYAGAIN_RETRIES: u8 = 5;
+
+/// Delay between TRYAGAIN retries (milliseconds).
+const TRYAGAIN_DELAY_MS: u64 = 20;
+
 /// MOVED redirect count threshold to trigger topology refresh.
 const MOVED_STORM_THRESHOLD: usize = 10;
 
@@ -40,6 +53,9 @@ const MOVED_STORM_WINDOW: Duration = Duration::from_secs(1);
 /// Minimum cooldown between topology refreshes (milliseconds).
 const REFRESH_COOLDOWN: Duration = Duration::from_millis(500);
 
+/// Commands that switch a connection into push mode, refused by `execute_on_node`.
+const PUSH_MODE_COMMANDS: [&[u8]; 4] = [b"SUBSCRIBE", b"PSUBSCRIBE", b"SSUBSCRIBE", b"MONITOR"];
+
 /// Helper function to create a connection to a Redis node.
 async fn connect_to_node(address: &str) -> Result<MultiplexedConnection> {
     // Parse address to get host and port
@@ -60,6 +76,34 @@ async fn connect_to_node(address: &str) -> Result<MultiplexedConnection> {
     Ok(MultiplexedConnection::new(connection, DEFAULT_QUEUE_SIZE))
 }
 
+/// Sends `frame` on `conn`, turning error replies into typed cluster errors.
+async fn send_mapped(conn: &MultiplexedConnection, frame: Frame) -> Result<Frame> {
+    match conn.send_command(frame).await {
+        Ok(Frame::Error(message)) => Err(parse_redis_error(&message)),
+        Err(Error::Server { message }) => Err(parse_redis_error(message.as_bytes())),
+        other => other,
+    }
+}
+
+/// Counts a TRYAGAIN reply for `slot` and waits before the next attempt.
+///
+/// Returns [`Error::TryAgain`] once the retries are used up.
+async fn wait_for_tryagain(slot: u16, retries: &mut u8) -> Result<()> {
+    *retries += 1;
+    if *retries > MAX_TRYAGAIN_RETRIES {
+        return Err(Error::TryAgain);
+    }
+
+    tracing::debug!(
+        "TRYAGAIN on slot {}, retry {}/{}",
+        slot,
+        retries,
+        MAX_TRYAGAIN_RETRIES
+    );
+    tokio::time::sleep(Duration::from_millis(TRYAGAIN_DELAY_MS)).await;
+    Ok(())
+}
+
 /// Tracks MOVED redirects to detect topology change storms.
 ///
 /// When many MOVED redirects occur in a short time window (e.g., during slot
@@ -129,6 +173,33 @@ impl MovedStormTracker {
     }
 }
 
+/// Counts redirects and retries since the client was created.
+#[derive(Debug, Default)]
+struct RedirectCounters {
+    moved: AtomicU64,
+    ask: AtomicU64,
+    io_retries: AtomicU64,
+    topology_refreshes: AtomicU64,
+}
+
+/// Redirect and retry counters of a [`ClusterClient`], for diagnosing an
+/// unstable cluster.
+///
+/// The counters are totals since the client was created, shared by its clones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ClusterStats {
+    /// Number of MOVED redirects received.
+    pub moved_total: u64,
+    /// Number of ASK redirects received.
+    pub ask_total: u64,
+    /// Number of commands retried after an IO error or another transient failure
+    /// such as CLUSTERDOWN.
+    pub io_retry_total: u64,
+    /// Number of successful topology refreshes.
+    pub topology_refresh_total: u64,
+}
+
 /// Redis Cluster client.
 ///
 /// Provides automatic slot-based routing to cluster nodes and handles
@@ -143,6 +214,10 @@ pub struct ClusterClient {
     pool: Arc<ConnectionPool>,
     /// MOVED storm tracker for throttling topology refreshes
     storm_tracker: Arc<MovedStormTracker>,
+    /// Node that answered the last successful topology refresh, tried first next time
+    preferred_seed: Arc<Mutex<Option<String>>>,
+    /// Redirect and retry counters reported by `stats`
+    counters: Arc<RedirectCounters>,
 }
 
 impl ClusterClient {
@@ -159,6 +234,7 @@ impl ClusterClient {
     ///
     /// Returns an error if:
     /// - Cannot connect to any seed node
+    /// - The server is a standalone Redis ([`Error::InvalidArgument`])
     /// - Topology discovery fails
     pub async fn connect(addresses: &str) -> Result<Self> {
         let seed_nodes = Self::parse_addresses(addresses)?;
@@ -171,14 +247,173 @@ impl ClusterClient {
             topology: Arc::new(RwLock::new(ClusterTopology::new())),
             pool,
             storm_tracker: Arc::new(MovedStormTracker::new()),
+            preferred_seed: Arc::new(Mutex::new(None)),
+            counters: Arc::new(RedirectCounters::default()),
         };
 
+        client.ensure_cluster_mode().await?;
+
         // Discover cluster topology
         client.refresh_topology().await?;
 
         Ok(client)
     }
 
+    /// Checks that the first reachable seed runs with cluster mode enabled.
+    ///
+    /// A standalone server would otherwise yield an empty topology and confusing
+    /// routing errors later. Servers whose INFO lacks `cluster_enabled` pass.
+    async fn ensure_cluster_mode(&self) -> Result<()> {
+        for seed_addr in self.seed_nodes.iter() {
+            let Ok(conn) = connect_to_node(seed_addr).await else {
+                continue;
+            };
+            let info = match conn.send_cmd(&command::info("cluster")).await {
+                Ok(Frame::BulkString(Some(info))) => info,
+                Ok(_) => return Ok(()),
+                Err(_) => continue,
+            };
+            return match command::find_info_field(&info, "cluster_enabled").as_deref() {
+                Some("0") => Err(Error::InvalidArgument {
+                    message: "server is not in cluster mode".to_string(),
+                }),
+                _ => Ok(()),
+            };
+        }
+        // Let topology discovery report the unreachable seeds
+        Ok(())
+    }
+
+    /// Connects to a Redis Cluster and opens a connection to every master up front.
+    ///
+    /// Like [`connect`](Self::connect) followed by [`warmup`](Self::warmup), so the
+    /// first command to each node does not pay for connection setup.
+    ///
+    /// # Arguments
+    ///
+    /// * `addresses` - Seed node addresses (e.g., "redis://127.0.0.1:7000,127.0.0.1:7001")
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if topology discovery fails or a master cannot be reached.
+    pub async fn connect_warm(addresses: &str) -> Result<Self> {
+        let client = Self::connect(addresses).await?;
+        client.warmup().await?;
+        Ok(client)
+    }
+
+    /// Opens and pools a connection to every master in the current topology.
+    ///
+    /// Connections are otherwise opened lazily on the first command to a node.
+    /// Masters that already have a pooled connection are skipped, and connections
+    /// are opened concurrently. Replicas are not connected, as commands are only
+    /// routed to masters.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first connection error; connections opened before it stay pooled.
+    pub async fn warmup(&self) -> Result<()> {
+        let masters = self.masters().await;
+        futures::future::try_join_all(
+            masters
+                .into_iter()
+                .map(|(node_id, address)| self.pooled_connection(node_id, address)),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Removes every key of the cluster by sending FLUSHALL to each master.
+    ///
+    /// The masters of the current topology are flushed concurrently. Replicas are
+    /// skipped, as they replicate the flush from their master. A master that fails
+    /// does not stop the others from being flushed.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Whether memory is freed in the background (`ASYNC`) or before
+    ///   replying (`SYNC`); `None` uses the server's default.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the failed master if only one failed, otherwise an
+    /// [`Error::Server`] listing each failed master and its error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::{ClusterClient, FlushMode};
+    /// # async fn example() -> muxis::Result<()> {
+    /// let client = ClusterClient::connect("127.0.0.1:7000").await?;
+    /// client.flush_all(Some(FlushMode::Async)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn flush_all(&self, mode: Option<FlushMode>) -> Result<()> {
+        let masters = self.masters().await;
+        let results =
+            futures::future::join_all(masters.into_iter().map(|(node_id, address)| async move {
+                let result = async {
+                    let conn = self.pooled_connection(node_id, address.clone()).await?;
+                    match conn.send_command(flushall(mode).into_frame()).await? {
+                        Frame::Error(message) => Err(parse_redis_error(&message)),
+                        _ => Ok(()),
+                    }
+                }
+                .await;
+                (address, result)
+            }))
+            .await;
+
+        let mut failures: Vec<(String, Error)> = results
+            .into_iter()
+            .filter_map(|(address, result)| result.err().map(|e| (address, e)))
+            .collect();
+        match failures.len() {
+            0 => Ok(()),
+            1 => Err(failures.remove(0).1),
+            _ => {
+                failures.sort_by(|a, b| a.0.cmp(&b.0));
+                let details: Vec<String> = failures
+                    .iter()
+                    .map(|(address, e)| format!("{}: {}", address, e))
+                    .collect();
+                Err(Error::Server {
+                    message: format!(
+                        "FLUSHALL failed on {} masters: {}",
+                        failures.len(),
+                        details.join("; ")
+                    ),
+                })
+            }
+        }
+    }
+
+    /// Returns the address of each master in the current topology, by node ID.
+    async fn masters(&self) -> HashMap<NodeId, String> {
+        let topology = self.topology.read().await;
+        topology
+            .slot_ranges
+            .iter()
+            .map(|range| (range.master.id.clone(), range.master.address.clone()))
+            .collect()
+    }
+
+    /// Returns a snapshot of the pooled node connections.
+    pub async fn pool_stats(&self) -> PoolStats {
+        self.pool.stats().await
+    }
+
+    /// Returns the redirect and retry counters of this client.
+    pub fn stats(&self) -> ClusterStats {
+        ClusterStats {
+            moved_total: self.counters.moved.load(Ordering::Relaxed),
+            ask_total: self.counters.ask.load(Ordering::Relaxed),
+            io_retry_total: self.counters.io_retries.load(Ordering::Relaxed),
+            topology_refresh_total: self.counters.topology_refreshes.load(Ordering::Relaxed),
+        }
+    }
+
     /// Parses a comma-separated list of addresses into individual URLs.
     fn parse_addresses(addresses: &str) -> Result<Vec<String>> {
         let mut parsed = Vec::new();
@@ -206,30 +441,86 @@ impl ClusterClient {
 
     /// Refreshes the cluster topology from seed nodes.
     ///
-    /// This queries the cluster for slot distribution and node information.
+    /// This queries the cluster for slot distribution and node information. The
+    /// node that answered the previous refresh is asked first, then the seed nodes,
+    /// then every node of the current topology, so a refresh still succeeds after
+    /// all seeds went away as long as one known node is reachable.
     pub async fn refresh_topology(&self) -> Result<()> {
-        // Try each seed node until we get a successful topology
-        for seed_addr in self.seed_nodes.iter() {
-            if let Ok(topology) = self.fetch_topology_from_node(seed_addr).await {
-                let mut topo = self.topology.write().await;
-                *topo = topology;
-                // Reset storm tracker after successful refresh
-                self.storm_tracker.reset().await;
-                return Ok(());
+        for address in self.refresh_candidates().await {
+            match self.fetch_topology_from_node(&address).await {
+                Ok(topology) => {
+                    *self.topology.write().await = topology;
+                    *self.preferred_seed.lock().await = Some(address);
+                    self.counters
+                        .topology_refreshes
+                        .fetch_add(1, Ordering::Relaxed);
+                    // Reset storm tracker after successful refresh
+                    self.storm_tracker.reset().await;
+                    return Ok(());
+                }
+                Err(e) => tracing::debug!(%address, error = %e, "topology refresh failed"),
             }
         }
 
         Err(Error::Protocol {
-            message: "failed to refresh topology from any seed node".to_string(),
+            message: "failed to refresh topology from any seed or known node".to_string(),
         })
     }
 
+    /// Lists the nodes to ask for the topology, without duplicates: the preferred
+    /// seed, the configured seeds, then the nodes of the current topology.
+    async fn refresh_candidates(&self) -> Vec<String> {
+        let preferred = self.preferred_seed.lock().await.clone();
+        let known: Vec<String> = {
+            let topology = self.topology.read().await;
+            topology
+                .nodes
+                .values()
+                .map(|node| node.address.clone())
+                .collect()
+        };
+
+        let host_port = |address: &str| -> String {
+            address
+                .split_once("://")
+                .map_or(address, |(_, rest)| rest)
+                .to_string()
+        };
+        let mut seen = std::collections::HashSet::new();
+        preferred
+            .into_iter()
+            .chain(self.seed_nodes.iter().cloned())
+            .chain(known)
+            .filter(|address| seen.insert(host_port(address)))
+            .collect()
+    }
+
     /// Fetches topology from a specific node.
     async fn fetch_topology_from_node(&self, address: &str) -> Result<ClusterTopology> {
         // Connect to the node
         let conn = connect_to_node(address).await?;
 
-        // Execute CLUSTER SLOTS
+        // Prefer CLUSTER SHARDS (Redis 7.0+), which also reports node health
+        match conn.send_command(cluster_shards().into_frame()).await {
+            Ok(frame) if !matches!(frame, Frame::Error(_)) => {
+                match ClusterTopology::from_cluster_shards(frame) {
+                    Ok(topology) if !topology.slot_ranges.is_empty() => return Ok(topology),
+                    Ok(_) => tracing::debug!("CLUSTER SHARDS returned no slots on {}", address),
+                    Err(e) => {
+                        tracing::debug!("Failed to parse CLUSTER SHARDS from {}: {}", address, e)
+                    }
+                }
+            }
+            Ok(_) | Err(Error::Server { .. }) => {
+                tracing::debug!(
+                    "CLUSTER SHARDS unsupported on {}, using CLUSTER SLOTS",
+                    address
+                )
+            }
+            Err(e) => return Err(e),
+        }
+
+        // Fall back to CLUSTER SLOTS
         let slots_cmd = cluster_slots();
         let slots_frame = slots_cmd.into_frame();
         let response = conn.send_command(slots_frame).await?;
@@ -239,7 +530,7 @@ impl ClusterClient {
     }
 
     /// Gets or creates a connection to the node responsible for a given slot.
-    async fn get_connection_for_slot(&self, slot: u16) -> Result<MultiplexedConnection> {
+    pub(super) async fn get_connection_for_slot(&self, slot: u16) -> Result<MultiplexedConnection> {
         let topology = self.topology.read().await;
 
         // Find the master node for this slot
@@ -254,6 +545,15 @@ impl ClusterClient {
 
         drop(topology);
 
+        self.pooled_connection(node_id, address).await
+    }
+
+    /// Returns a pooled connection to a node, connecting and pooling one if needed.
+    async fn pooled_connection(
+        &self,
+        node_id: NodeId,
+        address: String,
+    ) -> Result<MultiplexedConnection> {
         // Try to get existing connection from pool
         if let Some(conn) = self.pool.get_connection(&node_id).await {
             return Ok(conn);
@@ -348,6 +648,8 @@ impl ClusterClient {
     /// Additionally provides resilience features:
     /// - MOVED storm detection: Throttles topology refreshes during migrations
     /// - IO error retry: Automatically retries on connection failures with exponential backoff
+    /// - CLUSTERDOWN retry: Waits with the same backoff while the cluster recovers
+    /// - TRYAGAIN retry: Retries after a short delay while a slot migration completes
     /// - Node failure handling: Marks unhealthy connections and refreshes topology
     ///
     /// # Arguments
@@ -366,9 +668,21 @@ impl ClusterClient {
     /// - Maximum retry count exceeded
     /// - Connection fails after all retries
     /// - Command execution fails
-    async fn execute_with_redirects(&self, frame: Frame, slot: u16) -> Result<Frame> {
+    pub(super) async fn execute_with_redirects(&self, frame: Frame, slot: u16) -> Result<Frame> {
+        let (response, _) = self.execute_with_redirects_on(frame, slot).await?;
+        Ok(response)
+    }
+
+    /// Like [`execute_with_redirects`](Self::execute_with_redirects), also returning
+    /// the connection that finally executed the command.
+    async fn execute_with_redirects_on(
+        &self,
+        frame: Frame,
+        slot: u16,
+    ) -> Result<(Frame, MultiplexedConnection)> {
         let mut redirects = 0;
         let mut io_retries = 0;
+        let mut tryagain_retries = 0;
         let current_frame = frame;
 
         loop {
@@ -380,6 +694,7 @@ impl ClusterClient {
                 Err(Error::Io { source }) => {
                     // IO error getting connection - likely node down
                     io_retries += 1;
+                    self.counters.io_retries.fetch_add(1, Ordering::Relaxed);
                     if io_retries > MAX_RETRIES_ON_IO {
                         return Err(Error::Io { source });
                     }
@@ -397,109 +712,111 @@ impl ClusterClient {
                 Err(e) => return Err(e),
             };
 
-            // Execute command
-            let result = conn.send_command(current_frame.clone()).await;
+            // Execute command, turning server errors into typed cluster errors
+            let result = send_mapped(&conn, current_frame.clone()).await;
 
             match result {
-                Ok(response) => return Ok(response),
-                Err(Error::Server { message }) => {
-                    // Parse the error to check for redirects
-                    let error = parse_redis_error(message.as_bytes());
-
-                    match error {
-                        Error::Moved {
-                            slot: _new_slot,
-                            address,
-                        } => {
-                            // MOVED redirect: permanent slot migration
-                            redirects += 1;
-                            if redirects > MAX_REDIRECTS {
-                                return Err(Error::Protocol {
-                                    message: format!(
-                                        "exceeded maximum redirects ({})",
-                                        MAX_REDIRECTS
-                                    ),
-                                });
-                            }
-
-                            // Check if we should refresh topology (storm detection)
-                            if self.storm_tracker.should_refresh().await {
-                                tracing::debug!(
-                                    "MOVED storm detected, refreshing topology (threshold: {})",
-                                    MOVED_STORM_THRESHOLD
-                                );
-                                if let Err(e) = self.refresh_topology().await {
-                                    tracing::warn!("Failed to refresh topology after MOVED: {}", e);
-                                }
-                            } else {
-                                tracing::trace!(
-                                    "MOVED redirect to {} for slot {}, not refreshing yet",
-                                    address,
-                                    _new_slot
-                                );
-                            }
-
-                            // Retry with updated topology (loop will use slot routing)
-                            continue;
+                Ok(response) => return Ok((response, conn)),
+                Err(Error::Moved {
+                    slot: _new_slot,
+                    address,
+                }) => {
+                    // MOVED redirect: permanent slot migration
+                    redirects += 1;
+                    self.counters.moved.fetch_add(1, Ordering::Relaxed);
+                    if redirects > MAX_REDIRECTS {
+                        return Err(Error::Protocol {
+                            message: format!("exceeded maximum redirects ({})", MAX_REDIRECTS),
+                        });
+                    }
+
+                    // Check if we should refresh topology (storm detection)
+                    if self.storm_tracker.should_refresh().await {
+                        tracing::debug!(
+                            "MOVED storm detected, refreshing topology (threshold: {})",
+                            MOVED_STORM_THRESHOLD
+                        );
+                        if let Err(e) = self.refresh_topology().await {
+                            tracing::warn!("Failed to refresh topology after MOVED: {}", e);
                         }
-                        Error::Ask {
-                            slot: _ask_slot,
+                    } else {
+                        tracing::trace!(
+                            "MOVED redirect to {} for slot {}, not refreshing yet",
                             address,
-                        } => {
-                            // ASK redirect: temporary migration, use ASKING
-                            redirects += 1;
-                            if redirects > MAX_REDIRECTS {
-                                return Err(Error::Protocol {
-                                    message: format!(
-                                        "exceeded maximum redirects ({})",
-                                        MAX_REDIRECTS
-                                    ),
-                                });
-                            }
-
-                            // Get connection to the ASK address
-                            let ask_conn = self.get_connection_for_address(&address).await?;
-
-                            // Send ASKING command
-                            let asking_cmd = asking();
-                            ask_conn.send_command(asking_cmd.into_frame()).await?;
-
-                            // Retry the command on the ASK node
-                            return ask_conn.send_command(current_frame).await;
-                        }
-                        _ => {
-                            // Other errors: return as-is
-                            return Err(error);
+                            _new_slot
+                        );
+                    }
+
+                    // Retry with updated topology (loop will use slot routing)
+                    continue;
+                }
+                Err(Error::Ask {
+                    slot: _ask_slot,
+                    address,
+                }) => {
+                    // ASK redirect: temporary migration, use ASKING
+                    redirects += 1;
+                    self.counters.ask.fetch_add(1, Ordering::Relaxed);
+                    if redirects > MAX_REDIRECTS {
+                        return Err(Error::Protocol {
+                            message: format!("exceeded maximum redirects ({})", MAX_REDIRECTS),
+                        });
+                    }
+
+                    // Get connection to the ASK address
+                    let ask_conn = self.get_connection_for_address(&address).await?;
+
+                    // Send ASKING, then retry the command on the ASK node
+                    let result = match send_mapped(&ask_conn, asking().into_frame()).await {
+                        Ok(_) => send_mapped(&ask_conn, current_frame.clone()).await,
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(response) => return Ok((response, ask_conn)),
+                        // The importing node sends TRYAGAIN while a multi-key
+                        // command's keys are split; start over from the source node
+                        Err(Error::TryAgain) => {
+                            wait_for_tryagain(slot, &mut tryagain_retries).await?;
+                            continue;
                         }
+                        Err(e) => return Err(e),
                     }
                 }
-                Err(Error::Io { source }) => {
-                    // IO error during command execution - connection failure
+                Err(Error::TryAgain) => {
+                    // Keys are split by an ongoing migration; no topology change needed
+                    wait_for_tryagain(slot, &mut tryagain_retries).await?;
+                    continue;
+                }
+                Err(error) if error.is_retryable() => {
+                    // Transient failure (connection reset, timeout, failover, CLUSTERDOWN)
                     io_retries += 1;
+                    self.counters.io_retries.fetch_add(1, Ordering::Relaxed);
                     if io_retries > MAX_RETRIES_ON_IO {
-                        return Err(Error::Io { source });
+                        return Err(error);
                     }
 
                     tracing::warn!(
-                        "IO error on slot {}, retry {}/{}: {}",
+                        "Transient error on slot {}, retry {}/{}: {}",
                         slot,
                         io_retries,
                         MAX_RETRIES_ON_IO,
-                        source
+                        error
                     );
 
                     // Mark connection as unhealthy in pool
                     // (Pool will filter it out on next get_connection)
-                    let topology = self.topology.read().await;
-                    if let Some(master) = topology.get_master_for_slot(slot) {
-                        self.pool.mark_unhealthy(&master.id, &master.address).await;
-                        tracing::debug!("Marked node {} as unhealthy", master.address);
+                    if matches!(error, Error::Io { .. }) {
+                        let topology = self.topology.read().await;
+                        if let Some(master) = topology.get_master_for_slot(slot) {
+                            self.pool.mark_unhealthy(&master.id, &master.address).await;
+                            tracing::debug!("Marked node {} as unhealthy", master.address);
+                        }
+                        drop(topology);
                     }
-                    drop(topology);
 
                     // Refresh topology to discover new master
                     if let Err(e) = self.refresh_topology().await {
-                        tracing::warn!("Failed to refresh topology after IO error: {}", e);
+                        tracing::warn!("Failed to refresh topology after transient error: {}", e);
                     }
 
                     // Exponential backoff
@@ -512,6 +829,25 @@ impl ClusterClient {
         }
     }
 
+    /// Returns the cached cluster topology.
+    pub(super) fn topology(&self) -> &RwLock<ClusterTopology> {
+        &self.topology
+    }
+
+    /// Creates a pipeline whose commands are grouped by owning node.
+    ///
+    /// See [`ClusterPipeline`] for details.
+    pub fn pipeline(&self) -> ClusterPipeline<'_> {
+        ClusterPipeline::new(self)
+    }
+
+    /// Returns a snapshot of the current cluster topology.
+    ///
+    /// The snapshot does not change when the client later refreshes its view.
+    pub async fn topology_snapshot(&self) -> ClusterTopology {
+        self.topology.read().await.clone()
+    }
+
     /// Returns the number of known nodes in the cluster.
     pub async fn node_count(&self) -> usize {
         let topology = self.topology.read().await;
@@ -529,10 +865,8 @@ impl ClusterClient {
         let topology = self.topology.read().await;
         let mut covered = vec![false; SLOT_COUNT as usize];
 
-        for range in &topology.slot_ranges {
-            for slot in range.start..=range.end {
-                covered[slot as usize] = true;
-            }
+        for slot in topology.slot_ranges.iter().flat_map(SlotRange::slots) {
+            covered[slot as usize] = true;
         }
 
         covered.iter().all(|&c| c)
@@ -580,6 +914,84 @@ impl ClusterClient {
         }
     }
 
+    /// Gets the values of several keys, which may live on different nodes.
+    ///
+    /// When all keys hash to the same slot, e.g. because they share a hash tag
+    /// such as `{user:42}`, a single native MGET is sent to that slot's master.
+    /// Otherwise the keys are split by slot and one MGET per slot is sent, grouped
+    /// by node in a [`ClusterPipeline`]. Redirects are handled either way.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to retrieve
+    ///
+    /// # Returns
+    ///
+    /// One value per key, in the same order as `keys`; `None` for missing keys.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "cluster")]
+    /// # {
+    /// # use muxis::ClusterClient;
+    /// # async fn example() -> muxis::Result<()> {
+    /// let client = ClusterClient::connect("127.0.0.1:7000").await?;
+    ///
+    /// // Co-located keys: a single MGET
+    /// let profile = client.mget(&["{user:42}:name", "{user:42}:email"]).await?;
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub async fn mget(&self, keys: &[&str]) -> Result<Vec<Option<Bytes>>> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        if let Ok(slot) = Self::validate_same_slot(keys) {
+            let cmd = command::mget(keys.iter().map(|k| Bytes::copy_from_slice(k.as_bytes())));
+            let frame = self.execute_with_redirects(cmd.into_frame(), slot).await?;
+            return command::frame_to_vec_bytes(frame);
+        }
+
+        // Positions of the keys of each slot, in order of first appearance
+        let mut by_slot: Vec<(u16, Vec<usize>)> = Vec::new();
+        for (index, key) in keys.iter().enumerate() {
+            let slot = key_slot(key);
+            match by_slot.iter_mut().find(|(s, _)| *s == slot) {
+                Some((_, indices)) => indices.push(index),
+                None => by_slot.push((slot, vec![index])),
+            }
+        }
+
+        let mut pipeline = self.pipeline();
+        for (_, indices) in &by_slot {
+            let group = indices
+                .iter()
+                .map(|&i| Bytes::copy_from_slice(keys[i].as_bytes()));
+            pipeline = pipeline.cmd(keys[indices[0]], command::mget(group));
+        }
+        let replies = pipeline.execute().await?;
+
+        let mut values = vec![None; keys.len()];
+        for ((_, indices), reply) in by_slot.into_iter().zip(replies) {
+            let group_values = match reply {
+                Frame::Error(message) => return Err(parse_redis_error(&message)),
+                reply => command::frame_to_vec_bytes(reply)?,
+            };
+            if group_values.len() != indices.len() {
+                return Err(Error::Protocol {
+                    message: "MGET returned a wrong number of values".to_string(),
+                });
+            }
+            for (index, value) in indices.into_iter().zip(group_values) {
+                values[index] = value;
+            }
+        }
+        Ok(values)
+    }
+
     /// Sets a string value in Redis.
     ///
     /// This method automatically handles MOVED and ASK redirects.
@@ -610,6 +1022,71 @@ impl ClusterClient {
         Ok(())
     }
 
+    /// Sets a key and waits until enough replicas acknowledged the write (SET + WAIT).
+    ///
+    /// The SET is routed to the master owning the key's slot, following redirects,
+    /// and WAIT is then sent on the very connection that executed it.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to set
+    /// * `value` - The value to store
+    /// * `numreplicas` - The number of replicas that must acknowledge the write
+    /// * `timeout_ms` - How long to wait for acknowledgements, in milliseconds (0 waits
+    ///   forever)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NotReplicated` if fewer than `numreplicas` replicas acknowledged
+    /// the write in time. The value has been written to the master regardless and may
+    /// still be lost on failover.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "cluster")]
+    /// # {
+    /// # use muxis::ClusterClient;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> muxis::Result<()> {
+    /// let client = ClusterClient::connect("127.0.0.1:7000").await?;
+    /// client.set_durable("order:42", Bytes::from("paid"), 1, 100).await?;
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub async fn set_durable(
+        &self,
+        key: &str,
+        value: Bytes,
+        numreplicas: u64,
+        timeout_ms: u64,
+    ) -> Result<()> {
+        let slot = key_slot(key);
+        let cmd = command::set(key.to_string(), value);
+        let (_, conn) = self
+            .execute_with_redirects_on(cmd.into_frame(), slot)
+            .await?;
+
+        let frame = conn
+            .send_command(wait(numreplicas, timeout_ms).into_frame())
+            .await?;
+        let acked = u64::try_from(command::frame_to_int(frame)?).unwrap_or(0);
+        if acked < numreplicas {
+            tracing::debug!(
+                key,
+                acked,
+                numreplicas,
+                "write not acknowledged by enough replicas"
+            );
+            return Err(Error::NotReplicated {
+                acked,
+                required: numreplicas,
+            });
+        }
+        Ok(())
+    }
+
     /// Deletes a key from Redis.
     ///
     /// This method automatically handles MOVED and ASK redirects.
@@ -679,7 +1156,7 @@ impl ClusterClient {
     /// ```
     pub async fn exists(&self, key: &str) -> Result<bool> {
         let slot = key_slot(key);
-        let cmd = crate::core::command::exists(vec![key.to_string()]);
+        let cmd = crate::core::command::exists([key.to_string()]);
         let frame = self.execute_with_redirects(cmd.into_frame(), slot).await?;
 
         match frame {
@@ -690,50 +1167,313 @@ impl ClusterClient {
         }
     }
 
-    /// Returns information about the cluster state (CLUSTER INFO).
+    /// Gets the value of a key and deletes it atomically (GETDEL).
     ///
-    /// Executes the command on a random node.
-    pub async fn cluster_info(&self) -> Result<String> {
-        let cmd = cluster_info();
-        // Pick a random node (seed node or from topology)
-        // For simplicity, use refresh_topology logic's seed node or first available
-        // But we want to use the pool.
-        // Let's pick slot 0.
-        let frame = self.execute_with_redirects(cmd.into_frame(), 0).await?;
-        match frame {
-            Frame::BulkString(Some(bytes)) => {
-                String::from_utf8(bytes.to_vec()).map_err(|e| Error::Protocol {
-                    message: format!("invalid utf8 in cluster info: {}", e),
-                })
-            }
-            _ => Err(Error::Protocol {
-                message: "unexpected response for CLUSTER INFO".to_string(),
-            }),
-        }
+    /// This method automatically handles MOVED and ASK redirects.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to get and delete
+    ///
+    /// # Returns
+    ///
+    /// Returns the value if the key existed, or None if it did not.
+    pub async fn getdel(&self, key: &str) -> Result<Option<Bytes>> {
+        let slot = key_slot(key);
+        let cmd = command::getdel(key.to_string());
+        let frame = self.execute_with_redirects(cmd.into_frame(), slot).await?;
+        command::frame_to_bytes(frame)
     }
 
-    /// Returns the cluster node configuration (CLUSTER NODES).
+    /// Gets the value of a key and updates its expiry atomically (GETEX).
     ///
-    /// Executes the command on a random node.
-    pub async fn cluster_nodes(&self) -> Result<String> {
-        let cmd = cluster_nodes();
-        // Pick slot 0
-        let frame = self.execute_with_redirects(cmd.into_frame(), 0).await?;
-        match frame {
-            Frame::BulkString(Some(bytes)) => {
-                String::from_utf8(bytes.to_vec()).map_err(|e| Error::Protocol {
-                    message: format!("invalid utf8 in cluster nodes: {}", e),
-                })
-            }
-            _ => Err(Error::Protocol {
-                message: "unexpected response for CLUSTER NODES".to_string(),
-            }),
+    /// This method automatically handles MOVED and ASK redirects.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to get
+    /// * `expiry` - New time to live, or `None` to remove any existing expiry
+    ///
+    /// # Returns
+    ///
+    /// Returns the value if the key exists, or None if it does not.
+    pub async fn getex(&self, key: &str, expiry: Option<Duration>) -> Result<Option<Bytes>> {
+        let slot = key_slot(key);
+        let cmd = command::getex(key.to_string(), expiry);
+        let frame = self.execute_with_redirects(cmd.into_frame(), slot).await?;
+        command::frame_to_bytes(frame)
+    }
+
+    /// Rejects switching the logical database.
+    ///
+    /// Redis Cluster only supports database 0, so SELECT with any other index is
+    /// rejected locally without contacting the cluster. Selecting database 0 is a no-op.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database index
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArgument` if `db` is not 0.
+    pub async fn select(&self, db: u8) -> Result<()> {
+        if db != 0 {
+            return Err(Error::InvalidArgument {
+                message: format!("SELECT {} is not allowed in cluster mode", db),
+            });
         }
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
+    /// Publishes a message to a channel (PUBLISH).
+    ///
+    /// Classic Pub/Sub messages are propagated to every node, so any node can accept
+    /// the PUBLISH. It is sent to the master owning the channel name's slot, which
+    /// spreads publishers across the cluster. Sharded Pub/Sub (`SPUBLISH`) instead
+    /// keeps a message on the shard owning the channel's slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to publish to
+    /// * `message` - The message payload
+    ///
+    /// # Returns
+    ///
+    /// The number of clients that received the message on the node it was sent to.
+    pub async fn publish(&self, channel: &str, message: Bytes) -> Result<i64> {
+        let cmd = command::publish(channel.to_string(), message);
+        let frame = self
+            .execute_with_redirects(cmd.into_frame(), key_slot(channel))
+            .await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Subscribes to one or more channels (SUBSCRIBE).
+    ///
+    /// Classic Pub/Sub messages reach every node, so a subscription on a single node
+    /// sees messages published anywhere in the cluster. A dedicated connection is
+    /// opened to the master owning the first channel's slot, outside the pool, since a
+    /// subscribed connection cannot serve other commands. Unlike sharded Pub/Sub
+    /// (`SSUBSCRIBE`), this subscription is not moved when slots migrate.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - The channels to subscribe to (at least one)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "cluster")]
+    /// # {
+    /// # use muxis::ClusterClient;
+    /// # async fn example() -> muxis::Result<()> {
+    /// let client = ClusterClient::connect("127.0.0.1:7000").await?;
+    /// let mut pubsub = client.subscribe(&["news"]).await?;
+    /// while let Some(message) = pubsub.next_message().await? {
+    ///     println!("{}: {:?}", message.channel, message.payload);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # }
+    /// ```
+    pub async fn subscribe(&self, channels: &[&str]) -> Result<PubSub> {
+        let first = channels.first().ok_or_else(|| Error::InvalidArgument {
+            message: "SUBSCRIBE requires at least one channel".to_string(),
+        })?;
+        let slot = key_slot(first);
+        let address = self
+            .topology
+            .read()
+            .await
+            .get_master_for_slot(slot)
+            .map(|master| master.address.clone())
+            .ok_or_else(|| Error::Protocol {
+                message: format!("no node found for slot {}", slot),
+            })?;
+
+        let conn = connect_to_node(&address).await?;
+        let channels: Vec<String> = channels.iter().map(|c| c.to_string()).collect();
+        let receiver = conn.subscribe(channels.clone()).await?;
+        let connector: Connector = Arc::new(move || {
+            let address = address.clone();
+            Box::pin(async move { connect_to_node(&address).await })
+        });
+        Ok(PubSub::new(receiver, channels, connector))
+    }
+
+    /// Executes a command on a specific node, bypassing slot routing.
+    ///
+    /// Intended for node-local admin commands such as `CLUSTER FORGET` or `DEBUG`.
+    /// Redirects are not followed: a MOVED or ASK reply is returned as
+    /// [`Error::Moved`] or [`Error::Ask`].
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The node address as `host:port`. Nodes outside the cached
+    ///   topology are connected to directly.
+    /// * `cmd` - The command to send.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArgument` if `address` is not `host:port` or `cmd` is
+    /// SUBSCRIBE, PSUBSCRIBE, SSUBSCRIBE or MONITOR, which would leave the shared
+    /// pooled connection unable to answer regular commands (use
+    /// [`subscribe`](Self::subscribe) instead). Returns an IO error if the node is
+    /// unreachable, or the error reply of the node.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::{ClusterClient, Cmd};
+    /// # async fn example() -> muxis::Result<()> {
+    /// let client = ClusterClient::connect("127.0.0.1:7000").await?;
+    /// let cmd = Cmd::new("CLUSTER").arg("MYID");
+    /// let id = client.execute_on_node("127.0.0.1:7001", cmd).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_on_node(&self, address: &str, cmd: Cmd) -> Result<Frame> {
+        let valid = address
+            .rsplit_once(':')
+            .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+        if !valid {
+            return Err(Error::InvalidArgument {
+                message: format!("invalid node address '{}', expected host:port", address),
+            });
+        }
+        if PUSH_MODE_COMMANDS
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(cmd.name()))
+        {
+            return Err(Error::InvalidArgument {
+                message: format!(
+                    "{} switches the connection into push mode and cannot be sent with execute_on_node",
+                    String::from_utf8_lossy(cmd.name()).to_uppercase()
+                ),
+            });
+        }
+
+        let conn = self.get_connection_for_address(address).await?;
+        match conn.send_command(cmd.into_frame()).await {
+            Ok(Frame::Error(message)) => Err(parse_redis_error(&message)),
+            Err(Error::Server { message }) => Err(parse_redis_error(message.as_bytes())),
+            other => other,
+        }
+    }
+
+    /// Returns information about the cluster state (CLUSTER INFO).
+    ///
+    /// Executes the command on a random node.
+    pub async fn cluster_info(&self) -> Result<String> {
+        let cmd = cluster_info();
+        // Pick a random node (seed node or from topology)
+        // For simplicity, use refresh_topology logic's seed node or first available
+        // But we want to use the pool.
+        // Let's pick slot 0.
+        let frame = self.execute_with_redirects(cmd.into_frame(), 0).await?;
+        match frame {
+            Frame::BulkString(Some(bytes)) => {
+                String::from_utf8(bytes.to_vec()).map_err(|e| Error::Protocol {
+                    message: format!("invalid utf8 in cluster info: {}", e),
+                })
+            }
+            _ => Err(Error::Protocol {
+                message: "unexpected response for CLUSTER INFO".to_string(),
+            }),
+        }
+    }
+
+    /// Returns the number of keys stored in a hash slot (CLUSTER COUNTKEYSINSLOT).
+    ///
+    /// Executes on the master owning the slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The hash slot number (0-16383)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArgument` if `slot` is out of range.
+    pub async fn count_keys_in_slot(&self, slot: u16) -> Result<i64> {
+        Self::check_slot(slot)?;
+        let cmd = cluster_countkeysinslot(slot);
+        let frame = self.execute_with_redirects(cmd.into_frame(), slot).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Returns up to `count` keys stored in a hash slot (CLUSTER GETKEYSINSLOT).
+    ///
+    /// Executes on the master owning the slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The hash slot number (0-16383)
+    /// * `count` - Maximum number of keys to return
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArgument` if `slot` is out of range.
+    pub async fn get_keys_in_slot(&self, slot: u16, count: u64) -> Result<Vec<String>> {
+        Self::check_slot(slot)?;
+        let cmd = cluster_getkeysinslot(slot, count);
+        let frame = self.execute_with_redirects(cmd.into_frame(), slot).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Returns the hash slot the server computes for a key (CLUSTER KEYSLOT).
+    ///
+    /// Useful for debugging routing mismatches: the result should always equal
+    /// [`key_slot`](crate::key_slot) for the same key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to hash
+    pub async fn server_keyslot(&self, key: &str) -> Result<u16> {
+        let cmd = cluster_keyslot(key.to_string());
+        let frame = self
+            .execute_with_redirects(cmd.into_frame(), key_slot(key))
+            .await?;
+        let slot = command::frame_to_int(frame)?;
+        u16::try_from(slot)
+            .ok()
+            .filter(|slot| *slot < SLOT_COUNT)
+            .ok_or_else(|| Error::Protocol {
+                message: format!("invalid slot {} in CLUSTER KEYSLOT reply", slot),
+            })
+    }
+
+    /// Rejects slot numbers outside of 0-16383.
+    fn check_slot(slot: u16) -> Result<()> {
+        if slot >= SLOT_COUNT {
+            return Err(Error::InvalidArgument {
+                message: format!("slot {} is out of range (0-{})", slot, SLOT_COUNT - 1),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the cluster node configuration (CLUSTER NODES).
+    ///
+    /// Executes the command on a random node.
+    pub async fn cluster_nodes(&self) -> Result<String> {
+        let cmd = cluster_nodes();
+        // Pick slot 0
+        let frame = self.execute_with_redirects(cmd.into_frame(), 0).await?;
+        match frame {
+            Frame::BulkString(Some(bytes)) => {
+                String::from_utf8(bytes.to_vec()).map_err(|e| Error::Protocol {
+                    message: format!("invalid utf8 in cluster nodes: {}", e),
+                })
+            }
+            _ => Err(Error::Protocol {
+                message: "unexpected response for CLUSTER NODES".to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
     use super::*;
 
     #[test]
@@ -781,6 +1521,8 @@ mod tests {
             topology: Arc::new(RwLock::new(ClusterTopology::new())),
             pool,
             storm_tracker: Arc::new(MovedStormTracker::new()),
+            preferred_seed: Arc::new(Mutex::new(None)),
+            counters: Arc::new(RedirectCounters::default()),
         };
 
         assert_eq!(client.node_count().await, 0);
@@ -796,11 +1538,912 @@ mod tests {
             topology: Arc::new(RwLock::new(ClusterTopology::new())),
             pool,
             storm_tracker: Arc::new(MovedStormTracker::new()),
+            preferred_seed: Arc::new(Mutex::new(None)),
+            counters: Arc::new(RedirectCounters::default()),
         };
 
         assert!(!client.is_fully_covered().await);
     }
 
+    #[tokio::test]
+    async fn test_cluster_client_rejects_select() {
+        let client = ClusterClient {
+            seed_nodes: Arc::new(vec!["redis://127.0.0.1:7000".to_string()]),
+            topology: Arc::new(RwLock::new(ClusterTopology::new())),
+            pool: Arc::new(ConnectionPool::new(PoolConfig::default())),
+            storm_tracker: Arc::new(MovedStormTracker::new()),
+            preferred_seed: Arc::new(Mutex::new(None)),
+            counters: Arc::new(RedirectCounters::default()),
+        };
+
+        assert!(client.select(0).await.is_ok());
+        assert!(matches!(
+            client.select(1).await,
+            Err(Error::InvalidArgument { .. })
+        ));
+    }
+
+    /// Spawns a mock cluster node answering every command with `handler`.
+    async fn spawn_mock_node<F>(handler: F) -> String
+    where
+        F: Fn(&[Frame]) -> Frame + Send + Sync + 'static,
+    {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handler = Arc::new(handler);
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let handler = handler.clone();
+                tokio::spawn(async move {
+                    let mut conn = Connection::new(socket);
+                    while let Ok(Frame::Array(args)) = conn.read_frame().await {
+                        if conn.write_frame(&handler(&args)).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        addr.to_string()
+    }
+
+    /// Builds a client whose topology splits the slots between two nodes.
+    fn client_with_two_masters(first: &str, second: &str) -> ClusterClient {
+        client_with_replica(first, None, second)
+    }
+
+    /// Like `client_with_two_masters`, with an optional replica of the first master.
+    fn client_with_replica(first: &str, replica: Option<&str>, second: &str) -> ClusterClient {
+        let node = |addr: &str, id: &str| {
+            let (host, port) = addr.rsplit_once(':').unwrap();
+            Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from(host.to_string()))),
+                Frame::Integer(port.parse().unwrap()),
+                Frame::BulkString(Some(Bytes::from(id.to_string()))),
+            ])
+        };
+        let mut first_range = vec![
+            Frame::Integer(0),
+            Frame::Integer(8191),
+            node(first, "node-a"),
+        ];
+        if let Some(replica) = replica {
+            first_range.push(node(replica, "node-r"));
+        }
+        let slots = Frame::Array(vec![
+            Frame::Array(first_range),
+            Frame::Array(vec![
+                Frame::Integer(8192),
+                Frame::Integer(16383),
+                node(second, "node-b"),
+            ]),
+        ]);
+
+        ClusterClient {
+            seed_nodes: Arc::new(vec![format!("redis://{}", first)]),
+            topology: Arc::new(RwLock::new(
+                ClusterTopology::from_cluster_slots(slots).unwrap(),
+            )),
+            pool: Arc::new(ConnectionPool::new(PoolConfig::default())),
+            storm_tracker: Arc::new(MovedStormTracker::new()),
+            preferred_seed: Arc::new(Mutex::new(None)),
+            counters: Arc::new(RedirectCounters::default()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_all_skips_replicas() {
+        let flushed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let spawn = |name: &'static str| {
+            let flushed = flushed.clone();
+            spawn_mock_node(move |args| {
+                if args[0].as_bytes() == Some(b"FLUSHALL") {
+                    assert_eq!(args[1].as_bytes(), Some(&b"ASYNC"[..]));
+                    flushed.lock().unwrap().push(name);
+                    Frame::SimpleString(b"OK".to_vec())
+                } else {
+                    Frame::Error(b"ERR unexpected command".to_vec())
+                }
+            })
+        };
+        let first = spawn("first").await;
+        let replica = spawn("replica").await;
+        let second = spawn("second").await;
+
+        let client = client_with_replica(&first, Some(&replica), &second);
+
+        client.flush_all(Some(FlushMode::Async)).await.unwrap();
+        let mut flushed = flushed.lock().unwrap().clone();
+        flushed.sort();
+        assert_eq!(flushed, vec!["first", "second"]);
+    }
+
+    #[tokio::test]
+    async fn test_flush_all_reports_failed_master() {
+        let first = spawn_mock_node(|_| Frame::SimpleString(b"OK".to_vec())).await;
+        let second = spawn_mock_node(|_| {
+            Frame::Error(b"ERR You can't write against a read only replica.".to_vec())
+        })
+        .await;
+        let client = client_with_two_masters(&first, &second);
+
+        let error = client.flush_all(None).await.unwrap_err();
+        assert!(
+            matches!(&error, Error::Server { message } if message.contains("read only replica")),
+            "{:?}",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connect_rejects_standalone_server() {
+        let node = spawn_mock_node(|args| match args[0].as_bytes() {
+            Some(b"INFO") => {
+                Frame::BulkString(Some(Bytes::from("# Cluster\r\ncluster_enabled:0\r\n")))
+            }
+            _ => Frame::Error(b"ERR This instance has cluster support disabled".to_vec()),
+        })
+        .await;
+
+        let err = ClusterClient::connect(&node).await.unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidArgument { message } if message == "server is not in cluster mode"),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refresh_topology_falls_back_to_known_nodes() {
+        // A port nothing listens on stands in for the dead seed
+        let dead = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let dead_addr = dead.local_addr().unwrap().to_string();
+        drop(dead);
+
+        let live = Arc::new(std::sync::OnceLock::<String>::new());
+        let advertised = live.clone();
+        let node = spawn_mock_node(move |args| match args[1].as_bytes() {
+            Some(b"SLOTS") => {
+                let addr = advertised.get().unwrap();
+                let (host, port) = addr.rsplit_once(':').unwrap();
+                Frame::Array(vec![Frame::Array(vec![
+                    Frame::Integer(0),
+                    Frame::Integer(16383),
+                    Frame::Array(vec![
+                        Frame::BulkString(Some(Bytes::from(host.to_string()))),
+                        Frame::Integer(port.parse().unwrap()),
+                        Frame::BulkString(Some(Bytes::from("node-live"))),
+                    ]),
+                ])])
+            }
+            _ => Frame::Error(b"ERR unknown subcommand".to_vec()),
+        })
+        .await;
+        live.set(node.clone()).unwrap();
+
+        // The seed is dead, but the current topology still knows a live node
+        let mut client = client_with_two_masters(&dead_addr, &node);
+        client.seed_nodes = Arc::new(vec![format!("redis://{}", dead_addr)]);
+
+        client.refresh_topology().await.unwrap();
+        assert_eq!(client.slot_range_count().await, 1);
+        assert_eq!(client.preferred_seed.lock().await.as_deref(), Some(&*node));
+        assert_eq!(client.refresh_candidates().await[0], node);
+    }
+
+    #[tokio::test]
+    async fn test_keys_in_slot_routed_to_owner() {
+        let first = spawn_mock_node(|args| match args[1].as_bytes() {
+            Some(b"COUNTKEYSINSLOT") => Frame::Integer(1),
+            _ => Frame::Array(vec![Frame::BulkString(Some(Bytes::from("on-a")))]),
+        })
+        .await;
+        let second = spawn_mock_node(|args| match args[1].as_bytes() {
+            Some(b"COUNTKEYSINSLOT") => Frame::Integer(2),
+            _ => Frame::Array(vec![Frame::BulkString(Some(Bytes::from("on-b")))]),
+        })
+        .await;
+        let client = client_with_two_masters(&first, &second);
+
+        assert_eq!(client.count_keys_in_slot(100).await.unwrap(), 1);
+        assert_eq!(client.count_keys_in_slot(9000).await.unwrap(), 2);
+        assert_eq!(
+            client.get_keys_in_slot(100, 10).await.unwrap(),
+            vec!["on-a"]
+        );
+        assert_eq!(
+            client.get_keys_in_slot(9000, 10).await.unwrap(),
+            vec!["on-b"]
+        );
+
+        assert!(matches!(
+            client.count_keys_in_slot(SLOT_COUNT).await,
+            Err(Error::InvalidArgument { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_getdel_getex_routed_to_owner() {
+        let reply = |name: &'static str| {
+            move |args: &[Frame]| match args[0].as_bytes() {
+                Some(b"GETDEL") | Some(b"GETEX") => Frame::BulkString(Some(Bytes::from(name))),
+                _ => Frame::Error(b"ERR unknown command".to_vec()),
+            }
+        };
+        let first = spawn_mock_node(reply("a")).await;
+        let second = spawn_mock_node(reply("b")).await;
+        let client = client_with_two_masters(&first, &second);
+
+        for key in ["foo", "bar", "user:{42}", "session:7"] {
+            let owner = if key_slot(key) < 8192 { "a" } else { "b" };
+            let expected = Some(Bytes::from(owner));
+            assert_eq!(client.getdel(key).await.unwrap(), expected);
+            assert_eq!(
+                client
+                    .getex(key, Some(Duration::from_secs(10)))
+                    .await
+                    .unwrap(),
+                expected
+            );
+            assert_eq!(client.getex(key, None).await.unwrap(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warmup_connects_every_master() {
+        let ok = |_: &[Frame]| Frame::SimpleString(b"OK".to_vec());
+        let first = spawn_mock_node(ok).await;
+        let second = spawn_mock_node(ok).await;
+        let client = client_with_two_masters(&first, &second);
+        assert_eq!(client.pool_stats().await.total_connections, 0);
+
+        client.warmup().await.unwrap();
+        let stats = client.pool_stats().await;
+        assert_eq!(stats.total_connections, client.node_count().await);
+        assert_eq!(stats.healthy_connections, 2);
+
+        // Already pooled nodes are not connected again
+        client.warmup().await.unwrap();
+        client.set("foo", Bytes::from("v")).await.unwrap();
+        assert_eq!(client.pool_stats().await.total_connections, 2);
+    }
+
+    #[tokio::test]
+    async fn test_set_durable_waits_on_slot_owner() {
+        let recorder = |log: Arc<std::sync::Mutex<Vec<Bytes>>>| {
+            move |args: &[Frame]| {
+                let name = Bytes::copy_from_slice(args[0].as_bytes().unwrap_or_default());
+                log.lock().unwrap().push(name);
+                match args[0].as_bytes() {
+                    Some(b"WAIT") => Frame::Integer(1),
+                    _ => Frame::SimpleString(b"OK".to_vec()),
+                }
+            }
+        };
+        let first_log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let second_log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let first = spawn_mock_node(recorder(first_log.clone())).await;
+        let second = spawn_mock_node(recorder(second_log.clone())).await;
+        let client = client_with_two_masters(&first, &second);
+
+        // "foo" is owned by the second node
+        assert!(key_slot("foo") >= 8192);
+        client
+            .set_durable("foo", Bytes::from("v"), 1, 100)
+            .await
+            .unwrap();
+        assert_eq!(
+            *second_log.lock().unwrap(),
+            vec![Bytes::from("SET"), Bytes::from("WAIT")]
+        );
+        assert!(first_log.lock().unwrap().is_empty());
+
+        let error = client
+            .set_durable("foo", Bytes::from("v"), 2, 100)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::NotReplicated {
+                acked: 1,
+                required: 2
+            }
+        ));
+        assert!(!error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_publish_routed_to_channel_owner() {
+        let reply = |receivers: i64| {
+            move |args: &[Frame]| match args[0].as_bytes() {
+                Some(b"PUBLISH") => Frame::Integer(receivers),
+                _ => Frame::Error(b"ERR unknown command".to_vec()),
+            }
+        };
+        let first = spawn_mock_node(reply(1)).await;
+        let second = spawn_mock_node(reply(2)).await;
+        let client = client_with_two_masters(&first, &second);
+
+        for channel in ["news", "alerts", "chat:{room}", "events"] {
+            let expected = if key_slot(channel) < 8192 { 1 } else { 2 };
+            assert_eq!(
+                client.publish(channel, Bytes::from("hello")).await.unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_uses_channel_owner() {
+        let subscribed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let subscribe_node = |name: &'static str| {
+            let subscribed = subscribed.clone();
+            move |args: &[Frame]| {
+                subscribed.lock().unwrap().push(name);
+                Frame::Array(vec![
+                    Frame::BulkString(Some(Bytes::from("subscribe"))),
+                    args[1].clone(),
+                    Frame::Integer(1),
+                ])
+            }
+        };
+        let first = spawn_mock_node(subscribe_node("a")).await;
+        let second = spawn_mock_node(subscribe_node("b")).await;
+        let client = client_with_two_masters(&first, &second);
+
+        for channel in ["news", "alerts"] {
+            let _pubsub = client.subscribe(&[channel]).await.unwrap();
+            let expected = if key_slot(channel) < 8192 { "a" } else { "b" };
+            assert_eq!(subscribed.lock().unwrap().pop(), Some(expected));
+        }
+
+        assert!(matches!(
+            client.subscribe(&[]).await,
+            Err(Error::InvalidArgument { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stats_count_moved_redirects() {
+        let gets = Arc::new(AtomicUsize::new(0));
+        let seen = gets.clone();
+        let first = spawn_mock_node(move |args| match args[0].as_bytes() {
+            Some(b"GET") if seen.fetch_add(1, Ordering::SeqCst) == 0 => {
+                Frame::Error(b"MOVED 100 127.0.0.1:1".to_vec())
+            }
+            Some(b"GET") => Frame::BulkString(Some(Bytes::from("value"))),
+            _ => Frame::Error(b"ERR unexpected command".to_vec()),
+        })
+        .await;
+        let second = spawn_mock_node(|_| Frame::Error(b"ERR unexpected node".to_vec())).await;
+        let client = client_with_two_masters(&first, &second);
+        let key = (0..)
+            .map(|i| format!("key:{}", i))
+            .find(|key| key_slot(key) < 8192)
+            .unwrap();
+
+        assert_eq!(client.stats().moved_total, 0);
+        assert_eq!(client.get(&key).await.unwrap(), Some(Bytes::from("value")));
+
+        let stats = client.stats();
+        assert_eq!(stats.moved_total, 1);
+        assert_eq!(stats.ask_total, 0);
+        assert_eq!(stats.io_retry_total, 0);
+        assert_eq!(client.clone().stats(), stats);
+    }
+
+    #[tokio::test]
+    async fn test_execute_on_node_targets_address() {
+        let reply = |name: &'static str| {
+            move |args: &[Frame]| match args[0].as_bytes() {
+                Some(b"DEBUG") => Frame::BulkString(Some(Bytes::from(name))),
+                _ => Frame::Error(b"MOVED 100 127.0.0.1:1".to_vec()),
+            }
+        };
+        let first = spawn_mock_node(reply("a")).await;
+        let second = spawn_mock_node(reply("b")).await;
+        let client = client_with_two_masters(&first, &second);
+
+        for (address, expected) in [(&first, "a"), (&second, "b"), (&first, "a")] {
+            let reply = client
+                .execute_on_node(address, Cmd::new("DEBUG").arg("SLEEP").arg("0"))
+                .await
+                .unwrap();
+            assert_eq!(reply, Frame::BulkString(Some(Bytes::from(expected))));
+        }
+
+        // Redirects are surfaced instead of followed
+        assert!(matches!(
+            client
+                .execute_on_node(&second, Cmd::new("GET").arg("k"))
+                .await,
+            Err(Error::Moved { slot: 100, .. })
+        ));
+
+        for address in ["", "127.0.0.1", ":7000", "127.0.0.1:port"] {
+            assert!(matches!(
+                client.execute_on_node(address, Cmd::new("PING")).await,
+                Err(Error::InvalidArgument { .. })
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_on_node_refuses_push_mode_commands() {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let first = spawn_mock_node(move |args| {
+            log.lock()
+                .unwrap()
+                .push(args[0].as_str().unwrap_or_default().into_owned());
+            Frame::SimpleString(b"OK".to_vec())
+        })
+        .await;
+        let client = client_with_two_masters(&first, &first);
+
+        for cmd in [
+            Cmd::new("SUBSCRIBE").arg("news"),
+            Cmd::new("psubscribe").arg("news.*"),
+            Cmd::new("SSUBSCRIBE").arg("news"),
+            Cmd::new("MONITOR"),
+        ] {
+            assert!(matches!(
+                client.execute_on_node(&first, cmd).await,
+                Err(Error::InvalidArgument { .. })
+            ));
+        }
+        assert!(received.lock().unwrap().is_empty());
+
+        assert_eq!(
+            client
+                .execute_on_node(&first, Cmd::new("PING"))
+                .await
+                .unwrap(),
+            Frame::SimpleString(b"OK".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pool_drops_connections_in_push_mode() {
+        let node = spawn_mock_node(|args| match args[0].as_bytes() {
+            Some(b"MONITOR") => Frame::SimpleString(b"OK".to_vec()),
+            _ => Frame::BulkString(Some(Bytes::from("value"))),
+        })
+        .await;
+        let client = client_with_two_masters(&node, &node);
+
+        assert_eq!(client.get("k").await.unwrap(), Some(Bytes::from("value")));
+        let node_id = if key_slot("k") < 8192 {
+            "node-a"
+        } else {
+            "node-b"
+        };
+        let pooled = client
+            .pool
+            .get_connection(&NodeId::new(node_id))
+            .await
+            .unwrap();
+        let _monitor = pooled.monitor().await.unwrap();
+        assert!(pooled.is_push_mode());
+
+        // The pooled connection no longer answers commands, so a new one is used
+        assert_eq!(client.get("k").await.unwrap(), Some(Bytes::from("value")));
+        let stats = client.pool_stats().await;
+        assert_eq!(stats.total_connections, 1);
+        assert_eq!(stats.healthy_connections, 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_topology_prefers_shards_and_falls_back_to_slots() {
+        let text = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        let shards_node = spawn_mock_node(move |args| match args[1].as_bytes() {
+            Some(b"SHARDS") => Frame::Array(vec![Frame::Array(vec![
+                text("slots"),
+                Frame::Array(vec![Frame::Integer(0), Frame::Integer(16383)]),
+                text("nodes"),
+                Frame::Array(vec![Frame::Array(vec![
+                    text("id"),
+                    text("shard-master"),
+                    text("port"),
+                    Frame::Integer(7000),
+                    text("ip"),
+                    text("10.0.0.1"),
+                    text("role"),
+                    text("master"),
+                    text("health"),
+                    text("online"),
+                ])]),
+            ])]),
+            _ => Frame::Error(b"ERR unexpected".to_vec()),
+        })
+        .await;
+        let slots_node = spawn_mock_node(move |args| match args[1].as_bytes() {
+            Some(b"SLOTS") => Frame::Array(vec![Frame::Array(vec![
+                Frame::Integer(0),
+                Frame::Integer(16383),
+                Frame::Array(vec![
+                    text("10.0.0.2"),
+                    Frame::Integer(7000),
+                    text("slots-master"),
+                ]),
+            ])]),
+            _ => Frame::Error(b"ERR unknown subcommand 'SHARDS'".to_vec()),
+        })
+        .await;
+        let client = client_with_two_masters(&shards_node, &slots_node);
+
+        let topology = client.fetch_topology_from_node(&shards_node).await.unwrap();
+        let master = topology.get_master_for_slot(0).unwrap();
+        assert_eq!(master.id.to_string(), "shard-master");
+        assert_eq!(master.address, "10.0.0.1:7000");
+
+        let topology = client.fetch_topology_from_node(&slots_node).await.unwrap();
+        let master = topology.get_master_for_slot(0).unwrap();
+        assert_eq!(master.id.to_string(), "slots-master");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_clusterdown_is_retried_until_recovered() {
+        let failures = Arc::new(AtomicUsize::new(2));
+        let remaining = failures.clone();
+        let node = spawn_mock_node(move |args| match args[0].as_bytes() {
+            Some(b"GET") => {
+                let left = remaining.load(Ordering::SeqCst);
+                if left > 0 {
+                    remaining.store(left - 1, Ordering::SeqCst);
+                    Frame::Error(b"CLUSTERDOWN The cluster is down".to_vec())
+                } else {
+                    Frame::BulkString(Some(Bytes::from("value")))
+                }
+            }
+            _ => Frame::Error(b"ERR unknown command".to_vec()),
+        })
+        .await;
+        let client = client_with_two_masters(&node, &node);
+
+        let started = tokio::time::Instant::now();
+        assert_eq!(client.get("key").await.unwrap(), Some(Bytes::from("value")));
+        assert_eq!(failures.load(Ordering::SeqCst), 0);
+        // Backed off 100ms, then 200ms
+        assert_eq!(started.elapsed(), Duration::from_millis(300));
+
+        // Gives up once the retry limit is exhausted
+        failures.store(usize::MAX, Ordering::SeqCst);
+        let started = tokio::time::Instant::now();
+        assert!(matches!(client.get("key").await, Err(Error::ClusterDown)));
+        assert_eq!(started.elapsed(), Duration::from_millis(700));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tryagain_after_ask_is_retried() {
+        let failures = Arc::new(AtomicUsize::new(1));
+        let remaining = failures.clone();
+        let askings = Arc::new(AtomicUsize::new(0));
+        let asked = askings.clone();
+        let target = spawn_mock_node(move |args| match args[0].as_bytes() {
+            Some(b"ASKING") => {
+                asked.fetch_add(1, Ordering::SeqCst);
+                Frame::SimpleString(b"OK".to_vec())
+            }
+            Some(b"MGET") if remaining.load(Ordering::SeqCst) > 0 => {
+                remaining.fetch_sub(1, Ordering::SeqCst);
+                Frame::Error(b"TRYAGAIN Multiple keys request during rehashing of slot".to_vec())
+            }
+            _ => Frame::Array(vec![Frame::BulkString(Some(Bytes::from("v")))]),
+        })
+        .await;
+        let slot = key_slot("{user}");
+        let ask = format!("ASK {} {}", slot, target).into_bytes();
+        let source = spawn_mock_node(move |_| Frame::Error(ask.clone())).await;
+        let client = client_with_two_masters(&source, &source);
+        let mget = Cmd::new("MGET")
+            .arg("{user}:a")
+            .arg("{user}:b")
+            .into_frame();
+
+        let started = tokio::time::Instant::now();
+        let reply = client.execute_with_redirects(mget, slot).await.unwrap();
+        assert_eq!(
+            reply,
+            Frame::Array(vec![Frame::BulkString(Some(Bytes::from("v")))])
+        );
+        assert_eq!(failures.load(Ordering::SeqCst), 0);
+        assert_eq!(askings.load(Ordering::SeqCst), 2);
+        assert_eq!(started.elapsed(), Duration::from_millis(TRYAGAIN_DELAY_MS));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tryagain_is_retried_until_migration_completes() {
+        let failures = Arc::new(AtomicUsize::new(2));
+        let remaining = failures.clone();
+        let topology_requests = Arc::new(AtomicUsize::new(0));
+        let cluster_commands = topology_requests.clone();
+        let node = spawn_mock_node(move |args| match args[0].as_bytes() {
+            Some(b"MGET") => {
+                let left = remaining.load(Ordering::SeqCst);
+                if left > 0 {
+                    remaining.store(left - 1, Ordering::SeqCst);
+                    Frame::Error(
+                        b"TRYAGAIN Multiple keys request during rehashing of slot".to_vec(),
+                    )
+                } else {
+                    Frame::Array(vec![Frame::BulkString(Some(Bytes::from("v")))])
+                }
+            }
+            _ => {
+                cluster_commands.fetch_add(1, Ordering::SeqCst);
+                Frame::Error(b"ERR unknown command".to_vec())
+            }
+        })
+        .await;
+        let client = client_with_two_masters(&node, &node);
+        let mget = || {
+            Cmd::new("MGET")
+                .arg("{user}:a")
+                .arg("{user}:b")
+                .into_frame()
+        };
+        let slot = key_slot("{user}");
+
+        let started = tokio::time::Instant::now();
+        let reply = client.execute_with_redirects(mget(), slot).await.unwrap();
+        assert_eq!(
+            reply,
+            Frame::Array(vec![Frame::BulkString(Some(Bytes::from("v")))])
+        );
+        assert_eq!(failures.load(Ordering::SeqCst), 0);
+        assert_eq!(topology_requests.load(Ordering::SeqCst), 0);
+        assert_eq!(
+            started.elapsed(),
+            Duration::from_millis(2 * TRYAGAIN_DELAY_MS)
+        );
+
+        // Gives up once the retry limit is exhausted
+        failures.store(usize::MAX, Ordering::SeqCst);
+        let started = tokio::time::Instant::now();
+        assert!(matches!(
+            client.execute_with_redirects(mget(), slot).await,
+            Err(Error::TryAgain)
+        ));
+        assert_eq!(
+            started.elapsed(),
+            Duration::from_millis(MAX_TRYAGAIN_RETRIES as u64 * TRYAGAIN_DELAY_MS)
+        );
+    }
+
+    /// Mock node replying `<name>:<key>` to GET and recording the keys it received.
+    async fn spawn_recording_node(
+        name: &'static str,
+    ) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let addr = spawn_mock_node(move |args| {
+            let key = args[1].as_str().unwrap_or_default().into_owned();
+            log.lock().unwrap().push(key.clone());
+            Frame::BulkString(Some(Bytes::from(format!("{}:{}", name, key))))
+        })
+        .await;
+        (addr, received)
+    }
+
+    /// Mock node replying `<name>:<key>` for each key of an MGET and recording
+    /// the keys of every MGET it received.
+    async fn spawn_mget_node(
+        name: &'static str,
+    ) -> (String, Arc<std::sync::Mutex<Vec<Vec<String>>>>) {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let addr = spawn_mock_node(move |args| {
+            let keys: Vec<String> = args[1..]
+                .iter()
+                .map(|arg| arg.as_str().unwrap_or_default().into_owned())
+                .collect();
+            let values = keys
+                .iter()
+                .map(|key| Frame::BulkString(Some(Bytes::from(format!("{}:{}", name, key)))))
+                .collect();
+            log.lock().unwrap().push(keys);
+            Frame::Array(values)
+        })
+        .await;
+        (addr, received)
+    }
+
+    #[tokio::test]
+    async fn test_mget_colocated_keys_single_command() {
+        let (first, first_received) = spawn_mget_node("a").await;
+        let (second, second_received) = spawn_mget_node("b").await;
+        let client = client_with_two_masters(&first, &second);
+
+        let keys = ["{user:42}:name", "{user:42}:email", "{user:42}:plan"];
+        let values = client.mget(&keys).await.unwrap();
+
+        let (owner, received) = if key_slot("{user:42}") < 8192 {
+            ("a", &first_received)
+        } else {
+            ("b", &second_received)
+        };
+        let expected: Vec<Option<Bytes>> = keys
+            .iter()
+            .map(|key| Some(Bytes::from(format!("{}:{}", owner, key))))
+            .collect();
+        assert_eq!(values, expected);
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![keys.iter().map(|key| key.to_string()).collect::<Vec<_>>()]
+        );
+        let total = first_received.lock().unwrap().len() + second_received.lock().unwrap().len();
+        assert_eq!(total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_mget_splits_keys_across_slots() {
+        let (first, first_received) = spawn_mget_node("a").await;
+        let (second, second_received) = spawn_mget_node("b").await;
+        let client = client_with_two_masters(&first, &second);
+
+        let keys = ["foo", "bar", "session:7", "{user:42}:name", "baz"];
+        let values = client.mget(&keys).await.unwrap();
+
+        let owner = |key: &str| if key_slot(key) < 8192 { "a" } else { "b" };
+        let expected: Vec<Option<Bytes>> = keys
+            .iter()
+            .map(|key| Some(Bytes::from(format!("{}:{}", owner(key), key))))
+            .collect();
+        assert_eq!(values, expected);
+
+        // One MGET per distinct slot, each sent to the owning node
+        let sent: Vec<Vec<String>> = first_received
+            .lock()
+            .unwrap()
+            .iter()
+            .chain(second_received.lock().unwrap().iter())
+            .cloned()
+            .collect();
+        assert_eq!(sent.len(), keys.len());
+        assert!(sent.iter().all(|group| group.len() == 1));
+
+        assert!(client.mget(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_groups_by_node_and_keeps_order() {
+        let (first, first_received) = spawn_recording_node("a").await;
+        let (second, second_received) = spawn_recording_node("b").await;
+        let client = client_with_two_masters(&first, &second);
+
+        let keys = ["foo", "bar", "session:7", "user:{42}", "baz"];
+        let mut pipeline = client.pipeline();
+        for key in keys {
+            pipeline = pipeline.cmd(key, command::get(key.to_string()));
+        }
+        assert_eq!(pipeline.len(), keys.len());
+        let replies = pipeline.execute().await.unwrap();
+
+        let owner = |key: &str| if key_slot(key) < 8192 { "a" } else { "b" };
+        let expected: Vec<Frame> = keys
+            .iter()
+            .map(|key| Frame::BulkString(Some(Bytes::from(format!("{}:{}", owner(key), key)))))
+            .collect();
+        assert_eq!(replies, expected);
+
+        let sent_to = |node: &str| -> Vec<String> {
+            keys.iter()
+                .filter(|key| owner(key) == node)
+                .map(|key| key.to_string())
+                .collect()
+        };
+        assert!(!sent_to("a").is_empty() && !sent_to("b").is_empty());
+        assert_eq!(*first_received.lock().unwrap(), sent_to("a"));
+        assert_eq!(*second_received.lock().unwrap(), sent_to("b"));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_follows_ask_redirect() {
+        let target = spawn_mock_node(|args| match args[0].as_bytes() {
+            Some(b"ASKING") => Frame::SimpleString(b"OK".to_vec()),
+            _ => Frame::BulkString(Some(Bytes::from("migrated"))),
+        })
+        .await;
+        let ask = format!("ASK 1 {}", target);
+        let source = spawn_mock_node(move |args| match args[1].as_bytes() {
+            Some(b"moving") => Frame::Error(ask.clone().into_bytes()),
+            _ => Frame::BulkString(Some(Bytes::from("stable"))),
+        })
+        .await;
+        // Both halves of the slot space are owned by the source node
+        let client = client_with_two_masters(&source, &source);
+
+        let replies = client
+            .pipeline()
+            .cmd("stable", command::get("stable".to_string()))
+            .cmd("moving", command::get("moving".to_string()))
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(
+            replies,
+            vec![
+                Frame::BulkString(Some(Bytes::from("stable"))),
+                Frame::BulkString(Some(Bytes::from("migrated"))),
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_pipeline_retries_only_failed_commands() {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let node = spawn_mock_node(move |args| {
+            let key = args[1].as_str().unwrap_or_default().into_owned();
+            let mut log = log.lock().unwrap();
+            log.push(key.clone());
+            match key.as_str() {
+                "{p}:migrating" if log.iter().filter(|k| *k == "{p}:migrating").count() == 1 => {
+                    Frame::Error(
+                        b"TRYAGAIN Multiple keys request during rehashing of slot".to_vec(),
+                    )
+                }
+                "{p}:split" => {
+                    Frame::Error(b"CROSSSLOT Keys in request don't hash to the same slot".to_vec())
+                }
+                _ => Frame::BulkString(Some(Bytes::from(key))),
+            }
+        })
+        .await;
+        // The hash tag keeps every command in one group, sent in order
+        let client = client_with_two_masters(&node, &node);
+
+        let replies = client
+            .pipeline()
+            .cmd("{p}:stable", command::get("{p}:stable".to_string()))
+            .cmd("{p}:migrating", command::get("{p}:migrating".to_string()))
+            .cmd("{p}:split", command::get("{p}:split".to_string()))
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(
+            replies,
+            vec![
+                Frame::BulkString(Some(Bytes::from("{p}:stable"))),
+                Frame::BulkString(Some(Bytes::from("{p}:migrating"))),
+                Frame::Error(b"CROSSSLOT Keys in request don't hash to the same slot".to_vec()),
+            ]
+        );
+        // Only the command answered with TRYAGAIN was sent again
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec!["{p}:stable", "{p}:migrating", "{p}:split", "{p}:migrating"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_empty() {
+        let client = client_with_two_masters("127.0.0.1:1", "127.0.0.1:2");
+        let pipeline = client.pipeline();
+        assert!(pipeline.is_empty());
+        assert!(pipeline.execute().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_server_keyslot() {
+        let node = spawn_mock_node(|args| match args[2].as_bytes() {
+            Some(b"bad") => Frame::Integer(20000),
+            Some(key) => Frame::Integer(key_slot(std::str::from_utf8(key).unwrap()) as i64),
+            None => Frame::Error(b"ERR syntax error".to_vec()),
+        })
+        .await;
+        let client = client_with_two_masters(&node, &node);
+
+        assert_eq!(
+            client.server_keyslot("user:{42}:name").await.unwrap(),
+            key_slot("user:{42}:name")
+        );
+        assert!(matches!(
+            client.server_keyslot("bad").await,
+            Err(Error::Protocol { .. })
+        ));
+    }
+
     #[tokio::test]
     async fn test_max_redirects_constant() {
         // Document expected redirect limits for reference
@@ -813,6 +2456,8 @@ mod tests {
             topology: Arc::new(RwLock::new(ClusterTopology::new())),
             pool,
             storm_tracker: Arc::new(MovedStormTracker::new()),
+            preferred_seed: Arc::new(Mutex::new(None)),
+            counters: Arc::new(RedirectCounters::default()),
         };
 
         // Test passes if we can create a client (constant is defined)
@@ -829,6 +2474,8 @@ mod tests {
             topology: Arc::new(RwLock::new(ClusterTopology::new())),
             pool,
             storm_tracker: Arc::new(MovedStormTracker::new()),
+            preferred_seed: Arc::new(Mutex::new(None)),
+            counters: Arc::new(RedirectCounters::default()),
         };
 
         // Should attempt to create connection even if address not in topology
@@ -902,6 +2549,8 @@ mod tests {
         assert_eq!(MOVED_STORM_THRESHOLD, 10);
         assert_eq!(MOVED_STORM_WINDOW, Duration::from_secs(1));
         assert_eq!(REFRESH_COOLDOWN, Duration::from_millis(500));
+        assert_eq!(MAX_TRYAGAIN_RETRIES, 5);
+        assert_eq!(TRYAGAIN_DELAY_MS, 20);
     }
 
     // Tests for MovedStormTracker
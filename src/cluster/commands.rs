@@ -4,6 +4,7 @@
 //! used for topology discovery and redirect handling.
 
 use crate::core::command::Cmd;
+use crate::core::FlushMode;
 
 /// Creates a CLUSTER SLOTS command.
 ///
@@ -17,6 +18,19 @@ pub fn cluster_slots() -> Cmd {
     Cmd::new("CLUSTER").arg("SLOTS")
 }
 
+/// Creates a CLUSTER SHARDS command (Redis 7.0+).
+///
+/// Returns the cluster grouped by shard: the slot ranges of each shard together with
+/// its master and replicas, including their role and health.
+///
+/// # Response Format
+///
+/// Returns an array of shards. Each shard is a map with `slots` (flat start/end
+/// pairs) and `nodes` (a map per node with `id`, `ip`, `port`, `role`, `health`, ...).
+pub fn cluster_shards() -> Cmd {
+    Cmd::new("CLUSTER").arg("SHARDS")
+}
+
 /// Creates a CLUSTER NODES command.
 ///
 /// Returns a list of all nodes in the cluster with their ID, address, flags,
@@ -25,6 +39,19 @@ pub fn cluster_nodes() -> Cmd {
     Cmd::new("CLUSTER").arg("NODES")
 }
 
+/// Creates a FLUSHALL command.
+///
+/// In a cluster it only clears the node it is sent to, so it has to be sent to
+/// every master.
+///
+/// # Arguments
+///
+/// * `mode` - `ASYNC` or `SYNC`, or `None` for the server's default
+///   (`lazyfree-lazy-user-flush`).
+pub fn flushall(mode: Option<FlushMode>) -> Cmd {
+    Cmd::new("FLUSHALL").arg_opt(mode.map(|m| m.as_str()))
+}
+
 /// Creates a CLUSTER INFO command.
 ///
 /// Returns information about the cluster state, including:
@@ -39,6 +66,46 @@ pub fn cluster_info() -> Cmd {
     Cmd::new("CLUSTER").arg("INFO")
 }
 
+/// Creates a CLUSTER COUNTKEYSINSLOT command.
+///
+/// Returns the number of keys stored in the given slot. Only the master
+/// owning the slot reports a meaningful count.
+///
+/// # Arguments
+///
+/// * `slot` - The hash slot number (0-16383)
+pub fn cluster_countkeysinslot(slot: u16) -> Cmd {
+    Cmd::new("CLUSTER")
+        .arg("COUNTKEYSINSLOT")
+        .arg(slot.to_string())
+}
+
+/// Creates a CLUSTER GETKEYSINSLOT command.
+///
+/// Returns up to `count` key names stored in the given slot.
+///
+/// # Arguments
+///
+/// * `slot` - The hash slot number (0-16383)
+/// * `count` - Maximum number of keys to return
+pub fn cluster_getkeysinslot(slot: u16, count: u64) -> Cmd {
+    Cmd::new("CLUSTER")
+        .arg("GETKEYSINSLOT")
+        .arg(slot.to_string())
+        .arg(count.to_string())
+}
+
+/// Creates a CLUSTER KEYSLOT command.
+///
+/// Returns the hash slot the server computes for the given key.
+///
+/// # Arguments
+///
+/// * `key` - The key to hash
+pub fn cluster_keyslot(key: impl Into<bytes::Bytes>) -> Cmd {
+    Cmd::new("CLUSTER").arg("KEYSLOT").arg(key)
+}
+
 /// Creates an ASKING command.
 ///
 /// Used before retrying a command that received an ASK redirect.
@@ -50,6 +117,17 @@ pub fn asking() -> Cmd {
     Cmd::new("ASKING")
 }
 
+/// Creates a WAIT command.
+///
+/// Blocks until `numreplicas` replicas acknowledged all earlier writes made on the
+/// connection, or until `timeout_ms` elapsed (0 blocks forever). Replies with the
+/// number of replicas that acknowledged.
+pub fn wait(numreplicas: u64, timeout_ms: u64) -> Cmd {
+    Cmd::new("WAIT")
+        .arg(numreplicas.to_string())
+        .arg(timeout_ms.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,6 +149,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cluster_shards_cmd() {
+        assert_eq!(
+            cluster_shards().into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from("CLUSTER"))),
+                Frame::BulkString(Some(Bytes::from("SHARDS"))),
+            ])
+        );
+    }
+
     #[test]
     fn test_cluster_nodes_cmd() {
         let cmd = cluster_nodes();
@@ -99,6 +188,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cluster_countkeysinslot_cmd() {
+        let frame = cluster_countkeysinslot(1234).into_frame();
+        assert_eq!(
+            frame,
+            Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from("CLUSTER"))),
+                Frame::BulkString(Some(Bytes::from("COUNTKEYSINSLOT"))),
+                Frame::BulkString(Some(Bytes::from("1234"))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cluster_getkeysinslot_cmd() {
+        let frame = cluster_getkeysinslot(1234, 10).into_frame();
+        assert_eq!(
+            frame,
+            Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from("CLUSTER"))),
+                Frame::BulkString(Some(Bytes::from("GETKEYSINSLOT"))),
+                Frame::BulkString(Some(Bytes::from("1234"))),
+                Frame::BulkString(Some(Bytes::from("10"))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cluster_keyslot_cmd() {
+        let frame = cluster_keyslot("user:{42}").into_frame();
+        assert_eq!(
+            frame,
+            Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from("CLUSTER"))),
+                Frame::BulkString(Some(Bytes::from("KEYSLOT"))),
+                Frame::BulkString(Some(Bytes::from("user:{42}"))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_wait_cmd() {
+        assert_eq!(
+            wait(2, 500).into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from("WAIT"))),
+                Frame::BulkString(Some(Bytes::from("2"))),
+                Frame::BulkString(Some(Bytes::from("500"))),
+            ])
+        );
+    }
+
     #[test]
     fn test_asking_cmd() {
         let cmd = asking();
@@ -4,7 +4,13 @@
 //! - `MOVED <slot> <host>:<port>` - Permanent redirect
 //! - `ASK <slot> <host>:<port>` - Temporary redirect during migration
 //! - `CLUSTERDOWN` - Cluster is unavailable
+//! - `READONLY` - Write sent to a replica (e.g., after a failover)
+//! - `TRYAGAIN` - Multi-key command during a slot migration
+//!
+//! Errors that also occur on standalone servers (`READONLY`, `LOADING`,
+//! `MASTERDOWN`) are mapped by the shared standalone parser.
 
+use crate::proto::error::parse_server_error;
 use crate::Error;
 
 /// Parses a Redis error message and converts cluster redirects to typed errors.
@@ -18,6 +24,11 @@ use crate::Error;
 /// - `Error::Moved` for MOVED redirects
 /// - `Error::Ask` for ASK redirects
 /// - `Error::ClusterDown` for CLUSTERDOWN errors
+/// - `Error::ReadOnly` for READONLY errors
+/// - `Error::TryAgain` for TRYAGAIN errors
+/// - `Error::Loading` for LOADING errors
+/// - `Error::MasterDown` for MASTERDOWN errors
+/// - `Error::Overflow` for integer overflows of INCR and friends
 /// - `Error::Server` for other errors
 ///
 /// # Examples
@@ -46,15 +57,18 @@ pub fn parse_redis_error(error_msg: &[u8]) -> Error {
         return Error::ClusterDown;
     }
 
+    // Check for TRYAGAIN
+    if msg.starts_with("TRYAGAIN") {
+        return Error::TryAgain;
+    }
+
     // Check for CROSSSLOT
     if msg.contains("CROSSSLOT") {
         return Error::CrossSlot;
     }
 
-    // Default: generic server error
-    Error::Server {
-        message: msg.to_string(),
-    }
+    // READONLY, LOADING, MASTERDOWN or a generic server error
+    parse_server_error(msg.as_bytes())
 }
 
 /// Parses redirect arguments: "<slot> <host>:<port>"
@@ -115,6 +129,32 @@ mod tests {
         assert!(matches!(error2, Error::ClusterDown));
     }
 
+    #[test]
+    fn test_parse_readonly() {
+        let error = parse_redis_error(b"READONLY You can't write against a read only replica.");
+        assert!(matches!(error, Error::ReadOnly));
+    }
+
+    #[test]
+    fn test_parse_loading() {
+        let error = parse_redis_error(b"LOADING Redis is loading the dataset in memory");
+        assert!(matches!(error, Error::Loading));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_parse_masterdown() {
+        let error = parse_redis_error(b"MASTERDOWN Link with MASTER is down");
+        assert!(matches!(error, Error::MasterDown));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_parse_tryagain() {
+        let error = parse_redis_error(b"TRYAGAIN Multiple keys request during rehashing of slot");
+        assert!(matches!(error, Error::TryAgain));
+    }
+
     #[test]
     fn test_parse_crossslot() {
         let error = parse_redis_error(b"CROSSSLOT Keys in request don't hash to the same slot");
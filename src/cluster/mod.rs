@@ -6,7 +6,7 @@
 //! ## Features
 //!
 //! - **Slot-based routing**: Automatically routes commands to correct nodes
-//! - **Topology discovery**: Uses CLUSTER SLOTS to map slots to nodes
+//! - **Topology discovery**: Uses CLUSTER SHARDS (or CLUSTER SLOTS) to map slots to nodes
 //! - **Redirect handling**: Handles MOVED and ASK redirects transparently
 //! - **Connection pooling**: Maintains connections to all cluster nodes
 //! - **Hash tags**: Supports Redis hash tags `{...}` for multi-key operations
@@ -41,9 +41,13 @@
 mod client;
 pub mod commands;
 mod errors;
+mod pipeline;
 mod pool;
 mod slot;
 mod topology;
 
-pub use client::ClusterClient;
+pub use client::{ClusterClient, ClusterStats};
+pub use pipeline::ClusterPipeline;
+pub use pool::PoolStats;
 pub use slot::key_slot;
+pub use topology::{ClusterTopology, NodeFlags, NodeId, NodeInfo, SlotRange};
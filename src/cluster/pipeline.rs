@@ -0,0 +1,215 @@
+//! Slot-aware pipelining for Redis Cluster.
+//!
+//! Commands are grouped by the master node owning their key's slot. Each group
+//! is sent to its node as a single pipeline, and the replies are reassembled in
+//! submission order.
+
+use std::collections::HashMap;
+
+use futures::future::{join_all, try_join_all};
+
+use crate::core::command::Cmd;
+use crate::core::{Error, Result};
+use crate::proto::frame::Frame;
+
+use super::client::ClusterClient;
+use super::errors::parse_redis_error;
+use super::slot::key_slot;
+
+/// A pipeline of commands for a [`ClusterClient`].
+///
+/// Created by [`ClusterClient::pipeline`]. Each command is recorded together with
+/// the key used to route it. On [`execute`](Self::execute), commands are grouped by
+/// owning node and every group is sent as one pipeline.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[cfg(feature = "cluster")]
+/// # {
+/// # use muxis::{ClusterClient, Cmd};
+/// # async fn example() -> muxis::Result<()> {
+/// let client = ClusterClient::connect("127.0.0.1:7000").await?;
+///
+/// let replies = client
+///     .pipeline()
+///     .cmd("user:1", Cmd::new("GET").arg("user:1"))
+///     .cmd("user:2", Cmd::new("INCR").arg("user:2"))
+///     .execute()
+///     .await?;
+/// assert_eq!(replies.len(), 2);
+/// # Ok(())
+/// # }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ClusterPipeline<'a> {
+    client: &'a ClusterClient,
+    commands: Vec<(u16, Frame)>,
+}
+
+impl<'a> ClusterPipeline<'a> {
+    pub(super) fn new(client: &'a ClusterClient) -> Self {
+        Self {
+            client,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Appends a command to the pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key used to route the command to its slot's master
+    /// * `cmd` - The command to send
+    pub fn cmd(mut self, key: &str, cmd: Cmd) -> Self {
+        self.commands.push((key_slot(key), cmd.into_frame()));
+        self
+    }
+
+    /// Returns the number of queued commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns true if no commands are queued.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Sends all queued commands and returns their replies in submission order.
+    ///
+    /// Commands are grouped by the master owning their slot, and the groups are sent
+    /// concurrently. Commands answered with MOVED or ASK, or that failed with a
+    /// retryable error (see [`Error::is_retryable`]), are re-sent individually with
+    /// redirect and retry handling; the rest of their group is not sent again. Other
+    /// error replies are returned in place as [`Frame::Error`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a slot has no known owner or a node cannot be reached.
+    pub async fn execute(self) -> Result<Vec<Frame>> {
+        let groups = self.group_by_node().await?;
+
+        let client = self.client;
+        let commands = &self.commands;
+        let group_replies = try_join_all(groups.into_iter().map(|indices| async move {
+            let results = match Self::send_group(client, commands, &indices).await {
+                Ok(results) => results,
+                // Nothing was sent; every command of the group is retried on its own
+                Err(error) if error.is_retryable() => indices.iter().map(|_| None).collect(),
+                Err(error) => return Err(error),
+            };
+
+            let mut replies = Vec::with_capacity(indices.len());
+            for (&index, result) in indices.iter().zip(results) {
+                let reply = match result {
+                    Some(Ok(Frame::Error(message))) if !needs_retry(&message) => {
+                        Frame::Error(message)
+                    }
+                    Some(Ok(Frame::Error(_))) | None => {
+                        let (slot, frame) = &commands[index];
+                        Self::execute_one(client, frame.clone(), *slot).await?
+                    }
+                    Some(Ok(reply)) => reply,
+                    // Retry only this command, which re-resolves its node
+                    Some(Err(error)) if error.is_retryable() => {
+                        let (slot, frame) = &commands[index];
+                        Self::execute_one(client, frame.clone(), *slot).await?
+                    }
+                    Some(Err(error)) => return Err(error),
+                };
+                replies.push(reply);
+            }
+            Ok::<_, Error>((indices, replies))
+        }))
+        .await?;
+
+        let mut responses: Vec<Option<Frame>> = vec![None; commands.len()];
+        for (indices, replies) in group_replies {
+            for (index, reply) in indices.into_iter().zip(replies) {
+                responses[index] = Some(reply);
+            }
+        }
+
+        responses
+            .into_iter()
+            .map(|reply| {
+                reply.ok_or_else(|| Error::Protocol {
+                    message: "missing reply in cluster pipeline".to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Groups command indices by the node owning their slot, preserving submission order.
+    async fn group_by_node(&self) -> Result<Vec<Vec<usize>>> {
+        let topology = self.client.topology().read().await;
+        let mut positions = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+
+        for (index, (slot, _)) in self.commands.iter().enumerate() {
+            let master = topology
+                .get_master_for_slot(*slot)
+                .ok_or_else(|| Error::Protocol {
+                    message: format!("no node found for slot {}", slot),
+                })?;
+            let position = *positions.entry(master.id.clone()).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[position].push(index);
+        }
+
+        Ok(groups)
+    }
+
+    /// Sends one node's commands back-to-back on one connection.
+    ///
+    /// Returns one result per command, in submission order, so a failed command
+    /// does not discard the replies of the others.
+    async fn send_group(
+        client: &ClusterClient,
+        commands: &[(u16, Frame)],
+        indices: &[usize],
+    ) -> Result<Vec<Option<Result<Frame>>>> {
+        let slot = commands[indices[0]].0;
+        let conn = client.get_connection_for_slot(slot).await?;
+        let results = join_all(
+            indices
+                .iter()
+                .map(|&index| conn.send_command(commands[index].1.clone())),
+        )
+        .await;
+        Ok(results.into_iter().map(Some).collect())
+    }
+
+    /// Executes a single command with redirect handling, keeping server errors in place.
+    ///
+    /// Errors decoded from an error reply (including retryable ones left once the
+    /// retries are used up) become [`Frame::Error`]; connection-level errors are
+    /// returned.
+    async fn execute_one(client: &ClusterClient, frame: Frame, slot: u16) -> Result<Frame> {
+        match client.execute_with_redirects(frame, slot).await {
+            Err(Error::Server { message }) => Ok(Frame::Error(message.into_bytes())),
+            Err(Error::Overflow) => Ok(Frame::Error(
+                format!("ERR {}", Error::Overflow).into_bytes(),
+            )),
+            Err(
+                error @ (Error::ReadOnly
+                | Error::Loading
+                | Error::MasterDown
+                | Error::ClusterDown
+                | Error::CrossSlot
+                | Error::TryAgain),
+            ) => Ok(Frame::Error(error.to_string().into_bytes())),
+            other => other,
+        }
+    }
+}
+
+/// Returns true if an error reply is a MOVED or ASK redirect, or a retryable error.
+fn needs_retry(message: &[u8]) -> bool {
+    let error = parse_redis_error(message);
+    matches!(error, Error::Moved { .. } | Error::Ask { .. }) || error.is_retryable()
+}
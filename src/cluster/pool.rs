@@ -29,6 +29,16 @@ impl Default for PoolConfig {
     }
 }
 
+/// A snapshot of the connections held by a cluster client's pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PoolStats {
+    /// Number of pooled connections, across all nodes.
+    pub total_connections: usize,
+    /// Number of pooled connections not marked unhealthy.
+    pub healthy_connections: usize,
+}
+
 /// A connection to a Redis node in the cluster.
 ///
 /// Wraps a MultiplexedConnection with additional metadata for tracking
@@ -155,9 +165,21 @@ impl ConnectionPool {
         }
     }
 
+    /// Returns the number of pooled connections.
+    pub async fn stats(&self) -> PoolStats {
+        let conns = self.connections.read().await;
+        let all = || conns.values().flatten();
+        PoolStats {
+            total_connections: all().count(),
+            healthy_connections: all().filter(|c| c.is_healthy()).count(),
+        }
+    }
+
     /// Gets a connection from the pool for the specified node.
     ///
-    /// Returns None if no healthy connection exists for this node.
+    /// Connections switched into SUBSCRIBE or MONITOR mode can no longer answer
+    /// regular commands and are dropped from the pool. Returns None if no healthy
+    /// connection exists for this node.
     ///
     /// # Arguments
     ///
@@ -165,6 +187,7 @@ impl ConnectionPool {
     pub async fn get_connection(&self, node_id: &NodeId) -> Option<MultiplexedConnection> {
         let mut conns = self.connections.write().await;
         if let Some(node_conns) = conns.get_mut(node_id) {
+            node_conns.retain(|c| !c.connection.is_push_mode());
             // Remove and return first healthy connection
             if let Some(pos) = node_conns.iter().position(|c| c.is_healthy()) {
                 let mut conn = node_conns.remove(pos);
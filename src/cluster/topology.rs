@@ -1,9 +1,10 @@
 //! Cluster topology data structures and parsers.
 //!
 //! This module provides types for representing Redis Cluster topology,
-//! including node information, slot ranges, and parsers for CLUSTER SLOTS
-//! and CLUSTER NODES responses.
+//! including node information, slot ranges, and parsers for CLUSTER SLOTS,
+//! CLUSTER SHARDS and CLUSTER NODES responses.
 
+use super::slot::SLOT_COUNT;
 use crate::core::{Error, Result};
 use crate::proto::frame::Frame;
 use std::collections::HashMap;
@@ -87,7 +88,19 @@ pub struct NodeInfo {
     pub slots: Vec<(u16, u16)>,
 }
 
-impl NodeInfo {}
+impl NodeInfo {
+    /// Returns true if the cluster bus link to this node is up.
+    pub fn is_connected(&self) -> bool {
+        self.link_state == "connected"
+    }
+
+    /// Returns true if this node is a replica that is not failing, still in
+    /// handshake or missing its address.
+    pub fn is_available_replica(&self) -> bool {
+        let flags = &self.flags;
+        flags.slave && !flags.fail && !flags.pfail && !flags.handshake && !flags.noaddr
+    }
+}
 
 /// A range of hash slots assigned to a node.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -111,6 +124,11 @@ impl SlotRange {
     pub fn contains(&self, slot: u16) -> bool {
         slot >= self.start && slot <= self.end
     }
+
+    /// Returns every slot number in this range, from `start` to `end` inclusive.
+    pub fn slots(&self) -> impl Iterator<Item = u16> {
+        self.start..=self.end
+    }
 }
 
 /// Complete cluster topology information.
@@ -149,6 +167,46 @@ impl ClusterTopology {
             .map(|range| &range.master)
     }
 
+    /// Returns the replicas of the slot's master that can serve reads.
+    ///
+    /// Replicas that are failing, or whose link is disconnected, are left out.
+    /// Empty if the slot is not covered or has no healthy replica.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The hash slot number (0-16383)
+    pub fn healthy_replicas_for_slot(&self, slot: u16) -> Vec<&NodeInfo> {
+        self.slot_ranges
+            .iter()
+            .find(|range| range.contains(slot))
+            .map(|range| {
+                range
+                    .replicas
+                    .iter()
+                    .filter(|replica| replica.is_available_replica() && replica.is_connected())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns every slot whose master is the given node, in ascending order.
+    ///
+    /// Empty if the node is unknown or is a replica.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the master node
+    pub fn all_slots_owned_by(&self, node_id: &NodeId) -> Vec<u16> {
+        let mut slots: Vec<u16> = self
+            .slot_ranges
+            .iter()
+            .filter(|range| &range.master.id == node_id)
+            .flat_map(SlotRange::slots)
+            .collect();
+        slots.sort_unstable();
+        slots
+    }
+
     /// Parses cluster topology from CLUSTER SLOTS response.
     ///
     /// # Arguments
@@ -226,6 +284,156 @@ impl ClusterTopology {
         Ok(topology)
     }
 
+    /// Parses cluster topology from CLUSTER SHARDS response (Redis 7.0+).
+    ///
+    /// Each shard contributes one [`SlotRange`] per slot range it owns, served by the
+    /// node whose role is `master`; the other nodes of the shard become its replicas.
+    /// Node health is reflected in [`NodeFlags::fail`]. Shards without a master (for
+    /// example while a failover is in progress) leave their slots uncovered.
+    ///
+    /// Both the RESP2 encoding (flat key/value arrays) and RESP3 maps are accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The Frame returned by CLUSTER SHARDS command
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The frame is not an array
+    /// - A shard lacks its `slots` or `nodes` entry
+    /// - A slot number or node entry is malformed
+    pub fn from_cluster_shards(frame: Frame) -> Result<Self> {
+        let mut topology = Self::new();
+
+        let shards = match frame {
+            Frame::Array(arr) => arr,
+            _ => {
+                return Err(Error::Protocol {
+                    message: "CLUSTER SHARDS response must be an array".to_string(),
+                })
+            }
+        };
+
+        for shard in &shards {
+            let slots = shard
+                .map_get("slots")
+                .and_then(Frame::as_array)
+                .ok_or_else(|| Error::Protocol {
+                    message: "CLUSTER SHARDS entry is missing slots".to_string(),
+                })?;
+            let node_frames = shard
+                .map_get("nodes")
+                .and_then(Frame::as_array)
+                .ok_or_else(|| Error::Protocol {
+                    message: "CLUSTER SHARDS entry is missing nodes".to_string(),
+                })?;
+
+            if slots.len() % 2 != 0 {
+                return Err(Error::Protocol {
+                    message: "CLUSTER SHARDS slots must be start/end pairs".to_string(),
+                });
+            }
+            let mut ranges = Vec::with_capacity(slots.len() / 2);
+            for pair in slots.chunks_exact(2) {
+                ranges.push((
+                    Self::parse_shard_slot(&pair[0])?,
+                    Self::parse_shard_slot(&pair[1])?,
+                ));
+            }
+
+            let mut master = None;
+            let mut replicas = Vec::new();
+            for node_frame in node_frames {
+                let node = Self::parse_node_from_shard(node_frame)?;
+                if node.flags.master && master.is_none() {
+                    master = Some(node);
+                } else {
+                    replicas.push(node);
+                }
+            }
+
+            let Some(mut master) = master else {
+                for replica in replicas {
+                    topology.nodes.insert(replica.id.clone(), replica);
+                }
+                continue;
+            };
+            master.slots = ranges.clone();
+            for replica in &mut replicas {
+                replica.master_id = Some(master.id.clone());
+            }
+
+            for (start, end) in ranges {
+                topology.slot_ranges.push(SlotRange {
+                    start,
+                    end,
+                    master: master.clone(),
+                    replicas: replicas.clone(),
+                });
+            }
+
+            topology.nodes.insert(master.id.clone(), master);
+            for replica in replicas {
+                topology.nodes.insert(replica.id.clone(), replica);
+            }
+        }
+
+        Ok(topology)
+    }
+
+    /// Parses a slot number from a CLUSTER SHARDS `slots` entry.
+    fn parse_shard_slot(frame: &Frame) -> Result<u16> {
+        frame
+            .as_i64()
+            .and_then(|n| u16::try_from(n).ok())
+            .filter(|slot| *slot < SLOT_COUNT)
+            .ok_or_else(|| Error::Protocol {
+                message: format!("invalid slot {:?} in CLUSTER SHARDS response", frame),
+            })
+    }
+
+    /// Parses a node from a CLUSTER SHARDS `nodes` entry.
+    fn parse_node_from_shard(frame: &Frame) -> Result<NodeInfo> {
+        let field = |name: &str| frame.map_get(name).and_then(Frame::as_str);
+
+        let id = field("id").ok_or_else(|| Error::Protocol {
+            message: "CLUSTER SHARDS node is missing its id".to_string(),
+        })?;
+        let host = field("ip")
+            .or_else(|| field("endpoint"))
+            .ok_or_else(|| Error::Protocol {
+                message: format!("CLUSTER SHARDS node {} is missing its ip", id),
+            })?;
+        let port = frame
+            .map_get("port")
+            .or_else(|| frame.map_get("tls-port"))
+            .and_then(Frame::as_i64)
+            .ok_or_else(|| Error::Protocol {
+                message: format!("CLUSTER SHARDS node {} is missing its port", id),
+            })?;
+
+        let master = field("role").is_some_and(|role| role == "master");
+        let flags = NodeFlags {
+            master,
+            slave: !master,
+            fail: field("health").is_some_and(|health| health == "failed"),
+            ..NodeFlags::default()
+        };
+
+        Ok(NodeInfo {
+            id: NodeId::new(id.into_owned()),
+            address: format!("{}:{}", host, port),
+            flags,
+            master_id: None,
+            ping_sent: 0,
+            pong_recv: 0,
+            config_epoch: 0,
+            link_state: "connected".to_string(),
+            slots: Vec::new(),
+        })
+    }
+
     /// Helper function to parse a node from a Frame array.
     fn parse_node_from_array(frame: &Frame) -> Result<NodeInfo> {
         let node_arr = match frame {
@@ -332,6 +540,109 @@ mod tests {
         assert!(!range.contains(5461));
     }
 
+    #[test]
+    fn test_slot_range_slots() {
+        let master = NodeInfo {
+            id: NodeId::new("node1"),
+            address: "127.0.0.1:7000".to_string(),
+            flags: NodeFlags::default(),
+            master_id: None,
+            ping_sent: 0,
+            pong_recv: 0,
+            config_epoch: 0,
+            link_state: "connected".to_string(),
+            slots: Vec::new(),
+        };
+        let range = |start, end| SlotRange {
+            start,
+            end,
+            master: master.clone(),
+            replicas: Vec::new(),
+        };
+
+        assert_eq!(range(10, 13).slots().collect::<Vec<_>>(), [10, 11, 12, 13]);
+        assert_eq!(range(42, 42).slots().collect::<Vec<_>>(), [42]);
+        assert_eq!(range(16380, 16383).slots().count(), 4);
+    }
+
+    #[test]
+    fn test_healthy_replicas_for_slot_skips_unhealthy() {
+        let node = |id: &str, flags: NodeFlags, link_state: &str| NodeInfo {
+            id: NodeId::new(id),
+            address: format!("127.0.0.1:{}", 7000 + id.len()),
+            flags,
+            master_id: None,
+            ping_sent: 0,
+            pong_recv: 0,
+            config_epoch: 0,
+            link_state: link_state.to_string(),
+            slots: Vec::new(),
+        };
+        let replica = NodeFlags {
+            slave: true,
+            ..NodeFlags::default()
+        };
+        let mut topology = ClusterTopology::new();
+        topology.slot_ranges.push(SlotRange {
+            start: 0,
+            end: 8191,
+            master: node(
+                "master",
+                NodeFlags {
+                    master: true,
+                    ..NodeFlags::default()
+                },
+                "connected",
+            ),
+            replicas: vec![
+                node("healthy", replica.clone(), "connected"),
+                node("disconnected", replica.clone(), "disconnected"),
+                node(
+                    "failing",
+                    NodeFlags {
+                        pfail: true,
+                        ..replica
+                    },
+                    "connected",
+                ),
+            ],
+        });
+
+        let healthy = topology.healthy_replicas_for_slot(100);
+        assert_eq!(healthy.len(), 1);
+        assert_eq!(healthy[0].id, NodeId::new("healthy"));
+        assert!(!topology.slot_ranges[0].replicas[1].is_connected());
+        assert!(topology.healthy_replicas_for_slot(9000).is_empty());
+    }
+
+    #[test]
+    fn test_cluster_topology_all_slots_owned_by() {
+        let node = |host_port: i64, id: &str| {
+            Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from("127.0.0.1"))),
+                Frame::Integer(host_port),
+                Frame::BulkString(Some(Bytes::from(id.to_string()))),
+            ])
+        };
+        let frame = Frame::Array(vec![
+            Frame::Array(vec![
+                Frame::Integer(100),
+                Frame::Integer(102),
+                node(7000, "a"),
+            ]),
+            Frame::Array(vec![Frame::Integer(0), Frame::Integer(1), node(7001, "b")]),
+            Frame::Array(vec![Frame::Integer(7), Frame::Integer(7), node(7000, "a")]),
+        ]);
+        let topology = ClusterTopology::from_cluster_slots(frame).unwrap();
+
+        assert_eq!(
+            topology.all_slots_owned_by(&NodeId::new("a")),
+            [7, 100, 101, 102]
+        );
+        assert_eq!(topology.all_slots_owned_by(&NodeId::new("b")), [0, 1]);
+        assert!(topology.all_slots_owned_by(&NodeId::new("c")).is_empty());
+    }
+
     #[test]
     fn test_cluster_topology_from_slots_simple() {
         // Simulate CLUSTER SLOTS response with one range
@@ -414,6 +725,139 @@ mod tests {
         assert!(topology.get_master_for_slot(16000).is_none());
     }
 
+    /// Builds a CLUSTER SHARDS node entry in its RESP2 (flat array) form.
+    fn shard_node(id: &str, port: i64, role: &str, health: &str) -> Frame {
+        let text = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        Frame::Array(vec![
+            text("id"),
+            text(id),
+            text("port"),
+            Frame::Integer(port),
+            text("ip"),
+            text("127.0.0.1"),
+            text("endpoint"),
+            text("127.0.0.1"),
+            text("role"),
+            text(role),
+            text("replication-offset"),
+            Frame::Integer(72156),
+            text("health"),
+            text(health),
+        ])
+    }
+
+    fn shard(slots: &[i64], nodes: Vec<Frame>) -> Frame {
+        Frame::Array(vec![
+            Frame::BulkString(Some(Bytes::from("slots"))),
+            Frame::Array(slots.iter().map(|n| Frame::Integer(*n)).collect()),
+            Frame::BulkString(Some(Bytes::from("nodes"))),
+            Frame::Array(nodes),
+        ])
+    }
+
+    #[test]
+    fn test_cluster_topology_from_shards() {
+        let frame = Frame::Array(vec![
+            shard(
+                &[0, 5460, 10923, 12000],
+                vec![
+                    shard_node("replica1", 7003, "replica", "online"),
+                    shard_node("master1", 7000, "master", "online"),
+                ],
+            ),
+            shard(
+                &[5461, 10922],
+                vec![
+                    shard_node("master2", 7001, "master", "online"),
+                    shard_node("replica2", 7004, "replica", "failed"),
+                ],
+            ),
+        ]);
+
+        let topology = ClusterTopology::from_cluster_shards(frame).unwrap();
+
+        assert_eq!(topology.slot_ranges.len(), 3);
+        assert_eq!(topology.nodes.len(), 4);
+
+        let master1 = topology.get_master_for_slot(11000).unwrap();
+        assert_eq!(master1.id, NodeId::new("master1"));
+        assert_eq!(master1.address, "127.0.0.1:7000");
+        assert!(master1.flags.master);
+        assert_eq!(master1.slots, vec![(0, 5460), (10923, 12000)]);
+        assert!(topology.get_master_for_slot(13000).is_none());
+
+        let range = topology
+            .slot_ranges
+            .iter()
+            .find(|range| range.contains(6000))
+            .unwrap();
+        assert_eq!(range.master.address, "127.0.0.1:7001");
+        assert_eq!(range.replicas.len(), 1);
+        let replica = &range.replicas[0];
+        assert_eq!(replica.address, "127.0.0.1:7004");
+        assert!(replica.flags.slave);
+        assert!(replica.flags.fail);
+        assert_eq!(replica.master_id, Some(NodeId::new("master2")));
+
+        let healthy = &topology.nodes[&NodeId::new("replica1")];
+        assert!(!healthy.flags.fail);
+    }
+
+    #[test]
+    fn test_cluster_topology_from_shards_without_master() {
+        let frame = Frame::Array(vec![shard(
+            &[0, 16383],
+            vec![shard_node("replica1", 7003, "replica", "online")],
+        )]);
+
+        let topology = ClusterTopology::from_cluster_shards(frame).unwrap();
+        assert!(topology.slot_ranges.is_empty());
+        assert_eq!(topology.nodes.len(), 1);
+    }
+
+    #[test]
+    fn test_cluster_topology_from_shards_rejects_slots_reply() {
+        // A CLUSTER SLOTS reply must not be mistaken for CLUSTER SHARDS
+        let frame = Frame::Array(vec![Frame::Array(vec![
+            Frame::Integer(0),
+            Frame::Integer(5460),
+            Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from("127.0.0.1"))),
+                Frame::Integer(7000),
+                Frame::BulkString(Some(Bytes::from("node1"))),
+            ]),
+        ])]);
+        assert!(ClusterTopology::from_cluster_shards(frame).is_err());
+
+        let bad_slot = Frame::Array(vec![shard(
+            &[0, 16384],
+            vec![shard_node("master1", 7000, "master", "online")],
+        )]);
+        assert!(ClusterTopology::from_cluster_shards(bad_slot).is_err());
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_cluster_topology_from_shards_resp3_maps() {
+        let to_map = |frame: Frame| match frame {
+            Frame::Array(items) => Frame::Map(
+                items
+                    .chunks_exact(2)
+                    .map(|pair| (pair[0].clone(), pair[1].clone()))
+                    .collect(),
+            ),
+            other => other,
+        };
+        let node = to_map(shard_node("master1", 7000, "master", "online"));
+        let shard = to_map(shard(&[0, 16383], vec![node]));
+
+        let topology = ClusterTopology::from_cluster_shards(Frame::Array(vec![shard])).unwrap();
+        assert_eq!(
+            topology.get_master_for_slot(16383).unwrap().address,
+            "127.0.0.1:7000"
+        );
+    }
+
     #[test]
     fn test_cluster_topology_invalid_frame() {
         let frame = Frame::SimpleString(b"invalid".to_vec());
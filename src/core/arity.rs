@@ -0,0 +1,149 @@
+//! Local arity table of well-known commands, used by
+//! [`ClientBuilder::validate_arity`](crate::ClientBuilder::validate_arity).
+
+use crate::proto::error::{Error, Result};
+
+/// Arities of well-known commands, as reported by `COMMAND INFO` on Redis 7.2.
+///
+/// The arity counts the command name itself. A positive value is the exact number
+/// of arguments, a negative one the minimum. Sorted by name for binary search.
+static ARITIES: &[(&str, i32)] = &[
+    ("APPEND", 3),
+    ("BITCOUNT", -2),
+    ("BITPOS", -3),
+    ("BLPOP", -3),
+    ("BRPOP", -3),
+    ("COPY", -3),
+    ("DBSIZE", 1),
+    ("DECR", 2),
+    ("DECRBY", 3),
+    ("DEL", -2),
+    ("ECHO", 2),
+    ("EVAL", -3),
+    ("EVALSHA", -3),
+    ("EXISTS", -2),
+    ("EXPIRE", -3),
+    ("EXPIREAT", -3),
+    ("FCALL", -3),
+    ("FCALL_RO", -3),
+    ("GET", 2),
+    ("GETDEL", 2),
+    ("GETEX", -2),
+    ("GETRANGE", 4),
+    ("GETSET", 3),
+    ("HDEL", -3),
+    ("HEXISTS", 3),
+    ("HGET", 3),
+    ("HGETALL", 2),
+    ("HINCRBY", 4),
+    ("HINCRBYFLOAT", 4),
+    ("HKEYS", 2),
+    ("HLEN", 2),
+    ("HMGET", -3),
+    ("HMSET", -4),
+    ("HSCAN", -3),
+    ("HSET", -4),
+    ("HSETNX", 4),
+    ("HVALS", 2),
+    ("INCR", 2),
+    ("INCRBY", 3),
+    ("INCRBYFLOAT", 3),
+    ("KEYS", 2),
+    ("LINDEX", 3),
+    ("LINSERT", 5),
+    ("LLEN", 2),
+    ("LMOVE", 5),
+    ("LPOP", -2),
+    ("LPUSH", -3),
+    ("LRANGE", 4),
+    ("LREM", 4),
+    ("LSET", 4),
+    ("LTRIM", 4),
+    ("MGET", -2),
+    ("MSET", -3),
+    ("MSETNX", -3),
+    ("PERSIST", 2),
+    ("PEXPIRE", -3),
+    ("PING", -1),
+    ("PSETEX", 4),
+    ("PTTL", 2),
+    ("PUBLISH", 3),
+    ("RENAME", 3),
+    ("RENAMENX", 3),
+    ("RPOP", -2),
+    ("RPUSH", -3),
+    ("SADD", -3),
+    ("SCAN", -2),
+    ("SCARD", 2),
+    ("SDIFF", -2),
+    ("SELECT", 2),
+    ("SET", -3),
+    ("SETEX", 4),
+    ("SETNX", 3),
+    ("SETRANGE", 4),
+    ("SINTER", -2),
+    ("SISMEMBER", 3),
+    ("SMEMBERS", 2),
+    ("SREM", -3),
+    ("STRLEN", 2),
+    ("SUBSCRIBE", -2),
+    ("SUNION", -2),
+    ("TTL", 2),
+    ("TYPE", 2),
+    ("UNLINK", -2),
+    ("XADD", -5),
+    ("XLEN", 2),
+    ("XRANGE", -4),
+    ("ZADD", -4),
+    ("ZCARD", 2),
+    ("ZCOUNT", 4),
+    ("ZINCRBY", 4),
+    ("ZRANGE", -4),
+    ("ZRANK", -3),
+    ("ZREM", -3),
+    ("ZREVRANGE", -4),
+    ("ZSCORE", 3),
+];
+
+/// Rejects a known command given fewer arguments than it needs.
+///
+/// `argc` counts the command name. Unknown commands are not checked.
+pub(crate) fn check(name: &[u8], argc: usize) -> Result<()> {
+    let upper = name.to_ascii_uppercase();
+    let Ok(index) = ARITIES.binary_search_by(|(known, _)| known.as_bytes().cmp(&upper)) else {
+        return Ok(());
+    };
+    let (known, arity) = ARITIES[index];
+    let required = arity.unsigned_abs() as usize;
+    if argc < required {
+        return Err(Error::InvalidArgument {
+            message: format!(
+                "wrong number of arguments for '{}': expected at least {}, got {}",
+                known,
+                required - 1,
+                argc.saturating_sub(1)
+            ),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arity_table_is_sorted() {
+        assert!(ARITIES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_check_arity() {
+        assert!(check(b"GET", 2).is_ok());
+        assert!(check(b"get", 1).is_err());
+        assert!(check(b"SET", 5).is_ok());
+        assert!(check(b"ZADD", 3).is_err());
+        assert!(check(b"PING", 1).is_ok());
+        assert!(check(b"MYMODULE.CMD", 1).is_ok());
+    }
+}
@@ -1,6 +1,11 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::{Client, Error};
+use crate::{AddressResolver, Client, Error};
+
+/// Counter appended to names set with [`ClientBuilder::connection_name_unique`].
+static CONNECTION_NAME_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 /// Builder for configuring and creating a [`Client`] connection.
 ///
@@ -27,12 +32,22 @@ pub struct ClientBuilder {
     username: Option<String>,
     database: Option<u8>,
     client_name: Option<String>,
+    advertise_lib: bool,
     connection_timeout: Option<Duration>,
     read_timeout: Option<Duration>,
     write_timeout: Option<Duration>,
+    keepalive_ping: Option<Duration>,
     tls: bool,
     queue_size: Option<usize>,
+    backpressure: Option<bool>,
+    strict_types: bool,
+    validate_arity: bool,
     max_frame_size: Option<usize>,
+    tcp_nodelay: Option<bool>,
+    write_buffer_size: Option<usize>,
+    resolver: Option<Arc<dyn AddressResolver>>,
+    #[cfg(feature = "tls")]
+    alpn_protocols: Vec<Vec<u8>>,
 }
 
 impl ClientBuilder {
@@ -46,7 +61,8 @@ impl ClientBuilder {
     ///
     /// # Arguments
     ///
-    /// * `address` - Redis address in format `redis://host:port` or `rediss://host:port` for TLS
+    /// * `address` - Redis address in format `redis://host:port`, or `rediss://host:port`
+    ///   (alias `redis+tls://host:port`) for TLS
     #[inline]
     pub fn address(mut self, address: impl Into<String>) -> Self {
         self.address = Some(address.into());
@@ -97,6 +113,38 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets a client connection name that is unique within the process.
+    ///
+    /// The name is `prefix` followed by the process id and a per-process counter,
+    /// e.g. `myapp-1234-7`, so clients sharing a prefix can still be told apart in
+    /// `CLIENT LIST`. Replaces any name set with [`client_name`](Self::client_name).
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Leading part of the name
+    #[inline]
+    pub fn connection_name_unique(mut self, prefix: &str) -> Self {
+        let id = CONNECTION_NAME_COUNTER.fetch_add(1, Ordering::Relaxed);
+        self.client_name = Some(format!("{}-{}-{}", prefix, std::process::id(), id));
+        self
+    }
+
+    /// Advertises the library name and version to the server on connect.
+    ///
+    /// When enabled, `CLIENT SETINFO lib-name muxis` and `CLIENT SETINFO lib-ver
+    /// <version>` are sent after connecting, so the client shows up in `CLIENT LIST`.
+    /// Servers older than Redis 7.2 reject the command; those errors are ignored.
+    /// Disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - `true` to send `CLIENT SETINFO`
+    #[inline]
+    pub fn advertise_lib(mut self, enabled: bool) -> Self {
+        self.advertise_lib = enabled;
+        self
+    }
+
     /// Sets the connection timeout.
     ///
     /// # Arguments
@@ -110,6 +158,9 @@ impl ClientBuilder {
 
     /// Sets the read timeout for commands.
     ///
+    /// The timeout bounds each whole reply, not each socket read; when it elapses
+    /// the command fails with [`Error::Timeout`](crate::Error::Timeout).
+    ///
     /// # Arguments
     ///
     /// * `timeout` - Maximum time to wait for a response. `None` means no timeout.
@@ -141,6 +192,25 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets the ALPN protocols offered during the TLS handshake.
+    ///
+    /// Only needed when the server (or a TLS-terminating proxy in front of it)
+    /// requires ALPN negotiation. By default no protocols are offered.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocols` - Protocol identifiers in order of preference (e.g., `b"redis"`)
+    #[cfg(feature = "tls")]
+    #[inline]
+    pub fn alpn_protocols<I, P>(mut self, protocols: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Vec<u8>>,
+    {
+        self.alpn_protocols = protocols.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// Sets the maximum number of pending requests in the queue.
     ///
     /// # Arguments
@@ -152,6 +222,79 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets whether commands wait for room when the request queue is full.
+    ///
+    /// With backpressure disabled, a command sent while
+    /// [`queue_size`](Self::queue_size) requests are pending fails immediately
+    /// with [`Error::QueueFull`] instead of waiting.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - `true` to wait for room in the queue (default: `true`)
+    #[inline]
+    pub fn backpressure(mut self, enabled: bool) -> Self {
+        self.backpressure = Some(enabled);
+        self
+    }
+
+    /// Sends `PING` on the connection whenever it has been idle for `interval`.
+    ///
+    /// Keeps the connection from being dropped by the server's `timeout` setting or
+    /// by idle-closing firewalls, and notices a dead peer before the next command
+    /// does. The PING is queued like any other command, so it never races with
+    /// replies to user commands. If it fails, or gets no reply within `interval`,
+    /// the connection is closed: pending commands fail at once and later ones
+    /// return a retryable [`Error::Io`]. A PING queued behind a blocking command
+    /// such as `BLPOP` waits for it instead.
+    /// The client does not reconnect by itself; create a new one, e.g. with
+    /// [`Client::connect_with_retry`].
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Idle time before a PING is sent, `None` or zero to disable (default: `None`)
+    #[inline]
+    pub fn keepalive_ping(mut self, interval: Option<Duration>) -> Self {
+        self.keepalive_ping = interval;
+        self
+    }
+
+    /// Enables client-side type checks for keys whose type is already known.
+    ///
+    /// The client remembers key types from earlier replies (e.g. a successful
+    /// `SET`, `LPUSH` or `TYPE`) and refuses `GET`, `LPUSH`, `RPUSH`, `SADD`,
+    /// `HSET` and `ZADD` on a key known to hold another type with
+    /// [`Error::WrongType`], without a round-trip.
+    ///
+    /// This is a best-effort guard, not a correctness guarantee: the remembered
+    /// type goes stale when the key expires or another client changes it, so a
+    /// command may be refused although the server would accept it, and commands
+    /// not listed above are never checked.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - `true` to check key types client-side (default: `false`)
+    #[inline]
+    pub fn strict_types(mut self, enabled: bool) -> Self {
+        self.strict_types = enabled;
+        self
+    }
+
+    /// Checks the argument count of well-known commands before sending them.
+    ///
+    /// Applies to [`Client::send_command`]: a command from a built-in table of
+    /// Redis commands (e.g. `GET`, `SET`, `ZADD`) given fewer arguments than it
+    /// requires fails with [`Error::InvalidArgument`] without a round-trip. Unknown
+    /// commands, such as module commands, are sent unchecked.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - `true` to validate arity client-side (default: `false`)
+    #[inline]
+    pub fn validate_arity(mut self, enabled: bool) -> Self {
+        self.validate_arity = enabled;
+        self
+    }
+
     /// Sets the maximum frame size for the decoder.
     ///
     /// # Arguments
@@ -163,6 +306,50 @@ impl ClientBuilder {
         self
     }
 
+    /// Enables or disables `TCP_NODELAY` on the socket.
+    ///
+    /// Enabled by default, so small commands are sent without waiting for Nagle's
+    /// algorithm to coalesce them. Combine `false` with
+    /// [`write_buffer`](Self::write_buffer) only if latency does not matter at all.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - `true` to disable Nagle's algorithm (default: `true`)
+    #[inline]
+    pub fn tcp_nodelay(mut self, enabled: bool) -> Self {
+        self.tcp_nodelay = Some(enabled);
+        self
+    }
+
+    /// Sets the size of the buffer commands are written through.
+    ///
+    /// Commands queued while the connection is busy are collected in the buffer and
+    /// flushed together, always at a frame boundary, so bulk loads need fewer
+    /// syscalls and packets. A command is never held back once the queue is empty, so
+    /// with `TCP_NODELAY` (the default) a lone command still goes out immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Buffer size in bytes (default: 0, which writes every command directly)
+    #[inline]
+    pub fn write_buffer(mut self, size: usize) -> Self {
+        self.write_buffer_size = Some(size);
+        self
+    }
+
+    /// Sets a custom resolver used to turn the address host into a socket address.
+    ///
+    /// The hostname from the address is still used for TLS server name verification.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - Resolver consulted before opening the TCP connection
+    #[inline]
+    pub fn resolver(mut self, resolver: Arc<dyn AddressResolver>) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
     /// Builds the [`Client`] connection.
     ///
     /// # Errors
@@ -177,12 +364,23 @@ impl ClientBuilder {
 
         let settings = crate::core::ConnectionSettings {
             client_name: self.client_name,
+            advertise_lib: self.advertise_lib,
+            username: self.username,
             password: self.password,
             database: self.database,
             queue_size: self.queue_size.unwrap_or(1024),
+            backpressure: self.backpressure.unwrap_or(true),
+            strict_types: self.strict_types,
+            validate_arity: self.validate_arity,
             read_timeout: self.read_timeout,
             write_timeout: self.write_timeout,
+            keepalive_ping: self.keepalive_ping,
             max_frame_size: self.max_frame_size.unwrap_or(512 * 1024 * 1024),
+            tcp_nodelay: self.tcp_nodelay.unwrap_or(true),
+            write_buffer_size: self.write_buffer_size.unwrap_or(0),
+            resolver: self.resolver,
+            #[cfg(feature = "tls")]
+            alpn_protocols: self.alpn_protocols,
         };
 
         let client = Client::connect_inner(address, self.tls, settings).await?;
@@ -214,6 +412,25 @@ mod tests {
         assert_eq!(builder.password, Some("secret".to_string()));
     }
 
+    #[test]
+    fn test_builder_tcp_nodelay_and_write_buffer() {
+        let builder = ClientBuilder::new();
+        assert_eq!(builder.tcp_nodelay, None);
+        assert_eq!(builder.write_buffer_size, None);
+
+        let builder = ClientBuilder::new()
+            .tcp_nodelay(false)
+            .write_buffer(64 * 1024);
+        assert_eq!(builder.tcp_nodelay, Some(false));
+        assert_eq!(builder.write_buffer_size, Some(64 * 1024));
+    }
+
+    #[test]
+    fn test_builder_advertise_lib() {
+        assert!(!ClientBuilder::new().advertise_lib);
+        assert!(ClientBuilder::new().advertise_lib(true).advertise_lib);
+    }
+
     #[test]
     fn test_builder_set_database() {
         let builder = ClientBuilder::new().database(5);
@@ -226,6 +443,34 @@ mod tests {
         assert_eq!(builder.client_name, Some("myapp".to_string()));
     }
 
+    #[test]
+    fn test_builder_connection_name_unique() {
+        let first = ClientBuilder::new()
+            .connection_name_unique("myapp")
+            .client_name;
+        let second = ClientBuilder::new()
+            .connection_name_unique("myapp")
+            .client_name;
+
+        let prefix = format!("myapp-{}-", std::process::id());
+        for name in [&first, &second] {
+            let name = name.as_deref().unwrap();
+            assert!(name.starts_with(&prefix), "{}", name);
+            assert!(name[prefix.len()..].parse::<u64>().is_ok(), "{}", name);
+        }
+        assert_ne!(first, second);
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    fn test_builder_set_alpn_protocols() {
+        let builder = ClientBuilder::new().alpn_protocols([&b"redis"[..], &b"resp"[..]]);
+        assert_eq!(
+            builder.alpn_protocols,
+            vec![b"redis".to_vec(), b"resp".to_vec()]
+        );
+    }
+
     #[test]
     fn test_builder_set_tls() {
         let builder = ClientBuilder::new().tls(true);
@@ -248,6 +493,55 @@ mod tests {
         assert!(!builder.tls);
     }
 
+    #[derive(Debug)]
+    struct StaticResolver(std::net::SocketAddr);
+
+    impl AddressResolver for StaticResolver {
+        fn resolve<'a>(
+            &'a self,
+            host: &'a str,
+            port: u16,
+        ) -> futures::future::BoxFuture<'a, crate::Result<std::net::SocketAddr>> {
+            Box::pin(async move {
+                if host == "redis.invalid" && port == 6379 {
+                    Ok(self.0)
+                } else {
+                    Err(Error::InvalidArgument {
+                        message: format!("unknown host {}:{}", host, port),
+                    })
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_builder_custom_resolver() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move { listener.accept().await.is_ok() });
+
+        let result = ClientBuilder::new()
+            .address("redis://redis.invalid:6379")
+            .resolver(Arc::new(StaticResolver(addr)))
+            .build()
+            .await;
+
+        assert!(result.is_ok());
+        assert!(server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_builder_custom_resolver_error() {
+        let addr = "127.0.0.1:1".parse().unwrap();
+        let result = ClientBuilder::new()
+            .address("redis://other.invalid:6379")
+            .resolver(Arc::new(StaticResolver(addr)))
+            .build()
+            .await;
+
+        assert!(matches!(result, Err(Error::InvalidArgument { .. })));
+    }
+
     #[tokio::test]
     async fn test_builder_build_without_address() {
         let builder = ClientBuilder::new();
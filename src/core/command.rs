@@ -1,5 +1,6 @@
+use crate::core::types::{AclUser, Aggregate, BitUnit, Expiry, ZAddCondition, ZRangeOptions};
 use crate::proto::frame::Frame;
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
 
 /// A command ready to be sent to Redis.
 ///
@@ -23,6 +24,79 @@ impl Cmd {
         }
     }
 
+    /// Creates a command from an already split argument list.
+    ///
+    /// The first argument is the command name.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The command name followed by its arguments
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`](crate::Error::InvalidArgument) if `args`
+    /// is empty.
+    pub fn from_args<I, T>(args: I) -> Result<Self, crate::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Bytes>,
+    {
+        let args: Vec<Bytes> = args.into_iter().map(Into::into).collect();
+        if args.is_empty() {
+            return Err(crate::Error::InvalidArgument {
+                message: "a command needs at least a name".to_string(),
+            });
+        }
+        Ok(Self { args })
+    }
+
+    /// Parses a command line as typed into `redis-cli`.
+    ///
+    /// Arguments are separated by whitespace. Double quotes group an argument
+    /// containing spaces (`SET key "hello world"`); inside them, `\"` and `\\`
+    /// stand for a literal quote and backslash.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The command line, e.g. `SET key "hello world"`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`](crate::Error::InvalidArgument) if the
+    /// line is blank or a quote is left unterminated.
+    pub fn parse_line(line: &str) -> Result<Self, crate::Error> {
+        let unterminated = || crate::Error::InvalidArgument {
+            message: "unterminated quote in command line".to_string(),
+        };
+        let mut args = Vec::new();
+        let mut current: Option<String> = None;
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    let arg = current.get_or_insert_with(String::new);
+                    loop {
+                        match chars.next().ok_or_else(unterminated)? {
+                            '"' => break,
+                            '\\' => match chars.next().ok_or_else(unterminated)? {
+                                escaped @ ('"' | '\\') => arg.push(escaped),
+                                other => {
+                                    arg.push('\\');
+                                    arg.push(other);
+                                }
+                            },
+                            other => arg.push(other),
+                        }
+                    }
+                }
+                c if c.is_whitespace() => args.extend(current.take()),
+                c => current.get_or_insert_with(String::new).push(c),
+            }
+        }
+        args.extend(current);
+        Self::from_args(args)
+    }
+
     /// Appends an argument to the command.
     ///
     /// # Arguments
@@ -34,6 +108,46 @@ impl Cmd {
         self
     }
 
+    /// Appends an argument only if it is `Some`.
+    ///
+    /// # Arguments
+    ///
+    /// * `arg` - The optional argument value
+    #[inline]
+    pub fn arg_opt<T: Into<Bytes>>(self, arg: Option<T>) -> Self {
+        match arg {
+            Some(arg) => self.arg(arg),
+            None => self,
+        }
+    }
+
+    /// Appends an argument, typically a flag keyword, only if `cond` is true.
+    ///
+    /// # Arguments
+    ///
+    /// * `cond` - Whether to append the argument
+    /// * `arg` - The argument value
+    #[inline]
+    pub fn arg_if<T: Into<Bytes>>(self, cond: bool, arg: T) -> Self {
+        if cond {
+            self.arg(arg)
+        } else {
+            self
+        }
+    }
+
+    /// Returns the command name (e.g. `SET`).
+    #[inline]
+    pub fn name(&self) -> &[u8] {
+        &self.args[0]
+    }
+
+    /// Returns the number of arguments, including the command name.
+    #[inline]
+    pub(crate) fn arg_count(&self) -> usize {
+        self.args.len()
+    }
+
     /// Converts the command to a RESP Array frame.
     #[inline]
     pub fn into_frame(self) -> Frame {
@@ -44,6 +158,44 @@ impl Cmd {
                 .collect(),
         )
     }
+
+    /// Encodes the command as a RESP array directly into `buf`.
+    ///
+    /// Produces the same bytes as encoding [`into_frame`](Self::into_frame), without
+    /// building the intermediate frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - Buffer the encoded command is appended to
+    pub fn write_to(&self, buf: &mut BytesMut) {
+        let len: usize = self.args.iter().map(|arg| arg.len() + 16).sum();
+        buf.reserve(len + 16);
+        buf.put_u8(b'*');
+        put_decimal(buf, self.args.len());
+        buf.extend_from_slice(b"\r\n");
+        for arg in &self.args {
+            buf.put_u8(b'$');
+            put_decimal(buf, arg.len());
+            buf.extend_from_slice(b"\r\n");
+            buf.extend_from_slice(arg);
+            buf.extend_from_slice(b"\r\n");
+        }
+    }
+}
+
+/// Appends the decimal representation of `n` to `buf` without allocating.
+fn put_decimal(buf: &mut BytesMut, mut n: usize) {
+    let mut digits = [0u8; 20];
+    let mut start = digits.len();
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    buf.extend_from_slice(&digits[start..]);
 }
 
 /// Creates a PING command.
@@ -72,6 +224,8 @@ pub fn set(key: impl Into<Bytes>, value: impl Into<Bytes>) -> Cmd {
 
 /// Creates a SET command with expiration.
 ///
+/// A whole number of seconds is sent as EX, anything finer as PX.
+///
 /// # Arguments
 ///
 /// * `key` - The key to set
@@ -83,11 +237,23 @@ pub fn set_with_expiry(
     value: impl Into<Bytes>,
     expiry: std::time::Duration,
 ) -> Cmd {
+    set_with_expiry_opt(key, value, Expiry::from(expiry))
+}
+
+/// Creates a SET command with an EX, PX, EXAT or PXAT expiration.
+///
+/// # Arguments
+///
+/// * `key` - The key to set
+/// * `value` - The value to set
+/// * `expiry` - When the key expires
+#[inline]
+pub fn set_with_expiry_opt(key: impl Into<Bytes>, value: impl Into<Bytes>, expiry: Expiry) -> Cmd {
     Cmd::new("SET")
         .arg(key)
         .arg(value)
-        .arg("EX")
-        .arg(expiry.as_secs().to_string())
+        .arg(expiry.as_str())
+        .arg(expiry.value().to_string())
 }
 
 /// Creates a DEL command.
@@ -96,6 +262,22 @@ pub fn del(key: impl Into<Bytes>) -> Cmd {
     Cmd::new("DEL").arg(key)
 }
 
+/// Creates an UNLINK command for several keys.
+#[inline]
+pub fn unlink(keys: impl IntoIterator<Item = impl Into<Bytes>>) -> Cmd {
+    with_keys(Cmd::new("UNLINK"), keys)
+}
+
+/// Appends each key as an argument, without an intermediate `Vec<String>`.
+fn with_keys(mut cmd: Cmd, keys: impl IntoIterator<Item = impl Into<Bytes>>) -> Cmd {
+    let keys = keys.into_iter();
+    cmd.args.reserve(keys.size_hint().0);
+    for key in keys {
+        cmd = cmd.arg(key);
+    }
+    cmd
+}
+
 /// Creates an INCR command.
 #[inline]
 pub fn incr(key: impl Into<Bytes>) -> Cmd {
@@ -138,22 +320,211 @@ pub fn select(db: u8) -> Cmd {
     Cmd::new("SELECT").arg(db.to_string())
 }
 
+/// Creates a PUBLISH command.
+#[inline]
+pub fn publish(channel: impl Into<Bytes>, message: impl Into<Bytes>) -> Cmd {
+    Cmd::new("PUBLISH").arg(channel).arg(message)
+}
+
+/// Creates a SUBSCRIBE command.
+#[inline]
+pub fn subscribe(channels: Vec<String>) -> Cmd {
+    let mut cmd = Cmd::new("SUBSCRIBE");
+    for channel in channels {
+        cmd = cmd.arg(channel);
+    }
+    cmd
+}
+
+/// Creates a MONITOR command.
+#[inline]
+pub fn monitor() -> Cmd {
+    Cmd::new("MONITOR")
+}
+
+/// Creates a RESET command.
+#[inline]
+pub fn reset() -> Cmd {
+    Cmd::new("RESET")
+}
+
+/// Creates a WATCH command.
+#[inline]
+pub fn watch(keys: impl IntoIterator<Item = impl Into<Bytes>>) -> Cmd {
+    with_keys(Cmd::new("WATCH"), keys)
+}
+
+/// Creates an UNWATCH command.
+#[inline]
+pub fn unwatch() -> Cmd {
+    Cmd::new("UNWATCH")
+}
+
+/// Creates a MULTI command.
+#[inline]
+pub fn multi() -> Cmd {
+    Cmd::new("MULTI")
+}
+
+/// Creates an EXEC command.
+#[inline]
+pub fn exec() -> Cmd {
+    Cmd::new("EXEC")
+}
+
+/// Creates an ACL WHOAMI command.
+#[inline]
+pub fn acl_whoami() -> Cmd {
+    Cmd::new("ACL").arg("WHOAMI")
+}
+
+/// Creates an ACL CAT command, listing categories or the commands in one.
+#[inline]
+pub fn acl_cat(category: Option<&str>) -> Cmd {
+    Cmd::new("ACL")
+        .arg("CAT")
+        .arg_opt(category.map(str::to_string))
+}
+
+/// Creates an ACL GETUSER command.
+#[inline]
+pub fn acl_getuser(username: impl Into<Bytes>) -> Cmd {
+    Cmd::new("ACL").arg("GETUSER").arg(username)
+}
+
+/// Creates a SCRIPT LOAD command.
+#[inline]
+pub fn script_load(script: impl Into<Bytes>) -> Cmd {
+    Cmd::new("SCRIPT").arg("LOAD").arg(script)
+}
+
+/// Creates an EVALSHA command.
+#[inline]
+pub fn evalsha(sha: &str, keys: Vec<Bytes>, args: Vec<Bytes>) -> Cmd {
+    let mut cmd = Cmd::new("EVALSHA")
+        .arg(sha.to_string())
+        .arg(keys.len().to_string());
+    for key in keys {
+        cmd = cmd.arg(key);
+    }
+    for arg in args {
+        cmd = cmd.arg(arg);
+    }
+    cmd
+}
+
+/// Creates a FUNCTION LOAD command (Redis 7.0+).
+///
+/// With `replace`, an existing library of the same name is replaced.
+#[inline]
+pub fn function_load(replace: bool, code: &str) -> Cmd {
+    Cmd::new("FUNCTION")
+        .arg("LOAD")
+        .arg_if(replace, "REPLACE")
+        .arg(code.to_string())
+}
+
+/// Creates an FCALL command (Redis 7.0+).
+#[inline]
+pub fn fcall(function: &str, keys: Vec<Bytes>, args: Vec<Bytes>) -> Cmd {
+    function_call("FCALL", function, keys, args)
+}
+
+/// Creates an FCALL_RO command for read-only functions (Redis 7.0+).
+#[inline]
+pub fn fcall_ro(function: &str, keys: Vec<Bytes>, args: Vec<Bytes>) -> Cmd {
+    function_call("FCALL_RO", function, keys, args)
+}
+
+/// Builds `<name> function numkeys key... arg...`.
+fn function_call(name: &'static str, function: &str, keys: Vec<Bytes>, args: Vec<Bytes>) -> Cmd {
+    let mut cmd = Cmd::new(name)
+        .arg(function.to_string())
+        .arg(keys.len().to_string());
+    for key in keys {
+        cmd = cmd.arg(key);
+    }
+    for arg in args {
+        cmd = cmd.arg(arg);
+    }
+    cmd
+}
+
+/// Creates a CONFIG GET command. `pattern` may be a glob such as `maxmemory*`.
+#[inline]
+pub fn config_get(pattern: &str) -> Cmd {
+    Cmd::new("CONFIG").arg("GET").arg(pattern.to_string())
+}
+
+/// Creates an INFO command for one section, e.g. `replication`.
+#[inline]
+pub fn info(section: &str) -> Cmd {
+    Cmd::new("INFO").arg(section.to_string())
+}
+
+/// Creates a CONFIG SET command.
+#[inline]
+pub fn config_set(param: &str, value: &str) -> Cmd {
+    Cmd::new("CONFIG")
+        .arg("SET")
+        .arg(param.to_string())
+        .arg(value.to_string())
+}
+
+/// Creates a CLIENT SETINFO command (Redis 7.2+).
+///
+/// `attr` is `lib-name` or `lib-ver`.
+#[inline]
+pub fn client_setinfo(attr: &str, value: &str) -> Cmd {
+    Cmd::new("CLIENT")
+        .arg("SETINFO")
+        .arg(attr.to_string())
+        .arg(value.to_string())
+}
+
 /// Creates a CLIENT SETNAME command.
 #[inline]
 pub fn client_setname(name: impl Into<Bytes>) -> Cmd {
     Cmd::new("CLIENT").arg("SETNAME").arg(name)
 }
 
-/// Creates a MGET command.
+/// Creates a HELLO command.
+///
+/// # Arguments
+///
+/// * `protover` - The RESP protocol version to negotiate
+/// * `auth` - Optional (username, password) pair sent as `AUTH`
+/// * `client_name` - Optional connection name sent as `SETNAME`
+#[cfg(feature = "resp3")]
 #[inline]
-pub fn mget(keys: Vec<String>) -> Cmd {
-    let mut cmd = Cmd::new("MGET");
-    for key in keys {
-        cmd = cmd.arg(key);
+pub fn hello(protover: u8, auth: Option<(String, String)>, client_name: Option<String>) -> Cmd {
+    let mut cmd = Cmd::new("HELLO").arg(protover.to_string());
+    if let Some((username, password)) = auth {
+        cmd = cmd.arg("AUTH").arg(username).arg(password);
+    }
+    if let Some(name) = client_name {
+        cmd = cmd.arg("SETNAME").arg(name);
     }
     cmd
 }
 
+/// Creates a MGET command.
+///
+/// Accepts any keys convertible to [`Bytes`], e.g. `String`, `&'static str` or
+/// `Bytes`.
+#[inline]
+pub fn mget(keys: impl IntoIterator<Item = impl Into<Bytes>>) -> Cmd {
+    with_keys(Cmd::new("MGET"), keys)
+}
+
+/// Creates a MGET command with binary-safe keys.
+///
+/// Same as [`mget`] with the keys already given as [`Bytes`].
+#[inline]
+pub fn mget_bytes(keys: impl IntoIterator<Item = Bytes>) -> Cmd {
+    mget(keys)
+}
+
 /// Creates a MSET command.
 #[inline]
 pub fn mset(pairs: Vec<(String, Bytes)>) -> Cmd {
@@ -164,19 +535,26 @@ pub fn mset(pairs: Vec<(String, Bytes)>) -> Cmd {
     cmd
 }
 
+/// Creates a MSET command with binary-safe keys.
+#[inline]
+pub fn mset_bytes(pairs: Vec<(Bytes, Bytes)>) -> Cmd {
+    let mut cmd = Cmd::new("MSET");
+    for (key, value) in pairs {
+        cmd = cmd.arg(key).arg(value);
+    }
+    cmd
+}
+
 /// Creates a SETNX command.
 #[inline]
 pub fn setnx(key: impl Into<Bytes>, value: impl Into<Bytes>) -> Cmd {
     Cmd::new("SETNX").arg(key).arg(value)
 }
 
-/// Creates a SETEX command.
+/// Creates a SET command expiring after `seconds`, equivalent to SETEX.
 #[inline]
 pub fn setex(key: impl Into<Bytes>, seconds: u64, value: impl Into<Bytes>) -> Cmd {
-    Cmd::new("SETEX")
-        .arg(key)
-        .arg(seconds.to_string())
-        .arg(value)
+    set_with_expiry_opt(key, value, Expiry::Seconds(seconds))
 }
 
 /// Creates a GETDEL command.
@@ -185,6 +563,36 @@ pub fn getdel(key: impl Into<Bytes>) -> Cmd {
     Cmd::new("GETDEL").arg(key)
 }
 
+/// Creates a GETEX command.
+///
+/// With `Some(ttl)` the expiry is set in milliseconds (PX); with `None` any
+/// existing expiry is removed (PERSIST).
+#[inline]
+pub fn getex(key: impl Into<Bytes>, expiry: Option<std::time::Duration>) -> Cmd {
+    let cmd = Cmd::new("GETEX").arg(key);
+    match expiry {
+        Some(ttl) => cmd.arg("PX").arg(ttl.as_millis().to_string()),
+        None => cmd.arg("PERSIST"),
+    }
+}
+
+/// Creates a SET command with the GET flag, returning the previous value.
+///
+/// A whole number of seconds is sent as EX, anything finer as PX. With `None`
+/// the key is stored without an expiry, clearing any previous one.
+#[inline]
+pub fn set_get(
+    key: impl Into<Bytes>,
+    value: impl Into<Bytes>,
+    expiry: Option<std::time::Duration>,
+) -> Cmd {
+    let cmd = match expiry.map(Expiry::from) {
+        Some(expiry) => set_with_expiry_opt(key, value, expiry),
+        None => Cmd::new("SET").arg(key).arg(value),
+    };
+    cmd.arg("GET")
+}
+
 /// Creates an APPEND command.
 #[inline]
 pub fn append(key: impl Into<Bytes>, value: impl Into<Bytes>) -> Cmd {
@@ -197,14 +605,44 @@ pub fn strlen(key: impl Into<Bytes>) -> Cmd {
     Cmd::new("STRLEN").arg(key)
 }
 
+/// Creates a BITCOUNT command.
+///
+/// The unit (Redis 7.0+) is only sent together with a range.
+#[inline]
+pub fn bitcount(key: impl Into<Bytes>, range: Option<(i64, i64)>, unit: Option<BitUnit>) -> Cmd {
+    bit_range(Cmd::new("BITCOUNT").arg(key), range, unit)
+}
+
+/// Creates a BITPOS command looking for the first bit set to `bit`.
+///
+/// The unit (Redis 7.0+) is only sent together with a range.
+#[inline]
+pub fn bitpos(
+    key: impl Into<Bytes>,
+    bit: bool,
+    range: Option<(i64, i64)>,
+    unit: Option<BitUnit>,
+) -> Cmd {
+    let cmd = Cmd::new("BITPOS").arg(key).arg(if bit { "1" } else { "0" });
+    bit_range(cmd, range, unit)
+}
+
+fn bit_range(cmd: Cmd, range: Option<(i64, i64)>, unit: Option<BitUnit>) -> Cmd {
+    let Some((start, end)) = range else {
+        return cmd;
+    };
+    cmd.arg(start.to_string())
+        .arg(end.to_string())
+        .arg_opt(unit.map(|unit| unit.as_str()))
+}
+
 /// Creates an EXISTS command.
+///
+/// Accepts any keys convertible to [`Bytes`], e.g. `String`, `&'static str` or
+/// `Bytes`.
 #[inline]
-pub fn exists(keys: Vec<String>) -> Cmd {
-    let mut cmd = Cmd::new("EXISTS");
-    for key in keys {
-        cmd = cmd.arg(key);
-    }
-    cmd
+pub fn exists(keys: impl IntoIterator<Item = impl Into<Bytes>>) -> Cmd {
+    with_keys(Cmd::new("EXISTS"), keys)
 }
 
 /// Creates a TYPE command.
@@ -213,6 +651,18 @@ pub fn key_type(key: impl Into<Bytes>) -> Cmd {
     Cmd::new("TYPE").arg(key)
 }
 
+/// Creates an OBJECT FREQ command.
+#[inline]
+pub fn object_freq(key: impl Into<Bytes>) -> Cmd {
+    Cmd::new("OBJECT").arg("FREQ").arg(key)
+}
+
+/// Creates an OBJECT IDLETIME command.
+#[inline]
+pub fn object_idletime(key: impl Into<Bytes>) -> Cmd {
+    Cmd::new("OBJECT").arg("IDLETIME").arg(key)
+}
+
 /// Creates an EXPIRE command.
 #[inline]
 pub fn expire(key: impl Into<Bytes>, seconds: u64) -> Cmd {
@@ -249,6 +699,31 @@ pub fn scan(cursor: u64) -> Cmd {
     Cmd::new("SCAN").arg(cursor.to_string())
 }
 
+/// Creates a SCAN command that only returns keys matching `pattern`.
+///
+/// `count` is a hint for how many keys the server examines per call.
+#[inline]
+pub fn scan_match(cursor: u64, pattern: impl Into<Bytes>, count: usize) -> Cmd {
+    Cmd::new("SCAN")
+        .arg(cursor.to_string())
+        .arg("MATCH")
+        .arg(pattern)
+        .arg("COUNT")
+        .arg(count.to_string())
+}
+
+/// Creates an HSCAN command.
+///
+/// With `novalues` set, NOVALUES (Redis 7.4+) is appended so only field names are
+/// returned.
+#[inline]
+pub fn hscan(key: impl Into<Bytes>, cursor: u64, novalues: bool) -> Cmd {
+    Cmd::new("HSCAN")
+        .arg(key)
+        .arg(cursor.to_string())
+        .arg_if(novalues, "NOVALUES")
+}
+
 /// Creates an HSET command.
 #[inline]
 pub fn hset(key: impl Into<Bytes>, field: impl Into<Bytes>, value: impl Into<Bytes>) -> Cmd {
@@ -365,6 +840,26 @@ pub fn rpush(key: String, values: Vec<Bytes>) -> Cmd {
     cmd
 }
 
+/// Creates an LPUSHX command.
+#[inline]
+pub fn lpushx(key: String, values: Vec<Bytes>) -> Cmd {
+    let mut cmd = Cmd::new("LPUSHX").arg(key);
+    for value in values {
+        cmd = cmd.arg(value);
+    }
+    cmd
+}
+
+/// Creates an RPUSHX command.
+#[inline]
+pub fn rpushx(key: String, values: Vec<Bytes>) -> Cmd {
+    let mut cmd = Cmd::new("RPUSHX").arg(key);
+    for value in values {
+        cmd = cmd.arg(value);
+    }
+    cmd
+}
+
 /// Creates an LPOP command.
 #[inline]
 pub fn lpop(key: impl Into<Bytes>) -> Cmd {
@@ -377,6 +872,18 @@ pub fn rpop(key: impl Into<Bytes>) -> Cmd {
     Cmd::new("RPOP").arg(key)
 }
 
+/// Creates an LPOP command popping up to `count` elements.
+#[inline]
+pub fn lpop_count(key: impl Into<Bytes>, count: u64) -> Cmd {
+    Cmd::new("LPOP").arg(key).arg(count.to_string())
+}
+
+/// Creates an RPOP command popping up to `count` elements.
+#[inline]
+pub fn rpop_count(key: impl Into<Bytes>, count: u64) -> Cmd {
+    Cmd::new("RPOP").arg(key).arg(count.to_string())
+}
+
 /// Creates an LLEN command.
 #[inline]
 pub fn llen(key: impl Into<Bytes>) -> Cmd {
@@ -573,26 +1080,149 @@ pub fn zadd(key: String, members: Vec<(f64, Bytes)>) -> Cmd {
     cmd
 }
 
-/// Creates a ZREM command.
+/// Creates a ZADD command in INCR mode with an optional NX/XX condition.
 #[inline]
-pub fn zrem(key: String, members: Vec<Bytes>) -> Cmd {
-    let mut cmd = Cmd::new("ZREM").arg(key);
-    for member in members {
-        cmd = cmd.arg(member);
-    }
-    cmd
+pub fn zadd_incr(
+    key: impl Into<Bytes>,
+    delta: f64,
+    member: impl Into<Bytes>,
+    condition: Option<ZAddCondition>,
+) -> Cmd {
+    Cmd::new("ZADD")
+        .arg(key)
+        .arg_opt(condition.map(|c| c.as_str()))
+        .arg("INCR")
+        .arg(delta.to_string())
+        .arg(member)
 }
 
-/// Creates a ZRANGE command.
+/// Creates a `ZADD key GT CH score member` command.
+///
+/// Only raises the score of an existing member; a missing member is added.
 #[inline]
-pub fn zrange(key: impl Into<Bytes>, start: i64, stop: i64) -> Cmd {
-    Cmd::new("ZRANGE")
-        .arg(key)
-        .arg(start.to_string())
-        .arg(stop.to_string())
+pub fn zadd_gt(key: impl Into<Bytes>, score: f64, member: impl Into<Bytes>) -> Cmd {
+    zadd_compare(key, "GT", score, member)
 }
 
-/// Creates a ZRANGEBYSCORE command.
+/// Creates a `ZADD key LT CH score member` command.
+///
+/// Only lowers the score of an existing member; a missing member is added.
+#[inline]
+pub fn zadd_lt(key: impl Into<Bytes>, score: f64, member: impl Into<Bytes>) -> Cmd {
+    zadd_compare(key, "LT", score, member)
+}
+
+fn zadd_compare(
+    key: impl Into<Bytes>,
+    comparison: &'static str,
+    score: f64,
+    member: impl Into<Bytes>,
+) -> Cmd {
+    Cmd::new("ZADD")
+        .arg(key)
+        .arg(comparison)
+        .arg("CH")
+        .arg(score.to_string())
+        .arg(member)
+}
+
+/// Creates a ZREM command.
+#[inline]
+pub fn zrem(key: String, members: Vec<Bytes>) -> Cmd {
+    let mut cmd = Cmd::new("ZREM").arg(key);
+    for member in members {
+        cmd = cmd.arg(member);
+    }
+    cmd
+}
+
+/// Creates a ZRANGE command.
+#[inline]
+pub fn zrange(key: impl Into<Bytes>, start: i64, stop: i64) -> Cmd {
+    Cmd::new("ZRANGE")
+        .arg(key)
+        .arg(start.to_string())
+        .arg(stop.to_string())
+}
+
+/// Creates a ZUNION command (Redis 6.2+).
+#[inline]
+pub fn zunion(
+    keys: Vec<String>,
+    weights: Option<Vec<f64>>,
+    aggregate: Option<Aggregate>,
+    withscores: bool,
+) -> Cmd {
+    zcombine("ZUNION", keys, weights, aggregate, withscores)
+}
+
+/// Creates a ZINTER command (Redis 6.2+).
+#[inline]
+pub fn zinter(
+    keys: Vec<String>,
+    weights: Option<Vec<f64>>,
+    aggregate: Option<Aggregate>,
+    withscores: bool,
+) -> Cmd {
+    zcombine("ZINTER", keys, weights, aggregate, withscores)
+}
+
+/// Creates a ZDIFF command (Redis 6.2+).
+#[inline]
+pub fn zdiff(keys: Vec<String>, withscores: bool) -> Cmd {
+    zcombine("ZDIFF", keys, None, None, withscores)
+}
+
+/// Builds `<name> numkeys key [key ...] [WEIGHTS w ...] [AGGREGATE a] [WITHSCORES]`.
+fn zcombine(
+    name: &'static str,
+    keys: Vec<String>,
+    weights: Option<Vec<f64>>,
+    aggregate: Option<Aggregate>,
+    withscores: bool,
+) -> Cmd {
+    let mut cmd = Cmd::new(name).arg(keys.len().to_string());
+    for key in keys {
+        cmd = cmd.arg(key);
+    }
+    if let Some(weights) = weights {
+        cmd = cmd.arg("WEIGHTS");
+        for weight in weights {
+            cmd = cmd.arg(weight.to_string());
+        }
+    }
+    if let Some(aggregate) = aggregate {
+        cmd = cmd.arg("AGGREGATE").arg(aggregate.as_str());
+    }
+    cmd.arg_if(withscores, "WITHSCORES")
+}
+
+/// Creates a ZRANGESTORE command (Redis 6.2+).
+#[inline]
+pub fn zrangestore(
+    dest: impl Into<Bytes>,
+    src: impl Into<Bytes>,
+    start: impl Into<Bytes>,
+    stop: impl Into<Bytes>,
+    opts: ZRangeOptions,
+) -> Cmd {
+    let mut cmd = Cmd::new("ZRANGESTORE")
+        .arg(dest)
+        .arg(src)
+        .arg(start)
+        .arg(stop)
+        .arg_opt(opts.by)
+        .arg_if(opts.rev, "REV");
+    if let Some((offset, count)) = opts.limit {
+        cmd = cmd
+            .arg("LIMIT")
+            .arg(offset.to_string())
+            .arg(count.to_string());
+    }
+    cmd
+}
+
+/// Creates a ZRANGEBYSCORE command.
 #[inline]
 pub fn zrangebyscore(key: impl Into<Bytes>, min: impl Into<Bytes>, max: impl Into<Bytes>) -> Cmd {
     Cmd::new("ZRANGEBYSCORE").arg(key).arg(min).arg(max)
@@ -721,14 +1351,102 @@ pub fn zremrangebylex(key: impl Into<Bytes>, min: impl Into<Bytes>, max: impl In
 #[inline]
 pub fn parse_frame_response(frame: Frame) -> Result<Frame, crate::Error> {
     match frame {
-        Frame::Error(e) => Err(crate::Error::Server {
-            message: String::from_utf8_lossy(&e).into_owned(),
-        }),
+        Frame::Error(e) => Err(crate::proto::error::parse_server_error(&e)),
         _ => Ok(frame),
     }
 }
 
+/// Returns the command name of a request frame, i.e. its first array element.
+#[inline]
+pub fn command_name(frame: &Frame) -> Option<Bytes> {
+    match frame {
+        Frame::Array(args) => match args.first() {
+            Some(Frame::BulkString(Some(name))) => Some(name.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Commands whose arguments may carry passwords or other secrets.
+///
+/// `CONFIG` and `ACL` are matched as a whole, as `CONFIG SET requirepass` and
+/// `ACL SETUSER` can only be told apart by their arguments. `MIGRATE` accepts an
+/// `AUTH` option and `RESTORE` carries a serialized value.
+const SENSITIVE_COMMANDS: &[&[u8]] =
+    &[b"AUTH", b"HELLO", b"CONFIG", b"ACL", b"MIGRATE", b"RESTORE"];
+
+/// Returns true if the arguments of the command must not be logged.
+///
+/// The name is matched case-insensitively.
+#[inline]
+pub fn is_sensitive(name: &[u8]) -> bool {
+    SENSITIVE_COMMANDS
+        .iter()
+        .any(|sensitive| sensitive.eq_ignore_ascii_case(name))
+}
+
+/// A request as shown in logs and tracing spans.
+///
+/// Only the name of a [sensitive](is_sensitive) command is shown, its arguments
+/// are replaced by `<redacted>`.
+pub(crate) enum Redacted<'a> {
+    /// A request frame.
+    Frame(&'a Frame),
+    /// A command encoded from its arguments.
+    Cmd(&'a Cmd),
+    /// One or more requests already encoded to RESP.
+    ///
+    /// The requests are decoded again so each can be redacted on its own; data
+    /// that does not decode is only shown by its length.
+    Encoded(&'a [u8]),
+}
+
+impl std::fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Redacted::Frame(frame) => command_name(frame),
+            Redacted::Cmd(cmd) => Some(Bytes::copy_from_slice(cmd.name())),
+            Redacted::Encoded(data) => {
+                let mut decoder = crate::proto::codec::Decoder::new();
+                decoder.append(data);
+                return match decoder.decode_all() {
+                    Ok(frames) if !frames.is_empty() => f
+                        .debug_list()
+                        .entries(frames.iter().map(Redacted::Frame))
+                        .finish(),
+                    _ => write!(f, "<{} encoded bytes>", data.len()),
+                };
+            }
+        };
+        match name {
+            Some(name) if is_sensitive(&name) => {
+                write!(f, "{} <redacted>", String::from_utf8_lossy(&name))
+            }
+            _ => match self {
+                Redacted::Frame(frame) => frame.fmt(f),
+                Redacted::Cmd(cmd) => cmd.fmt(f),
+                Redacted::Encoded(_) => Ok(()),
+            },
+        }
+    }
+}
+
+/// Finds the value of one `field:value` line in an INFO reply.
+///
+/// Stops at the first match without decoding the rest of the reply; section
+/// headers (`# Replication`) and blank lines are skipped.
+pub fn find_info_field(info: &[u8], field: &str) -> Option<String> {
+    info.split(|&b| b == b'\n').find_map(|line| {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let value = line.strip_prefix(field.as_bytes())?.strip_prefix(b":")?;
+        Some(String::from_utf8_lossy(value).into_owned())
+    })
+}
+
 /// Converts a frame to bytes.
+///
+/// The payload of a bulk string is moved out of the frame, not copied.
 #[inline]
 pub fn frame_to_bytes(frame: Frame) -> Result<Option<Bytes>, crate::Error> {
     match frame {
@@ -765,6 +1483,19 @@ pub fn frame_to_int(frame: Frame) -> Result<i64, crate::Error> {
     }
 }
 
+/// Converts a TTL reply to a [`Ttl`](crate::Ttl).
+#[inline]
+pub fn frame_to_ttl(frame: Frame) -> Result<crate::Ttl, crate::Error> {
+    match frame_to_int(frame)? {
+        -1 => Ok(crate::Ttl::NoExpiry),
+        -2 => Ok(crate::Ttl::Missing),
+        seconds if seconds >= 0 => Ok(crate::Ttl::Seconds(seconds as u64)),
+        other => Err(crate::Error::Protocol {
+            message: format!("invalid TTL reply {}", other),
+        }),
+    }
+}
+
 /// Converts a frame to a boolean.
 #[inline]
 pub fn frame_to_bool(frame: Frame) -> Result<bool, crate::Error> {
@@ -781,6 +1512,8 @@ pub fn frame_to_bool(frame: Frame) -> Result<bool, crate::Error> {
 }
 
 /// Converts a frame array to a vector of optional bytes.
+///
+/// Like [`frame_to_bytes`], the payloads are moved out of the frames.
 #[inline]
 pub fn frame_to_vec_bytes(frame: Frame) -> Result<Vec<Option<Bytes>>, crate::Error> {
     match frame {
@@ -827,6 +1560,17 @@ pub fn frame_to_string(frame: Frame) -> Result<String, crate::Error> {
     }
 }
 
+/// Converts a TYPE reply to a [`KeyType`](crate::KeyType).
+#[inline]
+pub fn frame_to_key_type(frame: Frame) -> Result<crate::KeyType, crate::Error> {
+    match frame {
+        Frame::Error(e) => Err(crate::Error::Server {
+            message: String::from_utf8_lossy(&e).into_owned(),
+        }),
+        _ => frame_to_string(frame).map(|s| crate::KeyType::from(s.as_str())),
+    }
+}
+
 /// Converts a frame array to a SCAN response (cursor, keys).
 #[inline]
 pub fn frame_to_scan_response(frame: Frame) -> Result<(u64, Vec<String>), crate::Error> {
@@ -874,6 +1618,41 @@ pub fn frame_to_scan_response(frame: Frame) -> Result<(u64, Vec<String>), crate:
     }
 }
 
+/// Converts an HSCAN reply to the next cursor and the page of field/value pairs.
+#[inline]
+pub fn frame_to_hscan_page(frame: Frame) -> Result<(u64, Vec<(String, Bytes)>), crate::Error> {
+    match frame {
+        Frame::Array(arr) => {
+            let mut items = arr.into_iter();
+            let (Some(cursor_frame), Some(pairs_frame), None) =
+                (items.next(), items.next(), items.next())
+            else {
+                return Err(crate::Error::Protocol {
+                    message: "HSCAN response must have 2 elements".to_string(),
+                });
+            };
+
+            let cursor = frame_to_string(cursor_frame)?.parse::<u64>().map_err(|_| {
+                crate::Error::Protocol {
+                    message: "invalid cursor value".to_string(),
+                }
+            })?;
+            let pairs = frame_to_pairs(pairs_frame)?
+                .into_iter()
+                .map(|(field, value)| (String::from_utf8_lossy(&field).into_owned(), value))
+                .collect();
+
+            Ok((cursor, pairs))
+        }
+        Frame::Error(e) => Err(crate::Error::Server {
+            message: String::from_utf8_lossy(&e).into_owned(),
+        }),
+        _ => Err(crate::Error::Protocol {
+            message: "expected array frame for HSCAN".to_string(),
+        }),
+    }
+}
+
 /// Converts a frame array to a vector of strings.
 #[inline]
 pub fn frame_to_vec_string(frame: Frame) -> Result<Vec<String>, crate::Error> {
@@ -894,7 +1673,97 @@ pub fn frame_to_vec_string(frame: Frame) -> Result<Vec<String>, crate::Error> {
     }
 }
 
+/// Converts a frame array to field/value pairs (HGETALL response).
+///
+/// Unlike [`frame_to_hashmap`], this keeps the reply order and raw field bytes.
+#[inline]
+pub fn frame_to_pairs(frame: Frame) -> Result<Vec<(Bytes, Bytes)>, crate::Error> {
+    match frame {
+        Frame::Array(arr) => {
+            if arr.len() % 2 != 0 {
+                return Err(crate::Error::Protocol {
+                    message: "HGETALL response must have even number of elements".to_string(),
+                });
+            }
+
+            let mut result = Vec::with_capacity(arr.len() / 2);
+            let mut iter = arr.into_iter();
+
+            while let (Some(field_frame), Some(value_frame)) = (iter.next(), iter.next()) {
+                let field = match field_frame {
+                    Frame::BulkString(Some(b)) => b,
+                    Frame::SimpleString(s) => Bytes::from(s),
+                    _ => {
+                        return Err(crate::Error::Protocol {
+                            message: "unexpected field frame type".to_string(),
+                        })
+                    }
+                };
+                let value = match value_frame {
+                    Frame::BulkString(Some(b)) => b,
+                    Frame::BulkString(None) | Frame::Null => Bytes::new(),
+                    Frame::Error(e) => {
+                        return Err(crate::Error::Server {
+                            message: String::from_utf8_lossy(&e).into_owned(),
+                        })
+                    }
+                    _ => {
+                        return Err(crate::Error::Protocol {
+                            message: "unexpected value frame type".to_string(),
+                        })
+                    }
+                };
+                result.push((field, value));
+            }
+
+            Ok(result)
+        }
+        Frame::Error(e) => Err(crate::Error::Server {
+            message: String::from_utf8_lossy(&e).into_owned(),
+        }),
+        _ => Err(crate::Error::Protocol {
+            message: "expected array frame for HGETALL".to_string(),
+        }),
+    }
+}
+
+/// Converts a CONFIG GET reply to a map of parameter names to values.
+///
+/// A glob pattern can match several parameters, which the server returns as a flat
+/// array of alternating names and values.
+#[inline]
+pub fn frame_to_config(
+    frame: Frame,
+) -> Result<std::collections::HashMap<String, String>, crate::Error> {
+    match frame {
+        Frame::Array(arr) => {
+            if arr.len() % 2 != 0 {
+                return Err(crate::Error::Protocol {
+                    message: "CONFIG GET response must have even number of elements".to_string(),
+                });
+            }
+
+            let mut result = std::collections::HashMap::with_capacity(arr.len() / 2);
+            let mut iter = arr.into_iter();
+
+            while let (Some(param_frame), Some(value_frame)) = (iter.next(), iter.next()) {
+                result.insert(frame_to_string(param_frame)?, frame_to_string(value_frame)?);
+            }
+
+            Ok(result)
+        }
+        Frame::Error(e) => Err(crate::Error::Server {
+            message: String::from_utf8_lossy(&e).into_owned(),
+        }),
+        _ => Err(crate::Error::Protocol {
+            message: "expected array frame for CONFIG GET".to_string(),
+        }),
+    }
+}
+
 /// Converts a frame array to a hashmap (HGETALL response).
+///
+/// Values are moved out of the frames; field names are decoded to strings.
 #[inline]
 pub fn frame_to_hashmap(
     frame: Frame,
@@ -907,11 +1776,10 @@ pub fn frame_to_hashmap(
                 });
             }
 
-            let mut result = std::collections::HashMap::new();
+            let mut result = std::collections::HashMap::with_capacity(arr.len() / 2);
             let mut iter = arr.into_iter();
 
-            while let Some(key_frame) = iter.next() {
-                let value_frame = iter.next().unwrap();
+            while let (Some(key_frame), Some(value_frame)) = (iter.next(), iter.next()) {
                 let key = frame_to_string(key_frame)?;
                 let value = match value_frame {
                     Frame::BulkString(Some(b)) => b,
@@ -941,6 +1809,86 @@ pub fn frame_to_hashmap(
     }
 }
 
+/// Converts an ACL GETUSER reply to an [`AclUser`].
+///
+/// Returns `None` if the user does not exist. Unknown fields are ignored.
+pub fn frame_to_acl_user(frame: Frame) -> Result<Option<AclUser>, crate::Error> {
+    let fields = match frame {
+        Frame::Null | Frame::BulkString(None) => return Ok(None),
+        Frame::Array(fields) => fields,
+        Frame::Error(e) => {
+            return Err(crate::Error::Server {
+                message: String::from_utf8_lossy(&e).into_owned(),
+            })
+        }
+        _ => {
+            return Err(crate::Error::Protocol {
+                message: "expected array frame for ACL GETUSER".to_string(),
+            })
+        }
+    };
+
+    if fields.len() % 2 != 0 {
+        return Err(crate::Error::Protocol {
+            message: "ACL GETUSER response must have even number of elements".to_string(),
+        });
+    }
+
+    let mut user = AclUser::default();
+    let mut iter = fields.into_iter();
+    while let (Some(name), Some(value)) = (iter.next(), iter.next()) {
+        match frame_to_string(name)?.as_str() {
+            "flags" => user.flags = frame_to_vec_string(value)?,
+            "passwords" => user.passwords = frame_to_vec_string(value)?,
+            "commands" => user.commands = frame_to_string(value)?,
+            "keys" => user.keys = frame_to_acl_patterns(value)?,
+            "channels" => user.channels = frame_to_acl_patterns(value)?,
+            "selectors" => user.selectors = frame_to_acl_selectors(value)?,
+            _ => {}
+        }
+    }
+    Ok(Some(user))
+}
+
+/// Converts ACL patterns given either as a list or a space-separated string.
+fn frame_to_acl_patterns(frame: Frame) -> Result<Vec<String>, crate::Error> {
+    match frame {
+        Frame::Array(_) => frame_to_vec_string(frame),
+        _ => Ok(frame_to_string(frame)?
+            .split_whitespace()
+            .map(str::to_string)
+            .collect()),
+    }
+}
+
+/// Converts the ACL GETUSER selectors: a list of flat field/value arrays.
+fn frame_to_acl_selectors(frame: Frame) -> Result<Vec<Vec<(String, String)>>, crate::Error> {
+    let selectors = match frame {
+        Frame::Array(selectors) => selectors,
+        _ => {
+            return Err(crate::Error::Protocol {
+                message: "expected array frame for ACL selectors".to_string(),
+            })
+        }
+    };
+
+    selectors
+        .into_iter()
+        .map(|selector| {
+            let fields = frame_to_vec_string(selector)?;
+            if fields.len() % 2 != 0 {
+                return Err(crate::Error::Protocol {
+                    message: "ACL selector must have even number of elements".to_string(),
+                });
+            }
+            Ok(fields
+                .chunks_exact(2)
+                .map(|pair| (pair[0].clone(), pair[1].clone()))
+                .collect())
+        })
+        .collect()
+}
+
 /// Converts a frame to a float.
 #[inline]
 pub fn frame_to_float(frame: Frame) -> Result<f64, crate::Error> {
@@ -992,11 +1940,30 @@ pub fn frame_to_vec_bytes_list(frame: Frame) -> Result<Vec<Bytes>, crate::Error>
     }
 }
 
+/// Converts an LPOP/RPOP reply with a count to the popped elements.
+///
+/// A missing key yields a nil reply instead of an array, mapped to an empty list.
+#[inline]
+pub fn frame_to_popped(frame: Frame) -> Result<Vec<Bytes>, crate::Error> {
+    match frame {
+        Frame::Null | Frame::BulkString(None) => Ok(Vec::new()),
+        frame => frame_to_vec_bytes_list(frame),
+    }
+}
+
+/// Converts a single-key BLPOP/BRPOP response to just the popped value.
+#[inline]
+pub fn frame_to_blocking_pop_value(frame: Frame) -> Result<Option<Bytes>, crate::Error> {
+    Ok(frame_to_blocking_pop(frame)?.map(|(_, value)| value))
+}
+
 /// Converts a frame to a BLPOP/BRPOP response (key, value).
+///
+/// A timeout is a nil array (`*-1`), decoded as [`Frame::Null`], and yields `None`.
 #[inline]
 pub fn frame_to_blocking_pop(frame: Frame) -> Result<Option<(String, Bytes)>, crate::Error> {
     match frame {
-        Frame::Null => Ok(None),
+        Frame::Null | Frame::BulkString(None) => Ok(None),
         Frame::Array(mut arr) => {
             if arr.len() != 2 {
                 return Err(crate::Error::Protocol {
@@ -1032,7 +1999,7 @@ pub fn frame_to_blocking_pop(frame: Frame) -> Result<Option<(String, Bytes)>, cr
 #[inline]
 pub fn frame_to_optional_int(frame: Frame) -> Result<Option<i64>, crate::Error> {
     match frame {
-        Frame::Null => Ok(None),
+        Frame::Null | Frame::BulkString(None) => Ok(None),
         Frame::Integer(i) => Ok(Some(i)),
         Frame::Error(e) => Err(crate::Error::Server {
             message: String::from_utf8_lossy(&e).into_owned(),
@@ -1043,6 +2010,45 @@ pub fn frame_to_optional_int(frame: Frame) -> Result<Option<i64>, crate::Error>
     }
 }
 
+/// Converts an OBJECT FREQ/IDLETIME reply to an optional integer.
+///
+/// The server rejects these commands when the configured `maxmemory-policy` does not
+/// track the requested statistic; that error is mapped to [`Error::InvalidArgument`].
+///
+/// [`Error::InvalidArgument`]: crate::Error::InvalidArgument
+#[inline]
+pub fn frame_to_object_stat(frame: Frame) -> Result<Option<i64>, crate::Error> {
+    match frame {
+        Frame::Error(e) => Err(map_object_stat_error(crate::Error::Server {
+            message: String::from_utf8_lossy(&e).into_owned(),
+        })),
+        _ => frame_to_optional_int(frame),
+    }
+}
+
+/// Maps the error OBJECT FREQ/IDLETIME return under an unsuitable `maxmemory-policy`
+/// to [`Error::InvalidArgument`]; any other error is returned unchanged.
+///
+/// Also applies to errors already wrapped in [`Error::Command`].
+///
+/// [`Error::InvalidArgument`]: crate::Error::InvalidArgument
+/// [`Error::Command`]: crate::Error::Command
+pub fn map_object_stat_error(e: crate::Error) -> crate::Error {
+    match e.inner() {
+        crate::Error::Server { message } if message.contains("maxmemory policy") => {
+            let requirement = if message.contains("LFU maxmemory policy is not selected") {
+                "OBJECT FREQ requires an LFU maxmemory-policy"
+            } else {
+                "OBJECT IDLETIME requires a non-LFU maxmemory-policy"
+            };
+            crate::Error::InvalidArgument {
+                message: format!("{}: {}", requirement, message),
+            }
+        }
+        _ => e,
+    }
+}
+
 /// Converts a frame to an optional float (for ZSCORE).
 #[inline]
 pub fn frame_to_optional_float(frame: Frame) -> Result<Option<f64>, crate::Error> {
@@ -1053,25 +2059,87 @@ pub fn frame_to_optional_float(frame: Frame) -> Result<Option<f64>, crate::Error
     }
 }
 
-/// Converts a frame to a sorted set member with score (for ZPOPMIN/ZPOPMAX).
+/// Converts a WITHSCORES reply (alternating members and scores) to pairs.
 #[inline]
-pub fn frame_to_zpop_result(frame: Frame) -> Result<Option<(String, f64)>, crate::Error> {
+pub fn frame_to_scored_members(frame: Frame) -> Result<Vec<(String, f64)>, crate::Error> {
     match frame {
-        Frame::Null => Ok(None),
-        Frame::Array(mut arr) => {
-            if arr.is_empty() {
-                return Ok(None);
-            }
-            if arr.len() != 2 {
+        Frame::Array(arr) => {
+            if arr.len() % 2 != 0 {
                 return Err(crate::Error::Protocol {
-                    message: "ZPOP response must have 2 elements".to_string(),
+                    message: "WITHSCORES response must have even number of elements".to_string(),
                 });
             }
 
-            let score_frame = arr.pop().unwrap();
-            let member_frame = arr.pop().unwrap();
-
-            let member = frame_to_string(member_frame)?;
+            let mut result = Vec::with_capacity(arr.len() / 2);
+            let mut iter = arr.into_iter();
+            while let (Some(member_frame), Some(score_frame)) = (iter.next(), iter.next()) {
+                result.push((frame_to_string(member_frame)?, frame_to_float(score_frame)?));
+            }
+            Ok(result)
+        }
+        Frame::Error(e) => Err(crate::Error::Server {
+            message: String::from_utf8_lossy(&e).into_owned(),
+        }),
+        _ => Err(crate::Error::Protocol {
+            message: "expected array frame for WITHSCORES".to_string(),
+        }),
+    }
+}
+
+/// Converts a WITHSCORES reply to pairs, keeping members as raw bytes.
+#[inline]
+pub fn frame_to_scored_bytes(frame: Frame) -> Result<Vec<(Bytes, f64)>, crate::Error> {
+    match frame {
+        Frame::Array(arr) => {
+            if arr.len() % 2 != 0 {
+                return Err(crate::Error::Protocol {
+                    message: "WITHSCORES response must have even number of elements".to_string(),
+                });
+            }
+
+            let mut result = Vec::with_capacity(arr.len() / 2);
+            let mut iter = arr.into_iter();
+            while let (Some(member_frame), Some(score_frame)) = (iter.next(), iter.next()) {
+                let member = match member_frame {
+                    Frame::BulkString(Some(member)) => member,
+                    _ => {
+                        return Err(crate::Error::Protocol {
+                            message: "expected bulk string for sorted set member".to_string(),
+                        })
+                    }
+                };
+                result.push((member, frame_to_float(score_frame)?));
+            }
+            Ok(result)
+        }
+        Frame::Error(e) => Err(crate::Error::Server {
+            message: String::from_utf8_lossy(&e).into_owned(),
+        }),
+        _ => Err(crate::Error::Protocol {
+            message: "expected array frame for WITHSCORES".to_string(),
+        }),
+    }
+}
+
+/// Converts a frame to a sorted set member with score (for ZPOPMIN/ZPOPMAX).
+#[inline]
+pub fn frame_to_zpop_result(frame: Frame) -> Result<Option<(String, f64)>, crate::Error> {
+    match frame {
+        Frame::Null | Frame::BulkString(None) => Ok(None),
+        Frame::Array(mut arr) => {
+            if arr.is_empty() {
+                return Ok(None);
+            }
+            if arr.len() != 2 {
+                return Err(crate::Error::Protocol {
+                    message: "ZPOP response must have 2 elements".to_string(),
+                });
+            }
+
+            let score_frame = arr.pop().unwrap();
+            let member_frame = arr.pop().unwrap();
+
+            let member = frame_to_string(member_frame)?;
             let score = frame_to_float(score_frame)?;
 
             Ok(Some((member, score)))
@@ -1089,7 +2157,7 @@ pub fn frame_to_zpop_result(frame: Frame) -> Result<Option<(String, f64)>, crate
 #[inline]
 pub fn frame_to_bzpop_result(frame: Frame) -> Result<Option<(String, String, f64)>, crate::Error> {
     match frame {
-        Frame::Null => Ok(None),
+        Frame::Null | Frame::BulkString(None) => Ok(None),
         Frame::Array(mut arr) => {
             if arr.len() != 3 {
                 return Err(crate::Error::Protocol {
@@ -1120,6 +2188,24 @@ pub fn frame_to_bzpop_result(frame: Frame) -> Result<Option<(String, String, f64
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_write_to_matches_encoder() {
+        let cmds = [
+            ping(),
+            get("key"),
+            set("key", Bytes::from(vec![b'x'; 1234])),
+            Cmd::new("SET").arg("").arg(Bytes::from_static(b"\r\n\0")),
+        ];
+        for cmd in cmds {
+            let mut buf = BytesMut::new();
+            cmd.write_to(&mut buf);
+
+            let mut encoder = crate::proto::codec::Encoder::new();
+            encoder.encode(&cmd.into_frame());
+            assert_eq!(buf, encoder.take());
+        }
+    }
+
     #[test]
     fn test_ping_cmd() {
         let cmd = ping();
@@ -1203,6 +2289,28 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_hello_cmd() {
+        let cmd = hello(
+            3,
+            Some(("user".to_string(), "pass".to_string())),
+            Some("app".to_string()),
+        );
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("HELLO".into())),
+                Frame::BulkString(Some("3".into())),
+                Frame::BulkString(Some("AUTH".into())),
+                Frame::BulkString(Some("user".into())),
+                Frame::BulkString(Some("pass".into())),
+                Frame::BulkString(Some("SETNAME".into())),
+                Frame::BulkString(Some("app".into()))
+            ])
+        );
+    }
+
     #[test]
     fn test_mget_cmd() {
         let cmd = mget(vec!["key1".to_string(), "key2".to_string()]);
@@ -1234,6 +2342,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mget_bytes_cmd_non_utf8_keys() {
+        let key = Bytes::from_static(b"\xff\xfe\x00key");
+        let cmd = mget_bytes(vec![key.clone(), Bytes::from("plain")]);
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("MGET".into())),
+                Frame::BulkString(Some(key)),
+                Frame::BulkString(Some("plain".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_mset_bytes_cmd_non_utf8_keys() {
+        let key = Bytes::from_static(b"\xc3\x28");
+        let value = Bytes::from_static(b"\x00\x01");
+        let cmd = mset_bytes(vec![
+            (key.clone(), value.clone()),
+            (Bytes::from("plain"), Bytes::from("v")),
+        ]);
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("MSET".into())),
+                Frame::BulkString(Some(key)),
+                Frame::BulkString(Some(value)),
+                Frame::BulkString(Some("plain".into())),
+                Frame::BulkString(Some("v".into()))
+            ])
+        );
+    }
+
     #[test]
     fn test_setnx_cmd() {
         let cmd = setnx("key", "value");
@@ -1253,10 +2395,51 @@ mod tests {
         assert_eq!(
             cmd.into_frame(),
             Frame::Array(vec![
-                Frame::BulkString(Some("SETEX".into())),
+                Frame::BulkString(Some("SET".into())),
                 Frame::BulkString(Some("key".into())),
-                Frame::BulkString(Some("60".into())),
-                Frame::BulkString(Some("value".into()))
+                Frame::BulkString(Some("value".into())),
+                Frame::BulkString(Some("EX".into())),
+                Frame::BulkString(Some("60".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_set_with_expiry_opt_cmd() {
+        for (expiry, keyword, value) in [
+            (Expiry::Seconds(60), "EX", "60"),
+            (Expiry::Millis(1500), "PX", "1500"),
+            (Expiry::UnixSeconds(1_700_000_000), "EXAT", "1700000000"),
+            (
+                Expiry::UnixMillis(1_700_000_000_123),
+                "PXAT",
+                "1700000000123",
+            ),
+        ] {
+            assert_eq!(
+                set_with_expiry_opt("key", "value", expiry).into_frame(),
+                Frame::Array(vec![
+                    Frame::BulkString(Some("SET".into())),
+                    Frame::BulkString(Some("key".into())),
+                    Frame::BulkString(Some("value".into())),
+                    Frame::BulkString(Some(keyword.into())),
+                    Frame::BulkString(Some(value.into()))
+                ])
+            );
+        }
+    }
+
+    #[test]
+    fn test_set_with_expiry_sub_second_uses_px() {
+        let cmd = set_with_expiry("key", "value", std::time::Duration::from_millis(250));
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("SET".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("value".into())),
+                Frame::BulkString(Some("PX".into())),
+                Frame::BulkString(Some("250".into()))
             ])
         );
     }
@@ -1274,28 +2457,478 @@ mod tests {
     }
 
     #[test]
-    fn test_append_cmd() {
-        let cmd = append("key", "value");
+    fn test_getex_cmd() {
+        let cmd = getex("key", Some(std::time::Duration::from_millis(1500)));
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("GETEX".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("PX".into())),
+                Frame::BulkString(Some("1500".into()))
+            ])
+        );
+
+        let cmd = getex("key", None);
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("GETEX".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("PERSIST".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_set_get_cmd() {
+        let cmd = set_get("key", "value", Some(std::time::Duration::from_secs(30)));
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("SET".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("value".into())),
+                Frame::BulkString(Some("EX".into())),
+                Frame::BulkString(Some("30".into())),
+                Frame::BulkString(Some("GET".into()))
+            ])
+        );
+
+        let cmd = set_get("key", "value", Some(std::time::Duration::from_millis(1500)));
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("SET".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("value".into())),
+                Frame::BulkString(Some("PX".into())),
+                Frame::BulkString(Some("1500".into())),
+                Frame::BulkString(Some("GET".into()))
+            ])
+        );
+
+        let cmd = set_get("key", "value", None);
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("SET".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("value".into())),
+                Frame::BulkString(Some("GET".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_set_get_reply_for_new_key_is_none() {
+        assert_eq!(frame_to_bytes(Frame::BulkString(None)).unwrap(), None);
+        assert_eq!(frame_to_bytes(Frame::Null).unwrap(), None);
+        assert_eq!(
+            frame_to_bytes(Frame::BulkString(Some("old".into()))).unwrap(),
+            Some(Bytes::from("old"))
+        );
+    }
+
+    #[test]
+    fn test_append_cmd() {
+        let cmd = append("key", "value");
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("APPEND".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("value".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_strlen_cmd() {
+        let cmd = strlen("key");
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("STRLEN".into())),
+                Frame::BulkString(Some("key".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_scan_match_and_unlink_cmds() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        assert_eq!(
+            scan_match(7, "session:*", 100).into_frame(),
+            Frame::Array(vec![
+                bulk("SCAN"),
+                bulk("7"),
+                bulk("MATCH"),
+                bulk("session:*"),
+                bulk("COUNT"),
+                bulk("100")
+            ])
+        );
+        assert_eq!(
+            unlink(vec!["a".to_string(), "b".to_string()]).into_frame(),
+            Frame::Array(vec![bulk("UNLINK"), bulk("a"), bulk("b")])
+        );
+    }
+
+    #[test]
+    fn test_zunion_zinter_zdiff_cmds() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        let keys = || vec!["a".to_string(), "b".to_string()];
+        assert_eq!(
+            zunion(keys(), None, None, false).into_frame(),
+            Frame::Array(vec![bulk("ZUNION"), bulk("2"), bulk("a"), bulk("b")])
+        );
+        assert_eq!(
+            zinter(keys(), Some(vec![2.0, 0.5]), Some(Aggregate::Max), true).into_frame(),
+            Frame::Array(vec![
+                bulk("ZINTER"),
+                bulk("2"),
+                bulk("a"),
+                bulk("b"),
+                bulk("WEIGHTS"),
+                bulk("2"),
+                bulk("0.5"),
+                bulk("AGGREGATE"),
+                bulk("MAX"),
+                bulk("WITHSCORES")
+            ])
+        );
+        assert_eq!(
+            zunion(keys(), None, Some(Aggregate::Min), false).into_frame(),
+            Frame::Array(vec![
+                bulk("ZUNION"),
+                bulk("2"),
+                bulk("a"),
+                bulk("b"),
+                bulk("AGGREGATE"),
+                bulk("MIN")
+            ])
+        );
+        assert_eq!(
+            zdiff(keys(), true).into_frame(),
+            Frame::Array(vec![
+                bulk("ZDIFF"),
+                bulk("2"),
+                bulk("a"),
+                bulk("b"),
+                bulk("WITHSCORES")
+            ])
+        );
+    }
+
+    #[test]
+    fn test_frame_to_scored_members() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        let frame = Frame::Array(vec![bulk("x"), bulk("1.5"), bulk("y"), bulk("inf")]);
+        assert_eq!(
+            frame_to_scored_members(frame).unwrap(),
+            vec![("x".to_string(), 1.5), ("y".to_string(), f64::INFINITY)]
+        );
+        assert!(frame_to_scored_members(Frame::Array(vec![bulk("x")])).is_err());
+    }
+
+    #[test]
+    fn test_zrangestore_cmd() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        assert_eq!(
+            zrangestore("dst", "src", "0", "9", ZRangeOptions::new()).into_frame(),
+            Frame::Array(vec![
+                bulk("ZRANGESTORE"),
+                bulk("dst"),
+                bulk("src"),
+                bulk("0"),
+                bulk("9")
+            ])
+        );
+
+        let opts = ZRangeOptions::new().by_score().limit(5, 10);
+        assert_eq!(
+            zrangestore("dst", "src", "(1", "+inf", opts).into_frame(),
+            Frame::Array(vec![
+                bulk("ZRANGESTORE"),
+                bulk("dst"),
+                bulk("src"),
+                bulk("(1"),
+                bulk("+inf"),
+                bulk("BYSCORE"),
+                bulk("LIMIT"),
+                bulk("5"),
+                bulk("10")
+            ])
+        );
+
+        let opts = ZRangeOptions::new().by_lex().rev();
+        assert_eq!(
+            zrangestore("dst", "src", "[z", "-", opts).into_frame(),
+            Frame::Array(vec![
+                bulk("ZRANGESTORE"),
+                bulk("dst"),
+                bulk("src"),
+                bulk("[z"),
+                bulk("-"),
+                bulk("BYLEX"),
+                bulk("REV")
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lpop_count_cmd() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        assert_eq!(
+            lpop_count("list", 3).into_frame(),
+            Frame::Array(vec![bulk("LPOP"), bulk("list"), bulk("3")])
+        );
+        assert_eq!(
+            rpop_count("list", 2).into_frame(),
+            Frame::Array(vec![bulk("RPOP"), bulk("list"), bulk("2")])
+        );
+    }
+
+    #[test]
+    fn test_frame_to_popped() {
+        let popped = Frame::Array(vec![
+            Frame::BulkString(Some(Bytes::from("a"))),
+            Frame::BulkString(Some(Bytes::from("b"))),
+        ]);
+        assert_eq!(
+            frame_to_popped(popped).unwrap(),
+            vec![Bytes::from("a"), Bytes::from("b")]
+        );
+        assert!(frame_to_popped(Frame::Null).unwrap().is_empty());
+        assert!(frame_to_popped(Frame::BulkString(None)).unwrap().is_empty());
+        assert!(frame_to_popped(Frame::Array(vec![])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_info_cmd() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        assert_eq!(
+            info("replication").into_frame(),
+            Frame::Array(vec![bulk("INFO"), bulk("replication")])
+        );
+    }
+
+    #[test]
+    fn test_find_info_field() {
+        let info = b"# Replication\r\nrole:master\r\nconnected_slaves:1\r\n\
+            slave0:ip=10.0.0.2,port=6379,state=online\r\nmaster_repl_offset:42\r\n";
+        assert_eq!(find_info_field(info, "role"), Some("master".to_string()));
+        assert_eq!(
+            find_info_field(info, "connected_slaves"),
+            Some("1".to_string())
+        );
+        // A field that is a prefix of another must not match it
+        assert_eq!(find_info_field(info, "master_repl"), None);
+        assert_eq!(find_info_field(info, "used_memory"), None);
+    }
+
+    #[test]
+    fn test_cmd_from_args() {
+        let cmd = Cmd::from_args(["SET", "key", "value"]).unwrap();
+        assert_eq!(
+            cmd.into_frame(),
+            set(Bytes::from("key"), Bytes::from("value")).into_frame()
+        );
+
+        let empty: [&'static str; 0] = [];
+        assert!(matches!(
+            Cmd::from_args(empty),
+            Err(crate::Error::InvalidArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cmd_arg_opt_and_arg_if() {
+        let cmd = Cmd::new("SET")
+            .arg("key")
+            .arg_opt(None::<&str>)
+            .arg_opt(Some("NX"))
+            .arg_if(false, "GET")
+            .arg_if(true, "KEEPTTL");
+        assert_eq!(
+            cmd.into_frame(),
+            Cmd::from_args(["SET", "key", "NX", "KEEPTTL"])
+                .unwrap()
+                .into_frame()
+        );
+    }
+
+    #[test]
+    fn test_cmd_parse_line() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        let cmd = Cmd::parse_line(r#"  SET  greeting "hello world" "" "say \"hi\"" "#).unwrap();
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                bulk("SET"),
+                bulk("greeting"),
+                bulk("hello world"),
+                bulk(""),
+                bulk(r#"say "hi""#),
+            ])
+        );
+
+        assert!(matches!(
+            Cmd::parse_line("   "),
+            Err(crate::Error::InvalidArgument { .. })
+        ));
+        assert!(matches!(
+            Cmd::parse_line(r#"SET key "open"#),
+            Err(crate::Error::InvalidArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_frame_response_overflow() {
+        let reply = Frame::Error(b"ERR increment or decrement would overflow".to_vec());
+        assert!(matches!(
+            parse_frame_response(reply),
+            Err(crate::Error::Overflow)
+        ));
+
+        let reply = Frame::Error(b"ERR value is not an integer or out of range".to_vec());
+        assert!(matches!(
+            parse_frame_response(reply),
+            Err(crate::Error::Server { .. })
+        ));
+    }
+
+    #[test]
+    fn test_is_sensitive() {
+        assert!(is_sensitive(b"AUTH"));
+        assert!(is_sensitive(b"auth"));
+        assert!(is_sensitive(b"Config"));
+        assert!(!is_sensitive(b"GET"));
+        assert!(!is_sensitive(b"AUTHX"));
+    }
+
+    #[test]
+    fn test_redacted_hides_sensitive_arguments() {
+        let frame = auth_with_username("admin".to_string(), "hunter2".to_string()).into_frame();
+        assert_eq!(format!("{:?}", Redacted::Frame(&frame)), "AUTH <redacted>");
+
+        let cmd = Cmd::new("config")
+            .arg("SET")
+            .arg("requirepass")
+            .arg("hunter2");
+        assert_eq!(format!("{:?}", Redacted::Cmd(&cmd)), "config <redacted>");
+
+        let cmd = get("user:1");
+        assert_eq!(format!("{:?}", Redacted::Cmd(&cmd)), format!("{:?}", cmd));
+
+        let mut data = BytesMut::new();
+        auth("hunter2").write_to(&mut data);
+        get("user:1").write_to(&mut data);
+        let shown = format!("{:?}", Redacted::Encoded(&data));
+        assert!(shown.starts_with("[AUTH <redacted>, "), "{shown}");
+        assert!(!shown.contains("hunter2"), "{shown}");
+        assert_eq!(
+            format!("{:?}", Redacted::Encoded(b"*2\r\n$4")),
+            "<6 encoded bytes>"
+        );
+    }
+
+    #[test]
+    fn test_bytes_helpers_move_payloads() {
+        let payload = Bytes::from(vec![0xff_u8; 64]);
+        let ptr = payload.as_ptr();
+        let bulk = |b: &Bytes| Frame::BulkString(Some(b.clone()));
+
+        let bytes = frame_to_bytes(bulk(&payload)).unwrap().unwrap();
+        assert_eq!(bytes.as_ptr(), ptr);
+
+        let items = frame_to_vec_bytes(Frame::Array(vec![bulk(&payload), Frame::Null])).unwrap();
+        assert_eq!(items[0].as_ref().unwrap().as_ptr(), ptr);
+
+        let pair = Frame::Array(vec![bulk(&payload), bulk(&payload)]);
+        let pairs = frame_to_pairs(pair.clone()).unwrap();
+        assert_eq!(pairs[0].0.as_ptr(), ptr);
+        assert_eq!(pairs[0].1.as_ptr(), ptr);
+
+        let map = frame_to_hashmap(pair).unwrap();
+        assert_eq!(map.values().next().unwrap().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_blocking_pop_timeout_from_wire() {
+        let mut decoder = crate::proto::codec::Decoder::new();
+        decoder.append(b"*-1\r\n");
+        let frame = decoder.decode().unwrap().unwrap();
+        assert_eq!(frame_to_blocking_pop(frame).unwrap(), None);
+
+        decoder.append(b"*2\r\n$4\r\nlist\r\n$1\r\na\r\n");
+        let frame = decoder.decode().unwrap().unwrap();
+        assert_eq!(
+            frame_to_blocking_pop(frame).unwrap(),
+            Some(("list".to_string(), Bytes::from("a")))
+        );
+    }
+
+    #[test]
+    fn test_nil_bulk_string_replies_map_to_none() {
+        // RESP2 servers answer ZRANK for a missing member with `$-1`
+        assert_eq!(
+            frame_to_optional_int(Frame::BulkString(None)).unwrap(),
+            None
+        );
+        assert_eq!(
+            frame_to_blocking_pop(Frame::BulkString(None)).unwrap(),
+            None
+        );
+        assert_eq!(frame_to_zpop_result(Frame::BulkString(None)).unwrap(), None);
+        assert_eq!(
+            frame_to_bzpop_result(Frame::BulkString(None)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bitcount_cmd() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
         assert_eq!(
-            cmd.into_frame(),
+            bitcount("key", Some((0, 0)), Some(BitUnit::Bit)).into_frame(),
             Frame::Array(vec![
-                Frame::BulkString(Some("APPEND".into())),
-                Frame::BulkString(Some("key".into())),
-                Frame::BulkString(Some("value".into()))
+                bulk("BITCOUNT"),
+                bulk("key"),
+                bulk("0"),
+                bulk("0"),
+                bulk("BIT")
             ])
         );
+        assert_eq!(
+            bitcount("key", Some((1, -1)), None).into_frame(),
+            Frame::Array(vec![bulk("BITCOUNT"), bulk("key"), bulk("1"), bulk("-1")])
+        );
+        assert_eq!(
+            bitcount("key", None, None).into_frame(),
+            Frame::Array(vec![bulk("BITCOUNT"), bulk("key")])
+        );
     }
 
     #[test]
-    fn test_strlen_cmd() {
-        let cmd = strlen("key");
+    fn test_bitpos_cmd() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
         assert_eq!(
-            cmd.into_frame(),
+            bitpos("key", true, Some((2, 10)), Some(BitUnit::Byte)).into_frame(),
             Frame::Array(vec![
-                Frame::BulkString(Some("STRLEN".into())),
-                Frame::BulkString(Some("key".into()))
+                bulk("BITPOS"),
+                bulk("key"),
+                bulk("1"),
+                bulk("2"),
+                bulk("10"),
+                bulk("BYTE")
             ])
         );
+        assert_eq!(
+            bitpos("key", false, None, None).into_frame(),
+            Frame::Array(vec![bulk("BITPOS"), bulk("key"), bulk("0")])
+        );
     }
 
     #[test]
@@ -1325,6 +2958,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_key_list_builders_match_string_keys() {
+        let strings = || vec!["key1".to_string(), "key2".to_string()];
+        let strs: &[&'static str] = &["key1", "key2"];
+        let bytes = [Bytes::from("key1"), Bytes::from("key2")];
+
+        let expected = exists(strings()).into_frame();
+        assert_eq!(exists(strs.iter().copied()).into_frame(), expected);
+        assert_eq!(exists(bytes.iter().cloned()).into_frame(), expected);
+
+        let expected = mget(strings()).into_frame();
+        assert_eq!(mget(strs.iter().copied()).into_frame(), expected);
+        assert_eq!(mget(bytes.iter().cloned()).into_frame(), expected);
+
+        let expected = unlink(strings()).into_frame();
+        assert_eq!(unlink(strs.iter().copied()).into_frame(), expected);
+        assert_eq!(unlink(bytes).into_frame(), expected);
+    }
+
+    #[test]
+    fn test_reset_cmd() {
+        let cmd = reset();
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![Frame::BulkString(Some("RESET".into()))])
+        );
+    }
+
     #[test]
     fn test_key_type_cmd() {
         let cmd = key_type("key");
@@ -1337,6 +2998,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_object_freq_cmd() {
+        let cmd = object_freq("key");
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("OBJECT".into())),
+                Frame::BulkString(Some("FREQ".into())),
+                Frame::BulkString(Some("key".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_frame_to_object_stat() {
+        assert_eq!(frame_to_object_stat(Frame::Integer(5)).unwrap(), Some(5));
+        assert_eq!(frame_to_object_stat(Frame::Null).unwrap(), None);
+
+        let frame = Frame::Error(
+            b"ERR An LFU maxmemory policy is not selected, access frequency not tracked. \
+              Please note that when switching between policies at runtime LRU and LFU data \
+              will take some time to adjust."
+                .to_vec(),
+        );
+        match frame_to_object_stat(frame) {
+            Err(crate::Error::InvalidArgument { message }) => {
+                assert!(message.starts_with("OBJECT FREQ requires an LFU maxmemory-policy"));
+            }
+            other => panic!("expected InvalidArgument, got {:?}", other),
+        }
+
+        let frame = Frame::Error(
+            b"ERR An LFU maxmemory policy is selected, idle time not tracked.".to_vec(),
+        );
+        match frame_to_object_stat(frame) {
+            Err(crate::Error::InvalidArgument { message }) => {
+                assert!(message.starts_with("OBJECT IDLETIME requires a non-LFU"));
+            }
+            other => panic!("expected InvalidArgument, got {:?}", other),
+        }
+
+        let frame = Frame::Error(b"ERR syntax error".to_vec());
+        assert!(matches!(
+            frame_to_object_stat(frame),
+            Err(crate::Error::Server { .. })
+        ));
+    }
+
+    #[test]
+    fn test_frame_to_key_type() {
+        let cases = [
+            ("string", crate::KeyType::String),
+            ("list", crate::KeyType::List),
+            ("set", crate::KeyType::Set),
+            ("zset", crate::KeyType::ZSet),
+            ("hash", crate::KeyType::Hash),
+            ("stream", crate::KeyType::Stream),
+            ("none", crate::KeyType::None),
+        ];
+        for (reply, expected) in cases {
+            let frame = Frame::SimpleString(reply.as_bytes().to_vec());
+            assert_eq!(frame_to_key_type(frame).unwrap(), expected);
+        }
+
+        let frame = Frame::SimpleString(b"ReJSON-RL".to_vec());
+        assert_eq!(
+            frame_to_key_type(frame).unwrap(),
+            crate::KeyType::Other("ReJSON-RL".to_string())
+        );
+
+        let frame = Frame::Error(b"ERR wrong number of arguments".to_vec());
+        assert!(frame_to_key_type(frame).is_err());
+    }
+
     #[test]
     fn test_expire_cmd() {
         let cmd = expire("key", 60);
@@ -1412,6 +3147,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hscan_cmd() {
+        assert_eq!(
+            hscan("hash", 0, false).into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("HSCAN".into())),
+                Frame::BulkString(Some("hash".into())),
+                Frame::BulkString(Some("0".into()))
+            ])
+        );
+        assert_eq!(
+            hscan("hash", 17, true).into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("HSCAN".into())),
+                Frame::BulkString(Some("hash".into())),
+                Frame::BulkString(Some("17".into())),
+                Frame::BulkString(Some("NOVALUES".into()))
+            ])
+        );
+    }
+
     #[test]
     fn test_frame_to_scan_response() {
         let frame = Frame::Array(vec![
@@ -1628,6 +3384,280 @@ mod tests {
         assert_eq!(result.get("field2"), Some(&Bytes::from("value2")));
     }
 
+    #[test]
+    fn test_frame_to_pairs_keeps_order_and_binary_fields() {
+        let frame = Frame::Array(vec![
+            Frame::BulkString(Some("zeta".into())),
+            Frame::BulkString(Some("1".into())),
+            Frame::BulkString(Some(Bytes::from_static(b"\xff\x00bin"))),
+            Frame::BulkString(Some("2".into())),
+            Frame::BulkString(Some("alpha".into())),
+            Frame::BulkString(Some("3".into())),
+        ]);
+        let result = frame_to_pairs(frame).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                (Bytes::from("zeta"), Bytes::from("1")),
+                (Bytes::from_static(b"\xff\x00bin"), Bytes::from("2")),
+                (Bytes::from("alpha"), Bytes::from("3")),
+            ]
+        );
+
+        let odd = Frame::Array(vec![Frame::BulkString(Some("field".into()))]);
+        assert!(matches!(
+            frame_to_pairs(odd),
+            Err(crate::Error::Protocol { .. })
+        ));
+    }
+
+    #[test]
+    fn test_script_cmds() {
+        assert_eq!(
+            script_load("return 1").into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("SCRIPT".into())),
+                Frame::BulkString(Some("LOAD".into())),
+                Frame::BulkString(Some("return 1".into()))
+            ])
+        );
+        assert_eq!(
+            evalsha(
+                "abc",
+                vec![Bytes::from("k1"), Bytes::from("k2")],
+                vec![Bytes::from("a")]
+            )
+            .into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("EVALSHA".into())),
+                Frame::BulkString(Some("abc".into())),
+                Frame::BulkString(Some("2".into())),
+                Frame::BulkString(Some("k1".into())),
+                Frame::BulkString(Some("k2".into())),
+                Frame::BulkString(Some("a".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_function_cmds() {
+        let code = "#!lua name=mylib\nredis.register_function('f', function() return 1 end)";
+        assert_eq!(
+            function_load(false, code).into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("FUNCTION".into())),
+                Frame::BulkString(Some("LOAD".into())),
+                Frame::BulkString(Some(Bytes::from(code.to_string())))
+            ])
+        );
+        assert_eq!(
+            function_load(true, code).into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("FUNCTION".into())),
+                Frame::BulkString(Some("LOAD".into())),
+                Frame::BulkString(Some("REPLACE".into())),
+                Frame::BulkString(Some(Bytes::from(code.to_string())))
+            ])
+        );
+        assert_eq!(
+            fcall(
+                "f",
+                vec![Bytes::from("k")],
+                vec![Bytes::from("a"), Bytes::from("b")]
+            )
+            .into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("FCALL".into())),
+                Frame::BulkString(Some("f".into())),
+                Frame::BulkString(Some("1".into())),
+                Frame::BulkString(Some("k".into())),
+                Frame::BulkString(Some("a".into())),
+                Frame::BulkString(Some("b".into()))
+            ])
+        );
+        assert_eq!(
+            fcall_ro("f", vec![], vec![]).into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("FCALL_RO".into())),
+                Frame::BulkString(Some("f".into())),
+                Frame::BulkString(Some("0".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_config_cmds() {
+        assert_eq!(
+            config_get("maxmemory*").into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("CONFIG".into())),
+                Frame::BulkString(Some("GET".into())),
+                Frame::BulkString(Some("maxmemory*".into()))
+            ])
+        );
+        assert_eq!(
+            config_set("maxmemory", "100mb").into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("CONFIG".into())),
+                Frame::BulkString(Some("SET".into())),
+                Frame::BulkString(Some("maxmemory".into())),
+                Frame::BulkString(Some("100mb".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_frame_to_config_multiple_params() {
+        let frame = Frame::Array(vec![
+            Frame::BulkString(Some("maxmemory".into())),
+            Frame::BulkString(Some("0".into())),
+            Frame::BulkString(Some("maxmemory-policy".into())),
+            Frame::BulkString(Some("noeviction".into())),
+            Frame::BulkString(Some("maxmemory-samples".into())),
+            Frame::BulkString(Some("5".into())),
+        ]);
+        let config = frame_to_config(frame).unwrap();
+        assert_eq!(config.len(), 3);
+        assert_eq!(config.get("maxmemory").map(String::as_str), Some("0"));
+        assert_eq!(
+            config.get("maxmemory-policy").map(String::as_str),
+            Some("noeviction")
+        );
+        assert_eq!(
+            config.get("maxmemory-samples").map(String::as_str),
+            Some("5")
+        );
+
+        assert!(frame_to_config(Frame::Array(vec![])).unwrap().is_empty());
+        assert!(matches!(
+            frame_to_config(Frame::Array(vec![Frame::BulkString(Some(
+                "maxmemory".into()
+            ))])),
+            Err(crate::Error::Protocol { .. })
+        ));
+    }
+
+    #[test]
+    fn test_publish_and_subscribe_cmds() {
+        assert_eq!(
+            publish("news", "hello").into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("PUBLISH".into())),
+                Frame::BulkString(Some("news".into())),
+                Frame::BulkString(Some("hello".into()))
+            ])
+        );
+        assert_eq!(
+            subscribe(vec!["a".to_string(), "b".to_string()]).into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("SUBSCRIBE".into())),
+                Frame::BulkString(Some("a".into())),
+                Frame::BulkString(Some("b".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_client_setinfo_cmd() {
+        assert_eq!(
+            client_setinfo("lib-name", "muxis").into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("CLIENT".into())),
+                Frame::BulkString(Some("SETINFO".into())),
+                Frame::BulkString(Some("lib-name".into())),
+                Frame::BulkString(Some("muxis".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_acl_cmds() {
+        assert_eq!(
+            acl_whoami().into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("ACL".into())),
+                Frame::BulkString(Some("WHOAMI".into()))
+            ])
+        );
+        assert_eq!(
+            acl_cat(Some("dangerous")).into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("ACL".into())),
+                Frame::BulkString(Some("CAT".into())),
+                Frame::BulkString(Some("dangerous".into()))
+            ])
+        );
+        assert_eq!(
+            acl_getuser("alice").into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("ACL".into())),
+                Frame::BulkString(Some("GETUSER".into())),
+                Frame::BulkString(Some("alice".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_frame_to_acl_user() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        let frame = Frame::Array(vec![
+            bulk("flags"),
+            Frame::Array(vec![bulk("on"), bulk("sanitize-payload")]),
+            bulk("passwords"),
+            Frame::Array(vec![bulk(
+                "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8",
+            )]),
+            bulk("commands"),
+            bulk("+@all -debug"),
+            bulk("keys"),
+            bulk("~cache:* %R~app:*"),
+            bulk("channels"),
+            bulk("&*"),
+            bulk("selectors"),
+            Frame::Array(vec![Frame::Array(vec![
+                bulk("commands"),
+                bulk("-@all +get"),
+                bulk("keys"),
+                bulk("~readonly:*"),
+                bulk("channels"),
+                bulk(""),
+            ])]),
+            bulk("root-only-field"),
+            Frame::Integer(1),
+        ]);
+
+        let user = frame_to_acl_user(frame).unwrap().unwrap();
+        assert_eq!(user.flags, vec!["on", "sanitize-payload"]);
+        assert_eq!(user.passwords.len(), 1);
+        assert_eq!(user.commands, "+@all -debug");
+        assert_eq!(user.keys, vec!["~cache:*", "%R~app:*"]);
+        assert_eq!(user.channels, vec!["&*"]);
+        assert_eq!(
+            user.selectors,
+            vec![vec![
+                ("commands".to_string(), "-@all +get".to_string()),
+                ("keys".to_string(), "~readonly:*".to_string()),
+                ("channels".to_string(), String::new()),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_frame_to_acl_user_redis6_patterns_and_missing_user() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        let frame = Frame::Array(vec![
+            bulk("flags"),
+            Frame::Array(vec![bulk("on"), bulk("allkeys")]),
+            bulk("keys"),
+            Frame::Array(vec![bulk("*")]),
+        ]);
+        let user = frame_to_acl_user(frame).unwrap().unwrap();
+        assert_eq!(user.keys, vec!["*"]);
+        assert!(user.selectors.is_empty());
+
+        assert_eq!(frame_to_acl_user(Frame::Null).unwrap(), None);
+    }
+
     #[test]
     fn test_frame_to_vec_string() {
         let frame = Frame::Array(vec![
@@ -1640,6 +3670,42 @@ mod tests {
         assert_eq!(result[1], "str2");
     }
 
+    #[test]
+    fn test_lpushx_cmd() {
+        let cmd = lpushx(
+            "key".to_string(),
+            vec![Bytes::from("val1"), Bytes::from("val2")],
+        );
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("LPUSHX".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("val1".into())),
+                Frame::BulkString(Some("val2".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_rpushx_cmd() {
+        let cmd = rpushx("key".to_string(), vec![Bytes::from("val1")]);
+        assert_eq!(
+            cmd.into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("RPUSHX".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("val1".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_pushx_missing_key_reply() {
+        // LPUSHX/RPUSHX reply 0 when the list does not exist
+        assert_eq!(frame_to_int(Frame::Integer(0)).unwrap(), 0);
+    }
+
     #[test]
     fn test_lpush_cmd() {
         let cmd = lpush(
@@ -1725,6 +3791,19 @@ mod tests {
         assert_eq!(value, Bytes::from("value"));
     }
 
+    #[test]
+    fn test_frame_to_blocking_pop_value() {
+        let frame = Frame::Array(vec![
+            Frame::BulkString(Some("mylist".into())),
+            Frame::BulkString(Some("value".into())),
+        ]);
+        assert_eq!(
+            frame_to_blocking_pop_value(frame).unwrap(),
+            Some(Bytes::from("value"))
+        );
+        assert_eq!(frame_to_blocking_pop_value(Frame::Null).unwrap(), None);
+    }
+
     #[test]
     fn test_sadd_cmd() {
         let cmd = sadd("key".to_string(), vec![Bytes::from("a"), Bytes::from("b")]);
@@ -1929,6 +4008,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_zadd_gt_lt_cmds() {
+        for (cmd, comparison) in [
+            (zadd_gt("board", 42.5, "alice"), "GT"),
+            (zadd_lt("board", 42.5, "alice"), "LT"),
+        ] {
+            assert_eq!(
+                cmd.into_frame(),
+                Frame::Array(vec![
+                    Frame::BulkString(Some("ZADD".into())),
+                    Frame::BulkString(Some("board".into())),
+                    Frame::BulkString(Some(comparison.into())),
+                    Frame::BulkString(Some("CH".into())),
+                    Frame::BulkString(Some("42.5".into())),
+                    Frame::BulkString(Some("alice".into()))
+                ])
+            );
+        }
+    }
+
+    #[test]
+    fn test_zadd_ch_reply_reports_change() {
+        assert!(frame_to_bool(Frame::Integer(1)).unwrap());
+        assert!(!frame_to_bool(Frame::Integer(0)).unwrap());
+    }
+
+    #[test]
+    fn test_zadd_incr_cmd() {
+        assert_eq!(
+            zadd_incr("key", 1.5, "member", Some(ZAddCondition::Nx)).into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("ZADD".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("NX".into())),
+                Frame::BulkString(Some("INCR".into())),
+                Frame::BulkString(Some("1.5".into())),
+                Frame::BulkString(Some("member".into()))
+            ])
+        );
+        assert_eq!(
+            zadd_incr("key", -2.0, "member", Some(ZAddCondition::Xx)).into_frame(),
+            Frame::Array(vec![
+                Frame::BulkString(Some("ZADD".into())),
+                Frame::BulkString(Some("key".into())),
+                Frame::BulkString(Some("XX".into())),
+                Frame::BulkString(Some("INCR".into())),
+                Frame::BulkString(Some("-2".into())),
+                Frame::BulkString(Some("member".into()))
+            ])
+        );
+    }
+
+    #[test]
+    fn test_zadd_incr_null_reply() {
+        assert_eq!(frame_to_optional_float(Frame::Null).unwrap(), None);
+        assert_eq!(
+            frame_to_optional_float(Frame::BulkString(None)).unwrap(),
+            None
+        );
+        assert_eq!(
+            frame_to_optional_float(Frame::BulkString(Some("4".into()))).unwrap(),
+            Some(4.0)
+        );
+    }
+
     #[test]
     fn test_zincrby_cmd() {
         let cmd = zincrby("key", 2.5, "member");
@@ -1,9 +1,12 @@
 use std::fmt;
+use std::net::SocketAddr;
 use std::time::Duration;
 
-use crate::proto::codec::{Decoder, Encoder};
+use crate::proto::codec::{Decoder, Encoder, StreamStart};
 use crate::proto::frame::Frame;
-use tokio::io::{self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf};
+use tokio::io::{
+    self, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufWriter, ReadHalf, WriteHalf,
+};
 
 /// A connection to a Redis server.
 ///
@@ -15,6 +18,9 @@ pub struct Connection<S> {
     encoder: Encoder,
     read_timeout: Option<Duration>,
     write_timeout: Option<Duration>,
+    write_buffer_size: usize,
+    peer_addr: Option<SocketAddr>,
+    local_addr: Option<SocketAddr>,
 }
 
 /// Read half of a split connection.
@@ -25,8 +31,11 @@ pub struct ConnectionReader<S> {
 }
 
 /// Write half of a split connection.
+///
+/// With a write buffer configured, written frames are collected in memory until
+/// the buffer fills up or [`flush`](Self::flush) is called.
 pub struct ConnectionWriter<S> {
-    stream: WriteHalf<S>,
+    stream: BufWriter<WriteHalf<S>>,
     encoder: Encoder,
     timeout: Option<Duration>,
 }
@@ -43,16 +52,43 @@ where
             encoder: Encoder::new(),
             read_timeout: None,
             write_timeout: None,
+            write_buffer_size: 0,
+            peer_addr: None,
+            local_addr: None,
         }
     }
 
+    /// Records the socket addresses of the underlying TCP connection.
+    ///
+    /// The stream type does not expose them in general (e.g. TLS), so they are
+    /// passed by whoever opened the socket.
+    pub fn with_socket_addrs(
+        mut self,
+        peer_addr: Option<SocketAddr>,
+        local_addr: Option<SocketAddr>,
+    ) -> Self {
+        self.peer_addr = peer_addr;
+        self.local_addr = local_addr;
+        self
+    }
+
+    /// Returns the address of the server, if known.
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+
+    /// Returns the local address of the connection, if known.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
     /// Configures read and write timeouts for this connection.
     pub fn with_timeouts(
         mut self,
         read_timeout: Option<Duration>,
         write_timeout: Option<Duration>,
     ) -> Self {
-        self.read_timeout = read_timeout;
+        self.set_read_timeout(read_timeout);
         self.write_timeout = write_timeout;
         self
     }
@@ -63,6 +99,14 @@ where
         self
     }
 
+    /// Configures the write buffer used by the write half after [`split`](Self::split).
+    ///
+    /// A size of 0 (the default) writes every frame to the socket immediately.
+    pub fn with_write_buffer(mut self, size: usize) -> Self {
+        self.write_buffer_size = size;
+        self
+    }
+
     /// Splits the connection into a read half and a write half.
     ///
     /// This allows independent reading and writing, which is useful for
@@ -76,7 +120,7 @@ where
                 timeout: self.read_timeout,
             },
             ConnectionWriter {
-                stream: write_half,
+                stream: BufWriter::with_capacity(self.write_buffer_size, write_half),
                 encoder: self.encoder,
                 timeout: self.write_timeout,
             },
@@ -86,48 +130,49 @@ where
     /// Writes a frame to the connection.
     pub async fn write_frame(&mut self, frame: &Frame) -> Result<(), std::io::Error> {
         self.encoder.encode(frame);
-        let data = self.encoder.take();
-
-        match self.write_timeout {
-            Some(duration) => {
-                tokio::time::timeout(duration, self.stream.write_all(&data))
-                    .await
-                    .map_err(|_| {
-                        std::io::Error::new(std::io::ErrorKind::TimedOut, "write timeout")
-                    })??;
-            }
-            None => {
-                self.stream.write_all(&data).await?;
-            }
-        }
-        Ok(())
+        let result = write_all(&mut self.stream, self.write_timeout, self.encoder.buffer()).await;
+        self.encoder.reset();
+        result
+    }
+
+    /// Sets or clears the read timeout.
+    ///
+    /// The timeout bounds reading a whole frame, so a peer that stops sending in
+    /// the middle of a frame (e.g. a half-open connection) is detected even if it
+    /// delivered some bytes before.
+    pub fn set_read_timeout(&mut self, timeout: Option<Duration>) {
+        self.read_timeout = timeout;
     }
 
     /// Reads a frame from the connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`](crate::Error::Timeout) if the read timeout
+    /// elapses before a complete frame arrives. Bytes received so far stay
+    /// buffered, so reading again resumes the same frame.
     pub async fn read_frame(&mut self) -> Result<Frame, crate::Error> {
+        match self.read_timeout {
+            Some(duration) => tokio::time::timeout(duration, self.read_frame_unbounded())
+                .await
+                .map_err(|_| crate::Error::Timeout)?,
+            None => self.read_frame_unbounded().await,
+        }
+    }
+
+    /// Reads from the socket until a frame is decoded, however long it takes.
+    async fn read_frame_unbounded(&mut self) -> Result<Frame, crate::Error> {
         loop {
-            if let Some(frame) = self
-                .decoder
-                .decode()
-                .map_err(|e| crate::Error::Protocol { message: e })?
-            {
+            if let Some(frame) = self.decoder.decode()? {
                 return Ok(frame);
             }
 
             let mut buf = vec![0u8; 4096];
-            let read_future = self.stream.read(&mut buf);
-
-            let n = match self.read_timeout {
-                Some(duration) => tokio::time::timeout(duration, read_future)
-                    .await
-                    .map_err(|_| crate::Error::Io {
-                        source: std::io::Error::new(std::io::ErrorKind::TimedOut, "read timeout"),
-                    })?
-                    .map_err(|e| crate::Error::Io { source: e })?,
-                None => read_future
-                    .await
-                    .map_err(|e| crate::Error::Io { source: e })?,
-            };
+            let n = self
+                .stream
+                .read(&mut buf)
+                .await
+                .map_err(|e| crate::Error::Io { source: e })?;
 
             if n == 0 {
                 return Err(crate::Error::Protocol {
@@ -144,30 +189,69 @@ where
     S: AsyncRead + AsyncWrite,
 {
     /// Reads a frame from the connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`](crate::Error::Timeout) if the read timeout
+    /// elapses before a complete frame arrives, as [`Connection::read_frame`] does.
     pub async fn read_frame(&mut self) -> Result<Frame, crate::Error> {
+        self.read_with(Decoder::decode).await
+    }
+
+    /// Reads every complete frame available, waiting until there is at least one.
+    ///
+    /// Only for connections where every incoming frame goes to the same consumer,
+    /// such as one in push mode; otherwise replies to later requests would be
+    /// taken as well.
+    pub async fn read_frames(&mut self) -> Result<Vec<Frame>, crate::Error> {
+        self.read_with(|decoder| {
+            let frames = decoder.decode_all()?;
+            Ok((!frames.is_empty()).then_some(frames))
+        })
+        .await
+    }
+
+    /// Reads the start of a reply whose array elements are read one at a time.
+    ///
+    /// After [`StreamStart::Array`], the elements follow and must each be read with
+    /// [`read_frame`](Self::read_frame) before the next reply.
+    pub async fn read_stream_start(&mut self) -> Result<StreamStart, crate::Error> {
+        self.read_with(Decoder::decode_stream_start).await
+    }
+
+    /// Reads from the socket until `decode` yields a value, failing with
+    /// [`Error::Timeout`](crate::Error::Timeout) if the read timeout elapses first.
+    ///
+    /// The timeout bounds the whole value, not each socket read, so a peer that
+    /// stops in the middle of a frame is detected.
+    async fn read_with<T>(
+        &mut self,
+        decode: fn(&mut Decoder) -> Result<Option<T>, crate::Error>,
+    ) -> Result<T, crate::Error> {
+        match self.timeout {
+            Some(duration) => tokio::time::timeout(duration, self.read_with_unbounded(decode))
+                .await
+                .map_err(|_| crate::Error::Timeout)?,
+            None => self.read_with_unbounded(decode).await,
+        }
+    }
+
+    /// Reads from the socket until `decode` yields a value, however long it takes.
+    async fn read_with_unbounded<T>(
+        &mut self,
+        decode: fn(&mut Decoder) -> Result<Option<T>, crate::Error>,
+    ) -> Result<T, crate::Error> {
         loop {
-            if let Some(frame) = self
-                .decoder
-                .decode()
-                .map_err(|e| crate::Error::Protocol { message: e })?
-            {
-                return Ok(frame);
+            if let Some(value) = decode(&mut self.decoder)? {
+                return Ok(value);
             }
 
             let mut buf = vec![0u8; 4096];
-            let read_future = self.stream.read(&mut buf);
-
-            let n = match self.timeout {
-                Some(duration) => tokio::time::timeout(duration, read_future)
-                    .await
-                    .map_err(|_| crate::Error::Io {
-                        source: std::io::Error::new(std::io::ErrorKind::TimedOut, "read timeout"),
-                    })?
-                    .map_err(|e| crate::Error::Io { source: e })?,
-                None => read_future
-                    .await
-                    .map_err(|e| crate::Error::Io { source: e })?,
-            };
+            let n = self
+                .stream
+                .read(&mut buf)
+                .await
+                .map_err(|e| crate::Error::Io { source: e })?;
 
             if n == 0 {
                 return Err(crate::Error::Protocol {
@@ -184,23 +268,47 @@ where
     S: AsyncRead + AsyncWrite,
 {
     /// Writes a frame to the connection.
+    ///
+    /// With a write buffer, the frame may stay buffered until [`flush`](Self::flush).
     pub async fn write_frame(&mut self, frame: &Frame) -> Result<(), std::io::Error> {
         self.encoder.encode(frame);
-        let data = self.encoder.take();
+        let result = write_all(&mut self.stream, self.timeout, self.encoder.buffer()).await;
+        self.encoder.reset();
+        result
+    }
 
+    /// Writes already RESP-encoded bytes to the connection.
+    ///
+    /// With a write buffer, the bytes may stay buffered until [`flush`](Self::flush).
+    pub async fn write_encoded(&mut self, data: &[u8]) -> Result<(), std::io::Error> {
+        write_all(&mut self.stream, self.timeout, data).await
+    }
+
+    /// Writes any buffered bytes to the socket.
+    pub async fn flush(&mut self) -> Result<(), std::io::Error> {
         match self.timeout {
-            Some(duration) => {
-                tokio::time::timeout(duration, self.stream.write_all(&data))
-                    .await
-                    .map_err(|_| {
-                        std::io::Error::new(std::io::ErrorKind::TimedOut, "write timeout")
-                    })??;
-            }
-            None => {
-                self.stream.write_all(&data).await?;
-            }
+            Some(duration) => tokio::time::timeout(duration, self.stream.flush())
+                .await
+                .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "write timeout"))?,
+            None => self.stream.flush().await,
         }
-        Ok(())
+    }
+}
+
+/// Writes all of `data`, failing with `TimedOut` if it takes longer than `timeout`.
+async fn write_all<W>(
+    stream: &mut W,
+    timeout: Option<Duration>,
+    data: &[u8],
+) -> Result<(), std::io::Error>
+where
+    W: AsyncWrite + Unpin,
+{
+    match timeout {
+        Some(duration) => tokio::time::timeout(duration, stream.write_all(data))
+            .await
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "write timeout"))?,
+        None => stream.write_all(data).await,
     }
 }
 
@@ -209,6 +317,7 @@ impl<S> fmt::Debug for Connection<S> {
         f.debug_struct("Connection")
             .field("read_timeout", &self.read_timeout)
             .field("write_timeout", &self.write_timeout)
+            .field("write_buffer_size", &self.write_buffer_size)
             .finish()
     }
 }
@@ -274,4 +383,46 @@ mod tests {
 
         tokio::join!(server, client);
     }
+
+    #[tokio::test]
+    async fn test_read_timeout_on_partial_frame() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            stream.write_all(b"$5\r\nhel").await.unwrap();
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let mut conn = Connection::new(stream);
+        conn.set_read_timeout(Some(Duration::from_millis(100)));
+
+        let started = std::time::Instant::now();
+        let result = conn.read_frame().await;
+        assert!(matches!(result, Err(crate::Error::Timeout)), "{:?}", result);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_split_read_timeout_on_partial_frame() {
+        let (client, mut server) = tokio::io::duplex(1024);
+        server.write_all(b"$5\r\nhel").await.unwrap();
+
+        let mut conn = Connection::new(client);
+        conn.set_read_timeout(Some(Duration::from_millis(100)));
+        let (mut reader, _writer) = conn.split();
+
+        let started = tokio::time::Instant::now();
+        let result = reader.read_frame().await;
+        assert!(matches!(result, Err(crate::Error::Timeout)), "{:?}", result);
+        assert_eq!(started.elapsed(), Duration::from_millis(100));
+
+        // The bytes received so far stay buffered
+        server.write_all(b"lo\r\n").await.unwrap();
+        assert_eq!(
+            reader.read_frame().await.unwrap(),
+            Frame::BulkString(Some("hello".into()))
+        );
+    }
 }
@@ -9,24 +9,64 @@
 //! - [`command`] - Command builders
 //! - [`builder`] - Client builder
 //! - [`multiplexed`] - Multiplexed connection for concurrent requests
+//! - [`monitor`] - MONITOR output stream
+//! - [`pubsub`] - Pub/Sub subscriptions
+//! - [`resolver`] - Pluggable address resolution
+//! - [`retry`] - Connection retry policy
+//! - [`script`] - Lua scripts invoked by digest
+//! - [`types`] - Typed reply values
 //!
 
 #![warn(missing_docs)]
 
 use crate::proto::frame::Frame;
 use bytes::Bytes;
+use futures::stream::{self, Stream, StreamExt};
+use std::sync::Arc;
 use std::time::Duration;
 
 pub use crate::proto::error::{Error, Result};
 
+/// Local argument-count validation.
+mod arity;
 /// Client builder configuration.
 pub mod builder;
 /// Command construction helpers.
 pub mod command;
 /// Low-level connection management.
 pub mod connection;
+/// MONITOR output stream.
+pub mod monitor;
 /// Multiplexing logic.
 pub mod multiplexed;
+/// Pub/Sub subscriptions.
+pub mod pubsub;
+/// Pluggable address resolution.
+pub mod resolver;
+/// Connection retry policy.
+pub mod retry;
+/// Lua scripts invoked by digest.
+pub mod script;
+/// MULTI/EXEC transactions.
+pub mod transaction;
+/// Typed reply values.
+pub mod types;
+/// Conversions from reply frames to Rust types.
+pub mod value;
+
+pub use monitor::MonitorStream;
+pub use multiplexed::MuxMetrics;
+pub use pubsub::{PubSub, PubSubEvent, PubSubMessage, ReconnectEvent};
+pub use resolver::AddressResolver;
+pub use retry::RetryPolicy;
+pub use script::Script;
+pub use transaction::TxnBuilder;
+pub use types::{
+    AclUser, Aggregate, BitUnit, Expiry, FlushMode, KeyType, Ttl, ZAddCondition, ZRangeOptions,
+};
+#[cfg(feature = "json")]
+pub use value::Json;
+pub use value::{FromRedisValue, RedisValue};
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "tls")] {
@@ -34,29 +74,242 @@ cfg_if::cfg_if! {
     }
 }
 
+/// Protocol version requested by HELLO during connection setup.
+///
+/// The multiplexer flattens RESP3 replies into their RESP2 shapes, so commands
+/// parse the same replies whichever protocol the handshake settled on.
+#[cfg(feature = "resp3")]
+const HELLO_PROTOCOL_VERSION: u8 = 3;
+
 /// Connection configuration settings.
 #[derive(Debug, Clone)]
 pub(crate) struct ConnectionSettings {
     pub client_name: Option<String>,
+    pub advertise_lib: bool,
+    pub username: Option<String>,
     pub password: Option<String>,
     pub database: Option<u8>,
     pub queue_size: usize,
+    pub backpressure: bool,
+    pub strict_types: bool,
+    pub validate_arity: bool,
     pub read_timeout: Option<Duration>,
     pub write_timeout: Option<Duration>,
+    pub keepalive_ping: Option<Duration>,
     pub max_frame_size: usize,
+    pub tcp_nodelay: bool,
+    pub write_buffer_size: usize,
+    pub resolver: Option<Arc<dyn AddressResolver>>,
+    #[cfg(feature = "tls")]
+    pub alpn_protocols: Vec<Vec<u8>>,
+}
+
+/// The address and settings a client connected with.
+///
+/// Kept so that equivalent connections can be opened later, e.g. to re-subscribe
+/// a [`PubSub`] after its connection dropped.
+#[derive(Debug)]
+struct Endpoint {
+    address: String,
+    is_tls: bool,
+    settings: ConnectionSettings,
 }
 
 impl Default for ConnectionSettings {
     fn default() -> Self {
         Self {
             client_name: None,
+            advertise_lib: false,
+            username: None,
             password: None,
             database: None,
             queue_size: 1024,
+            backpressure: true,
+            strict_types: false,
+            validate_arity: false,
             read_timeout: None,
             write_timeout: None,
+            keepalive_ping: None,
             max_frame_size: 512 * 1024 * 1024,
+            tcp_nodelay: true,
+            write_buffer_size: 0,
+            resolver: None,
+            #[cfg(feature = "tls")]
+            alpn_protocols: Vec::new(),
+        }
+    }
+}
+
+/// Maximum number of keys whose type is remembered for strict type checking.
+const TYPE_CACHE_CAPACITY: usize = 1024;
+
+/// `SCAN COUNT` hint used by [`Client::del_pattern`], bounding each UNLINK batch.
+const DEL_PATTERN_BATCH: usize = 500;
+
+/// `SCAN COUNT` hint used by [`Client::count_keys`].
+const COUNT_KEYS_BATCH: usize = 1000;
+
+/// Maximum number of times [`Client::transaction`] runs before giving up on a
+/// transaction whose watched keys keep changing.
+pub const TRANSACTION_ATTEMPTS: usize = 5;
+
+/// Sets `KEYS[1]` to `ARGV[2]` if it currently holds `ARGV[1]`; used by
+/// [`Client::compare_and_set`].
+static COMPARE_AND_SET: std::sync::LazyLock<Script> = std::sync::LazyLock::new(|| {
+    Script::new(
+        "if redis.call('GET', KEYS[1]) == ARGV[1] then \
+             redis.call('SET', KEYS[1], ARGV[2]) \
+             return 1 \
+         end \
+         return 0",
+    )
+});
+
+/// Appends `ARGV[1]` to `KEYS[1]`, then keeps only the last `ARGV[2]` bytes if the
+/// value grew longer; used by [`Client::append_capped`].
+static APPEND_CAPPED: std::sync::LazyLock<Script> = std::sync::LazyLock::new(|| {
+    Script::new(
+        "local len = redis.call('APPEND', KEYS[1], ARGV[1]) \
+         local max = tonumber(ARGV[2]) \
+         if len > max then \
+             local tail = redis.call('GETRANGE', KEYS[1], -max, -1) \
+             redis.call('SET', KEYS[1], tail, 'KEEPTTL') \
+             return max \
+         end \
+         return len",
+    )
+});
+
+/// Increments `KEYS[1]` and starts its `ARGV[1]` milliseconds expiry on the first
+/// hit of a window; used by [`Client::rate_limit_incr`].
+static RATE_LIMIT_INCR: std::sync::LazyLock<Script> = std::sync::LazyLock::new(|| {
+    Script::new(
+        "local count = redis.call('INCR', KEYS[1]) \
+         if count == 1 then \
+             redis.call('PEXPIRE', KEYS[1], ARGV[1]) \
+         end \
+         return count",
+    )
+});
+
+/// Key types learned from earlier replies, used for strict type checking.
+///
+/// Best-effort only: entries go stale when keys expire or are changed by other
+/// clients, and the cache is cleared once it holds [`TYPE_CACHE_CAPACITY`] keys.
+#[derive(Debug, Default)]
+struct TypeCache {
+    types: std::sync::Mutex<std::collections::HashMap<String, KeyType>>,
+}
+
+impl TypeCache {
+    fn types(&self) -> std::sync::MutexGuard<'_, std::collections::HashMap<String, KeyType>> {
+        self.types
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn check(&self, key: &str, expected: KeyType) -> Result<()> {
+        match self.types().get(key) {
+            Some(actual) if *actual != expected => Err(Error::WrongType {
+                key: key.to_string(),
+                actual: actual.to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn remember(&self, key: &str, key_type: KeyType) {
+        let mut types = self.types();
+        if key_type == KeyType::None {
+            types.remove(key);
+            return;
         }
+        if types.len() >= TYPE_CACHE_CAPACITY && !types.contains_key(key) {
+            types.clear();
+        }
+        types.insert(key.to_string(), key_type);
+    }
+
+    fn forget(&self, key: &str) {
+        self.types().remove(key);
+    }
+
+    fn clear(&self) {
+        self.types().clear();
+    }
+}
+
+/// Rejects NaN and infinite increments before a command is sent.
+///
+/// Formatting such a value yields `NaN` or `inf`, which Redis rejects with an
+/// unhelpful "not a valid float" error.
+fn check_finite(name: &str, value: f64) -> Result<()> {
+    if !value.is_finite() {
+        return Err(Error::InvalidArgument {
+            message: format!("{} must be a finite number, got {}", name, value),
+        });
+    }
+    Ok(())
+}
+
+/// Rejects NaN sorted set scores before a command is sent.
+///
+/// Unlike increments, `+inf` and `-inf` are valid scores.
+fn check_score(value: f64) -> Result<()> {
+    if value.is_nan() {
+        return Err(Error::InvalidArgument {
+            message: "score must not be NaN".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Rejects ZUNION/ZINTER weights that do not pair up with the keys or are NaN.
+fn check_weights(keys: &[&str], weights: &[f64]) -> Result<()> {
+    if weights.len() != keys.len() {
+        return Err(Error::InvalidArgument {
+            message: format!("expected {} weights, got {}", keys.len(), weights.len()),
+        });
+    }
+    weights.iter().try_for_each(|weight| check_score(*weight))
+}
+
+/// Rejects a BITCOUNT/BITPOS unit without a range, which Redis cannot express.
+fn check_bit_unit(range: Option<(i64, i64)>, unit: Option<BitUnit>) -> Result<()> {
+    if range.is_none() && unit.is_some() {
+        return Err(Error::InvalidArgument {
+            message: "a bit range unit requires a range".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Explains the syntax error an older server returns for an option it does not know.
+fn map_unsupported_option(e: Error, option: &str, version: &str) -> Error {
+    match e.inner() {
+        Error::Server { message } if message.contains("syntax error") => Error::InvalidArgument {
+            message: format!("{} requires Redis {} or later ({})", option, version, e),
+        },
+        _ => e,
+    }
+}
+
+/// Returns whether an address scheme selects a TLS connection.
+///
+/// `redis+tls` is accepted as an alias for `rediss`.
+fn scheme_uses_tls(scheme: &str) -> Result<bool> {
+    match scheme {
+        "redis" => Ok(false),
+        "rediss" => Ok(true),
+        #[cfg(feature = "tls")]
+        "redis+tls" => Ok(true),
+        #[cfg(not(feature = "tls"))]
+        "redis+tls" => Err(Error::InvalidArgument {
+            message: "redis+tls:// requires the `tls` feature".to_string(),
+        }),
+        _ => Err(Error::InvalidArgument {
+            message: "invalid scheme, expected redis://, rediss:// or redis+tls://".to_string(),
+        }),
     }
 }
 
@@ -81,6 +334,8 @@ impl Default for ConnectionSettings {
 #[derive(Debug, Clone)]
 pub struct Client {
     connection: multiplexed::MultiplexedConnection,
+    endpoint: Arc<Endpoint>,
+    type_cache: Option<Arc<TypeCache>>,
 }
 
 impl Client {
@@ -89,17 +344,49 @@ impl Client {
         is_tls: bool,
         settings: ConnectionSettings,
     ) -> Result<Self> {
-        // Parse the address using url crate for proper validation
-        let parsed_url = url::Url::parse(&address).map_err(|_| Error::InvalidArgument {
-            message: "invalid address format".to_string(),
-        })?;
+        let endpoint = Endpoint {
+            address,
+            is_tls,
+            settings,
+        };
+        let connection = Self::open(&endpoint).await?;
+        let type_cache = endpoint
+            .settings
+            .strict_types
+            .then(|| Arc::new(TypeCache::default()));
+        Ok(Self {
+            connection,
+            endpoint: Arc::new(endpoint),
+            type_cache,
+        })
+    }
 
-        let scheme = parsed_url.scheme();
-        if scheme != "redis" && scheme != "rediss" {
-            return Err(Error::InvalidArgument {
-                message: "invalid scheme, expected redis:// or rediss://".to_string(),
-            });
+    /// Fails fast if strict type checking knows that `key` holds another type.
+    fn check_type(&self, key: &str, expected: KeyType) -> Result<()> {
+        match &self.type_cache {
+            Some(cache) => cache.check(key, expected),
+            None => Ok(()),
         }
+    }
+
+    /// Records the type of `key` for strict type checking.
+    fn remember_type(&self, key: &str, key_type: KeyType) {
+        if let Some(cache) = &self.type_cache {
+            cache.remember(key, key_type);
+        }
+    }
+
+    /// Opens and initializes a new multiplexed connection to `endpoint`.
+    async fn open(endpoint: &Endpoint) -> Result<multiplexed::MultiplexedConnection> {
+        let settings = &endpoint.settings;
+
+        // Parse the address using url crate for proper validation
+        let parsed_url =
+            url::Url::parse(&endpoint.address).map_err(|_| Error::InvalidArgument {
+                message: "invalid address format".to_string(),
+            })?;
+
+        let is_tls = endpoint.is_tls || scheme_uses_tls(parsed_url.scheme())?;
 
         let host = parsed_url
             .host_str()
@@ -109,38 +396,52 @@ impl Client {
 
         let port = parsed_url.port().unwrap_or(6379);
 
-        let addr = format!("{}:{}", host, port);
-        let stream = tokio::net::TcpStream::connect(&addr)
-            .await
+        // Reject a bad server name before opening a socket
+        #[cfg(feature = "tls")]
+        let server_name = if is_tls {
+            Some(tls::server_name(host)?)
+        } else {
+            None
+        };
+
+        let stream = match &settings.resolver {
+            Some(resolver) => {
+                let socket_addr = resolver.resolve(host, port).await?;
+                tokio::net::TcpStream::connect(socket_addr).await
+            }
+            None => tokio::net::TcpStream::connect(format!("{}:{}", host, port)).await,
+        }
+        .map_err(|e| Error::Io { source: e })?;
+        stream
+            .set_nodelay(settings.tcp_nodelay)
             .map_err(|e| Error::Io { source: e })?;
+        let (peer_addr, local_addr) = (stream.peer_addr().ok(), stream.local_addr().ok());
 
         if is_tls {
             #[cfg(feature = "tls")]
             {
-                let connector = tls::TlsConnectorInner::new()?.connector();
-                let domain = rustls::pki_types::ServerName::try_from(host)
-                    .map_err(|e| Error::InvalidArgument {
-                        message: e.to_string(),
-                    })?
-                    .to_owned();
+                let connector = tls::TlsConnectorInner::new(&settings.alpn_protocols)?.connector();
+                let domain = match server_name {
+                    Some(name) => name,
+                    None => tls::server_name(host)?,
+                };
                 let tls_stream = connector
                     .connect(domain, stream)
                     .await
-                    .map_err(|e| Error::Io { source: e })?;
+                    .map_err(tls::handshake_error)?;
 
                 let mut connection = connection::Connection::new(tls_stream)
+                    .with_socket_addrs(peer_addr, local_addr)
                     .with_timeouts(settings.read_timeout, settings.write_timeout)
-                    .with_max_frame_size(settings.max_frame_size);
-                Self::initialize_connection(
-                    &mut connection,
-                    settings.password,
-                    settings.database,
-                    settings.client_name,
-                )
-                .await?;
+                    .with_max_frame_size(settings.max_frame_size)
+                    .with_write_buffer(settings.write_buffer_size);
+                Self::initialize_connection(&mut connection, settings).await?;
                 let connection =
-                    multiplexed::MultiplexedConnection::new(connection, settings.queue_size);
-                Ok(Self { connection })
+                    multiplexed::MultiplexedConnection::new(connection, settings.queue_size)
+                        .with_backpressure(settings.backpressure)
+                        .with_keepalive(settings.keepalive_ping);
+                connection.set_database(settings.database.unwrap_or(0));
+                Ok(connection)
             }
             #[cfg(not(feature = "tls"))]
             {
@@ -150,32 +451,38 @@ impl Client {
             }
         } else {
             let mut connection = connection::Connection::new(stream)
+                .with_socket_addrs(peer_addr, local_addr)
                 .with_timeouts(settings.read_timeout, settings.write_timeout)
-                .with_max_frame_size(settings.max_frame_size);
-            Self::initialize_connection(
-                &mut connection,
-                settings.password,
-                settings.database,
-                settings.client_name,
-            )
-            .await?;
+                .with_max_frame_size(settings.max_frame_size)
+                .with_write_buffer(settings.write_buffer_size);
+            Self::initialize_connection(&mut connection, settings).await?;
             let connection =
-                multiplexed::MultiplexedConnection::new(connection, settings.queue_size);
-            Ok(Self { connection })
+                multiplexed::MultiplexedConnection::new(connection, settings.queue_size)
+                    .with_backpressure(settings.backpressure)
+                    .with_keepalive(settings.keepalive_ping);
+            connection.set_database(settings.database.unwrap_or(0));
+            Ok(connection)
         }
     }
 
     async fn initialize_connection<S>(
         connection: &mut connection::Connection<S>,
-        password: Option<String>,
-        database: Option<u8>,
-        client_name: Option<String>,
+        settings: &ConnectionSettings,
     ) -> Result<()>
     where
         S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
     {
-        if let Some(pwd) = password {
-            let auth_cmd = command::auth(pwd);
+        // Servers without HELLO get separate AUTH and CLIENT SETNAME commands
+        #[cfg(feature = "resp3")]
+        let separate = !Self::hello(connection, settings).await?;
+        #[cfg(not(feature = "resp3"))]
+        let separate = true;
+
+        if let Some(pwd) = settings.password.as_ref().filter(|_| separate) {
+            let auth_cmd = match &settings.username {
+                Some(user) => command::auth_with_username(user.clone(), pwd.clone()),
+                None => command::auth(pwd.clone()),
+            };
             connection
                 .write_frame(&auth_cmd.into_frame())
                 .await
@@ -186,7 +493,7 @@ impl Client {
             }
         }
 
-        if let Some(db) = database {
+        if let Some(db) = settings.database {
             let select_cmd = command::select(db);
             connection
                 .write_frame(&select_cmd.into_frame())
@@ -195,8 +502,8 @@ impl Client {
             let _resp = connection.read_frame().await?;
         }
 
-        if let Some(name) = client_name {
-            let setname_cmd = command::client_setname(name);
+        if let Some(name) = settings.client_name.as_ref().filter(|_| separate) {
+            let setname_cmd = command::client_setname(name.clone());
             connection
                 .write_frame(&setname_cmd.into_frame())
                 .await
@@ -204,12 +511,111 @@ impl Client {
             let _resp = connection.read_frame().await?;
         }
 
+        if settings.advertise_lib {
+            for (attr, value) in [
+                ("lib-name", "muxis"),
+                ("lib-ver", env!("CARGO_PKG_VERSION")),
+            ] {
+                let setinfo_cmd = command::client_setinfo(attr, value);
+                connection
+                    .write_frame(&setinfo_cmd.into_frame())
+                    .await
+                    .map_err(|e| Error::Io { source: e })?;
+                // Servers before Redis 7.2 reply with an error, which is fine to ignore
+                let _resp = connection.read_frame().await?;
+            }
+        }
+
         Ok(())
     }
 
+    /// Performs AUTH and CLIENT SETNAME in a single HELLO round-trip.
+    ///
+    /// Nothing is sent when neither a password nor a client name is configured.
+    /// Returns false if the server does not know HELLO (before Redis 6.0), in
+    /// which case nothing was done.
+    #[cfg(feature = "resp3")]
+    async fn hello<S>(
+        connection: &mut connection::Connection<S>,
+        settings: &ConnectionSettings,
+    ) -> Result<bool>
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+    {
+        if settings.password.is_none() && settings.client_name.is_none() {
+            return Ok(true);
+        }
+
+        let auth = settings.password.as_ref().map(|pwd| {
+            let user = settings.username.as_deref().unwrap_or("default");
+            (user.to_string(), pwd.clone())
+        });
+        let hello_cmd = command::hello(HELLO_PROTOCOL_VERSION, auth, settings.client_name.clone());
+        connection
+            .write_frame(&hello_cmd.into_frame())
+            .await
+            .map_err(|e| Error::Io { source: e })?;
+
+        match connection.read_frame().await? {
+            Frame::Error(e) if e.starts_with(b"ERR unknown command") => Ok(false),
+            Frame::Error(_) if settings.password.is_some() => Err(Error::Auth),
+            Frame::Error(e) => Err(Error::Server {
+                message: String::from_utf8_lossy(&e).into_owned(),
+            }),
+            _ => Ok(true),
+        }
+    }
+
+    /// Sends a command, naming it in the error if the server rejects it.
+    async fn request(&self, frame: Frame) -> Result<Frame> {
+        let name = command::command_name(&frame);
+        let reply = self.connection.send_command(frame).await?;
+        command::parse_frame_response(reply).map_err(|e| match name {
+            Some(name) => e.with_command(&String::from_utf8_lossy(&name)),
+            None => e,
+        })
+    }
+
+    /// Like [`request`](Self::request), encoding `cmd` directly.
+    async fn request_cmd(&self, cmd: &command::Cmd) -> Result<Frame> {
+        let reply = self.connection.send_cmd(cmd).await?;
+        command::parse_frame_response(reply)
+            .map_err(|e| e.with_command(&String::from_utf8_lossy(cmd.name())))
+    }
+
+    /// Sends an arbitrary command and returns its raw reply.
+    ///
+    /// Useful for commands without a dedicated method, such as module commands.
+    /// With [`ClientBuilder::validate_arity`](crate::ClientBuilder::validate_arity)
+    /// enabled, a well-known command with too few arguments is refused before it
+    /// is sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if arity validation rejects the command,
+    /// or an [`Error::Command`] if the server replies with an error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::{Client, Cmd};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let reply = client.send_command(Cmd::new("OBJECT").arg("ENCODING").arg("key")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_command(&mut self, cmd: command::Cmd) -> Result<Frame> {
+        if self.endpoint.settings.validate_arity {
+            arity::check(cmd.name(), cmd.arg_count())?;
+        }
+        self.request_cmd(&cmd).await
+    }
+
     /// Connects to a Redis server using the provided address.
     ///
     /// The address should be in the format `redis://host:port` or `rediss://host:port` (for TLS).
+    /// `redis+tls://host:port` is accepted as an alias for `rediss://`.
     ///
     /// # Arguments
     ///
@@ -220,8 +626,121 @@ impl Client {
     /// A `Result` containing the connected `Client` or an error.
     pub async fn connect<T: AsRef<str>>(addr: T) -> Result<Self> {
         let addr_str = addr.as_ref().to_string();
-        let is_tls = addr_str.starts_with("rediss://");
-        Self::connect_inner(addr_str, is_tls, ConnectionSettings::default()).await
+        Self::connect_inner(addr_str, false, ConnectionSettings::default()).await
+    }
+
+    /// Connects to a Redis server, failing if the connection is not established in time.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The connection string (e.g., "redis://127.0.0.1:6379")
+    /// * `timeout` - Maximum time to wait for the connection to be established
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] with [`std::io::ErrorKind::TimedOut`] if the timeout elapses.
+    pub async fn connect_timeout<T: AsRef<str>>(addr: T, timeout: Duration) -> Result<Self> {
+        tokio::time::timeout(timeout, Self::connect(addr))
+            .await
+            .map_err(|_| Error::Io {
+                source: std::io::Error::new(std::io::ErrorKind::TimedOut, "connect timeout"),
+            })?
+    }
+
+    /// Connects to a Redis server, retrying transient failures with exponential backoff.
+    ///
+    /// Only IO errors (e.g., connection refused) are retried; invalid addresses and
+    /// authentication failures are returned immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The connection string (e.g., "redis://127.0.0.1:6379")
+    /// * `policy` - Number of attempts, backoff delays and overall timeout
+    ///
+    /// # Returns
+    ///
+    /// The first successfully connected `Client`, or the error of the last attempt.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::{Client, RetryPolicy};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::connect_with_retry("redis://127.0.0.1:6379", RetryPolicy::default())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_retry<T: AsRef<str>>(addr: T, policy: RetryPolicy) -> Result<Self> {
+        let addr = addr.as_ref();
+        let attempts = async {
+            let mut attempt = 0;
+            loop {
+                attempt += 1;
+                match Self::connect(addr).await {
+                    Ok(client) => return Ok(client),
+                    Err(Error::Io { source }) if attempt < policy.attempts => {
+                        tracing::debug!(attempt, error = %source, "connect failed, retrying");
+                        tokio::time::sleep(policy.backoff(attempt)).await;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        };
+
+        match policy.timeout {
+            Some(timeout) => {
+                tokio::time::timeout(timeout, attempts)
+                    .await
+                    .map_err(|_| Error::Io {
+                        source: std::io::Error::new(
+                            std::io::ErrorKind::TimedOut,
+                            "connect timeout",
+                        ),
+                    })?
+            }
+            None => attempts.await,
+        }
+    }
+
+    /// Waits until the server answers PING, polling until `timeout` elapses.
+    ///
+    /// Unlike [`connect`](Self::connect), which only establishes the connection, this
+    /// blocks until the server actually serves commands, e.g. while it still replies
+    /// `LOADING` after a restart.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for a successful PING
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Timeout`] if no PING succeeded in time.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// client.wait_ready(Duration::from_secs(30)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_ready(&mut self, timeout: Duration) -> Result<()> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+        self::timeout(timeout, async {
+            loop {
+                match self.ping().await {
+                    Ok(_) => return Ok(()),
+                    Err(e) => tracing::debug!(error = %e, "server not ready yet"),
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        })
+        .await
     }
 
     /// Sends a PING command to the server.
@@ -231,7 +750,7 @@ impl Client {
     /// Returns `PONG` as bytes if successful.
     pub async fn ping(&mut self) -> Result<Bytes> {
         let cmd = command::ping();
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         command::parse_frame_response(frame)?;
         Ok("PONG".into())
     }
@@ -243,7 +762,7 @@ impl Client {
     /// * `msg` - The message to echo.
     pub async fn echo(&mut self, msg: &str) -> Result<Bytes> {
         let cmd = command::echo(msg.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         let bytes = command::frame_to_bytes(frame)?;
         Ok(bytes.unwrap_or_default())
     }
@@ -258,8 +777,9 @@ impl Client {
     ///
     /// Returns `Some(Bytes)` if the key exists, or `None` if it does not.
     pub async fn get(&mut self, key: &str) -> Result<Option<Bytes>> {
+        self.check_type(key, KeyType::String)?;
         let cmd = command::get(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request_cmd(&cmd).await?;
         command::frame_to_bytes(frame)
     }
 
@@ -271,12 +791,15 @@ impl Client {
     /// * `value` - The value to set.
     pub async fn set(&mut self, key: &str, value: Bytes) -> Result<()> {
         let cmd = command::set(key.to_string(), value);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request_cmd(&cmd).await?;
         command::parse_frame_response(frame)?;
+        self.remember_type(key, KeyType::String);
         Ok(())
     }
 
-    /// Sets the value of a key with an expiration time (SETEX).
+    /// Sets the value of a key with an expiration time (SET ... EX/PX).
+    ///
+    /// Sub-second durations are sent with millisecond precision.
     ///
     /// # Arguments
     ///
@@ -290,7 +813,7 @@ impl Client {
         expiry: Duration,
     ) -> Result<()> {
         let cmd = command::set_with_expiry(key.to_string(), value, expiry);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         command::parse_frame_response(frame)?;
         Ok(())
     }
@@ -304,9 +827,13 @@ impl Client {
     /// # Returns
     ///
     /// The value of the key after the increment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Overflow`] if the result does not fit in an i64.
     pub async fn incr(&mut self, key: &str) -> Result<i64> {
         let cmd = command::incr(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         command::frame_to_int(frame)
     }
 
@@ -316,9 +843,13 @@ impl Client {
     ///
     /// * `key` - The key to increment.
     /// * `amount` - The amount to increment by.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Overflow`] if the result does not fit in an i64.
     pub async fn incr_by(&mut self, key: &str, amount: i64) -> Result<i64> {
         let cmd = command::incr_by(key.to_string(), amount);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         command::frame_to_int(frame)
     }
 
@@ -331,9 +862,13 @@ impl Client {
     /// # Returns
     ///
     /// The value of the key after the decrement.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Overflow`] if the result does not fit in an i64.
     pub async fn decr(&mut self, key: &str) -> Result<i64> {
         let cmd = command::decr(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         command::frame_to_int(frame)
     }
 
@@ -343,9 +878,13 @@ impl Client {
     ///
     /// * `key` - The key to decrement.
     /// * `amount` - The amount to decrement by.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Overflow`] if the result does not fit in an i64.
     pub async fn decr_by(&mut self, key: &str, amount: i64) -> Result<i64> {
         let cmd = command::decr_by(key.to_string(), amount);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         command::frame_to_int(frame)
     }
 
@@ -360,8 +899,9 @@ impl Client {
     /// `true` if the key was removed, `false` if the key did not exist.
     pub async fn del(&mut self, key: &str) -> Result<bool> {
         let cmd = command::del(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         let n = command::frame_to_int(frame)?;
+        self.remember_type(key, KeyType::None);
         Ok(n > 0)
     }
 
@@ -372,7 +912,7 @@ impl Client {
     /// * `password` - The password to use.
     pub async fn auth(&mut self, password: &str) -> Result<()> {
         let cmd = command::auth(password.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         command::parse_frame_response(frame)?;
         Ok(())
     }
@@ -385,324 +925,429 @@ impl Client {
     /// * `password` - The password to use.
     pub async fn auth_with_username(&mut self, username: &str, password: &str) -> Result<()> {
         let cmd = command::auth_with_username(username.to_string(), password.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         command::parse_frame_response(frame)?;
         Ok(())
     }
 
     /// Selects the Redis logical database to use.
     ///
+    /// The selected database is shared by all clones of this client and is
+    /// reported by [`current_database`](Self::current_database).
+    ///
     /// # Arguments
     ///
     /// * `db` - The database index (e.g., 0).
     pub async fn select(&mut self, db: u8) -> Result<()> {
         let cmd = command::select(db);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::parse_frame_response(frame)?;
-        Ok(())
-    }
-
-    /// Sets the name of the current connection.
-    ///
-    /// This name is displayed in the `CLIENT LIST` command output on the server.
-    ///
-    /// # Arguments
-    ///
-    /// * `name` - The name to assign to the connection.
-    pub async fn client_setname(&mut self, name: &str) -> Result<()> {
-        let cmd = command::client_setname(name.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+        let frame = self.request(cmd.into_frame()).await?;
         command::parse_frame_response(frame)?;
+        self.connection.set_database(db);
+        if let Some(cache) = &self.type_cache {
+            cache.clear();
+        }
         Ok(())
     }
 
-    /// Gets the values of all specified keys (MGET).
-    ///
-    /// For every key that does not exist, the corresponding element will be `None`.
+    /// Returns the connection to its default state (RESET).
     ///
-    /// # Arguments
-    ///
-    /// * `keys` - Slice of key names to retrieve.
-    ///
-    /// # Returns
-    ///
-    /// A vector of `Option<Bytes>`, one for each key. `None` for keys that do not exist.
+    /// The server deselects the database (back to 0), discards any MULTI transaction,
+    /// leaves subscribe mode and drops authentication. The connection is shared by all
+    /// clones of this client, so they observe the reset as well. Requires Redis 6.2 or later.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.set("key1", Bytes::from("value1")).await?;
-    /// client.set("key2", Bytes::from("value2")).await?;
-    /// let values = client.mget(&["key1", "key2", "key3"]).await?;
-    /// assert_eq!(values[0], Some(Bytes::from("value1")));
-    /// assert_eq!(values[1], Some(Bytes::from("value2")));
-    /// assert_eq!(values[2], None);
+    /// client.select(2).await?;
+    /// client.reset().await?;
+    /// assert_eq!(client.current_database(), 0);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn mget(&mut self, keys: &[&str]) -> Result<Vec<Option<Bytes>>> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::mget(keys_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_bytes(frame)
+    pub async fn reset(&mut self) -> Result<()> {
+        self.connection.reset().await?;
+        if let Some(cache) = &self.type_cache {
+            cache.clear();
+        }
+        Ok(())
     }
 
-    /// Sets multiple key-value pairs atomically (MSET).
+    /// Streams every command processed by the server (MONITOR).
     ///
-    /// # Arguments
-    ///
-    /// * `pairs` - Slice of (key, value) tuples to set.
+    /// MONITOR turns the connection into a one-way push stream, so this consumes the
+    /// client. Clones of this client share the connection; their commands fail with
+    /// [`Error::InvalidArgument`] from then on. Intended for debugging only, as
+    /// MONITOR has a significant performance cost on the server.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.mset(&[
-    ///     ("key1", Bytes::from("value1")),
-    ///     ("key2", Bytes::from("value2")),
-    /// ]).await?;
+    /// let client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let mut monitor = client.monitor().await?;
+    /// while let Some(line) = monitor.next().await? {
+    ///     println!("{}", line);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn mset(&mut self, pairs: &[(&str, Bytes)]) -> Result<()> {
-        let pairs_vec = pairs
-            .iter()
-            .map(|(k, v)| (k.to_string(), v.clone()))
-            .collect();
-        let cmd = command::mset(pairs_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::parse_frame_response(frame)?;
-        Ok(())
+    pub async fn monitor(self) -> Result<MonitorStream> {
+        let receiver = self.connection.monitor().await?;
+        Ok(MonitorStream::new(receiver))
     }
 
-    /// Sets the value of a key only if it does not exist (SETNX).
+    /// Loads a Lua script into the script cache (SCRIPT LOAD).
+    ///
+    /// # Returns
+    ///
+    /// The SHA1 digest to pass to [`evalsha`](Self::evalsha).
+    pub async fn script_load(&mut self, script: &str) -> Result<String> {
+        let cmd = command::script_load(script.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_string(command::parse_frame_response(frame)?)
+    }
+
+    /// Runs a cached Lua script by its digest (EVALSHA).
+    ///
+    /// See [`Script`] for a helper that loads the script when needed.
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to set.
-    /// * `value` - The value to set.
+    /// * `sha` - The digest returned by [`script_load`](Self::script_load).
+    /// * `keys` - The keys the script accesses, available as `KEYS`.
+    /// * `args` - Additional arguments, available as `ARGV`.
     ///
     /// # Returns
     ///
-    /// `true` if the key was set, `false` if the key already existed.
+    /// The raw reply of the script.
     ///
-    /// # Example
+    /// # Errors
     ///
-    /// ```no_run
-    /// # use muxis::Client;
-    /// # use bytes::Bytes;
-    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let was_set = client.setnx("mykey", Bytes::from("value")).await?;
-    /// assert!(was_set);
-    /// let was_set_again = client.setnx("mykey", Bytes::from("newvalue")).await?;
-    /// assert!(!was_set_again);
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn setnx(&mut self, key: &str, value: Bytes) -> Result<bool> {
-        let cmd = command::setnx(key.to_string(), value);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bool(frame)
+    /// Returns an [`Error::Server`] with a `NOSCRIPT` message, wrapped in
+    /// [`Error::Command`], if the script is not cached.
+    pub async fn evalsha(&mut self, sha: &str, keys: &[&str], args: &[Bytes]) -> Result<Frame> {
+        let keys = keys.iter().map(|k| Bytes::from(k.to_string())).collect();
+        let cmd = command::evalsha(sha, keys, args.to_vec());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)
     }
 
-    /// Sets the value of a key with an expiration in seconds (SETEX).
+    /// Loads a library of Redis Functions (FUNCTION LOAD, Redis 7.0+).
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to set.
-    /// * `seconds` - Expiration time in seconds.
-    /// * `value` - The value to set.
+    /// * `code` - The library source, starting with a `#!lua name=<library>` shebang.
+    /// * `replace` - Replace an existing library with the same name instead of failing.
+    ///
+    /// # Returns
+    ///
+    /// The name of the loaded library.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.setex("mykey", 10, Bytes::from("value")).await?;
+    /// let code = "#!lua name=mylib\n\
+    ///     redis.register_function('echo', function(keys, args) return args[1] end)";
+    /// client.function_load(code, true).await?;
+    /// let reply = client.fcall("echo", &[], &[bytes::Bytes::from("hi")]).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn setex(&mut self, key: &str, seconds: u64, value: Bytes) -> Result<()> {
-        let cmd = command::setex(key.to_string(), seconds, value);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::parse_frame_response(frame)?;
-        Ok(())
+    pub async fn function_load(&mut self, code: &str, replace: bool) -> Result<String> {
+        let cmd = command::function_load(replace, code);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_string(command::parse_frame_response(frame)?)
     }
 
-    /// Gets the value of a key and deletes it atomically (GETDEL).
+    /// Calls a Redis Function (FCALL, Redis 7.0+).
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to get and delete.
+    /// * `function` - The function name.
+    /// * `keys` - The keys the function accesses.
+    /// * `args` - Additional arguments.
     ///
     /// # Returns
     ///
-    /// `Some(Bytes)` if the key exists, or `None` if it does not.
+    /// The raw reply of the function.
+    pub async fn fcall(&mut self, function: &str, keys: &[&str], args: &[Bytes]) -> Result<Frame> {
+        let keys = keys.iter().map(|k| Bytes::from(k.to_string())).collect();
+        let cmd = command::fcall(function, keys, args.to_vec());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)
+    }
+
+    /// Calls a read-only Redis Function (FCALL_RO, Redis 7.0+).
+    ///
+    /// The function must be registered with the `no-writes` flag. Unlike FCALL, the
+    /// command is accepted by replicas, so it can be served by read replicas.
+    ///
+    /// # Arguments
+    ///
+    /// * `function` - The function name.
+    /// * `keys` - The keys the function accesses.
+    /// * `args` - Additional arguments.
+    ///
+    /// # Returns
+    ///
+    /// The raw reply of the function.
+    pub async fn fcall_ro(
+        &mut self,
+        function: &str,
+        keys: &[&str],
+        args: &[Bytes],
+    ) -> Result<Frame> {
+        let keys = keys.iter().map(|k| Bytes::from(k.to_string())).collect();
+        let cmd = command::fcall_ro(function, keys, args.to_vec());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)
+    }
+
+    /// Publishes a message to a channel (PUBLISH).
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to publish to.
+    /// * `message` - The message payload.
+    ///
+    /// # Returns
+    ///
+    /// The number of clients that received the message.
+    pub async fn publish(&mut self, channel: &str, message: Bytes) -> Result<i64> {
+        let cmd = command::publish(channel.to_string(), message);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Subscribes to one or more channels (SUBSCRIBE).
+    ///
+    /// A subscribed connection only receives messages, so this consumes the client.
+    /// Clones of this client share the connection; their commands fail with
+    /// [`Error::InvalidArgument`] from then on instead of being sent. See
+    /// [`PubSub::with_auto_resubscribe`] to survive dropped connections.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - The channels to subscribe to (at least one).
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.set("mykey", Bytes::from("value")).await?;
-    /// let value = client.getdel("mykey").await?;
-    /// assert_eq!(value, Some(Bytes::from("value")));
-    /// let value_again = client.get("mykey").await?;
-    /// assert_eq!(value_again, None);
+    /// let client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let mut pubsub = client.subscribe(&["news"]).await?;
+    /// while let Some(message) = pubsub.next_message().await? {
+    ///     println!("{}: {:?}", message.channel, message.payload);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn getdel(&mut self, key: &str) -> Result<Option<Bytes>> {
-        let cmd = command::getdel(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bytes(frame)
+    pub async fn subscribe(self, channels: &[&str]) -> Result<PubSub> {
+        let channels: Vec<String> = channels.iter().map(|c| c.to_string()).collect();
+        let receiver = self.connection.subscribe(channels.clone()).await?;
+        let endpoint = self.endpoint;
+        let connector: pubsub::Connector = Arc::new(move || {
+            let endpoint = endpoint.clone();
+            Box::pin(async move { Self::open(&endpoint).await })
+        });
+        Ok(PubSub::new(receiver, channels, connector))
     }
 
-    /// Appends a value to a key (APPEND).
-    ///
-    /// If the key does not exist, it is created and set as an empty string, then the value
-    /// is appended.
-    ///
-    /// # Arguments
+    /// Returns the logical database currently selected on this connection.
     ///
-    /// * `key` - The key to append to.
-    /// * `value` - The value to append.
+    /// This is the database configured at connect time, updated by every
+    /// successful [`select`](Self::select).
+    pub fn current_database(&self) -> u8 {
+        self.connection.database()
+    }
+
+    /// Returns the address of the server this client is connected to.
     ///
-    /// # Returns
+    /// This is the resolved socket address, useful to tell which server a hostname
+    /// pointed at. `None` if the address could not be determined.
+    pub fn peer_addr(&self) -> Option<std::net::SocketAddr> {
+        self.connection.peer_addr()
+    }
+
+    /// Returns the local address of this client's connection.
+    pub fn local_addr(&self) -> Option<std::net::SocketAddr> {
+        self.connection.local_addr()
+    }
+
+    /// Returns counters of the underlying multiplexed connection.
     ///
-    /// The length of the string after the append operation.
+    /// Shared by all clones of this client. A `max_in_flight_seen` equal to the
+    /// configured queue size suggests the queue is a bottleneck.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.set("mykey", Bytes::from("Hello")).await?;
-    /// let len = client.append("mykey", Bytes::from(" World")).await?;
-    /// assert_eq!(len, 11);
+    /// client.ping().await?;
+    /// let metrics = client.metrics();
+    /// println!("{} commands, {} errors", metrics.total_commands, metrics.total_errors);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn append(&mut self, key: &str, value: Bytes) -> Result<i64> {
-        let cmd = command::append(key.to_string(), value);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub fn metrics(&self) -> MuxMetrics {
+        self.connection.metrics()
     }
 
-    /// Returns the length of the string value stored at key (STRLEN).
+    /// Sets the name of the current connection.
     ///
-    /// If the key does not exist, returns 0.
+    /// This name is displayed in the `CLIENT LIST` command output on the server.
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to check.
+    /// * `name` - The name to assign to the connection.
+    pub async fn client_setname(&mut self, name: &str) -> Result<()> {
+        let cmd = command::client_setname(name.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)?;
+        Ok(())
+    }
+
+    /// Returns the username of the current connection (ACL WHOAMI).
+    pub async fn acl_whoami(&mut self) -> Result<String> {
+        let cmd = command::acl_whoami();
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_string(command::parse_frame_response(frame)?)
+    }
+
+    /// Lists ACL categories, or the commands in a category (ACL CAT).
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - A category such as `dangerous`, or `None` to list all categories.
+    pub async fn acl_cat(&mut self, category: Option<&str>) -> Result<Vec<String>> {
+        let cmd = command::acl_cat(category);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Returns the ACL rules of a user (ACL GETUSER).
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The user to look up.
     ///
     /// # Returns
     ///
-    /// The length of the string at key, or 0 if the key does not exist.
+    /// The user's rules, or `None` if no such user exists.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.set("mykey", Bytes::from("Hello World")).await?;
-    /// let len = client.strlen("mykey").await?;
-    /// assert_eq!(len, 11);
+    /// if let Some(user) = client.acl_getuser("default").await? {
+    ///     println!("flags: {:?}, commands: {}", user.flags, user.commands);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn strlen(&mut self, key: &str) -> Result<i64> {
-        let cmd = command::strlen(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn acl_getuser(&mut self, username: &str) -> Result<Option<AclUser>> {
+        let cmd = command::acl_getuser(username.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_acl_user(frame)
     }
 
-    /// Checks if one or more keys exist (EXISTS).
+    /// Reads server configuration parameters (CONFIG GET).
     ///
     /// # Arguments
     ///
-    /// * `keys` - Slice of key names to check.
+    /// * `pattern` - A parameter name or glob pattern such as `maxmemory*`.
     ///
     /// # Returns
     ///
-    /// The number of keys that exist.
+    /// Every matching parameter with its value; empty if nothing matches.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.set("key1", Bytes::from("value1")).await?;
-    /// let count = client.exists(&["key1", "key2", "key3"]).await?;
-    /// assert_eq!(count, 1);
+    /// for (param, value) in client.config_get("maxmemory*").await? {
+    ///     println!("{} = {}", param, value);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn exists(&mut self, keys: &[&str]) -> Result<i64> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::exists(keys_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn config_get(
+        &mut self,
+        pattern: &str,
+    ) -> Result<std::collections::HashMap<String, String>> {
+        let cmd = command::config_get(pattern);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_config(frame)
     }
 
-    /// Returns the type of value stored at key (TYPE).
+    /// Reads a single field of an INFO section.
+    ///
+    /// Requests only the given section and scans it for the `field:value` line,
+    /// without parsing the rest, which keeps health probes cheap.
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to check.
+    /// * `section` - The INFO section, e.g. `replication` or `memory`.
+    /// * `field` - The field name, e.g. `role` or `used_memory`.
     ///
     /// # Returns
     ///
-    /// The type as a string: "string", "list", "set", "zset", "hash", "stream", or "none".
+    /// The field value, or `None` if the section does not contain it.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.set("mykey", Bytes::from("value")).await?;
-    /// let key_type = client.key_type("mykey").await?;
-    /// assert_eq!(key_type, "string");
+    /// let role = client.info_field("replication", "role").await?;
+    /// assert_eq!(role.as_deref(), Some("master"));
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn key_type(&mut self, key: &str) -> Result<String> {
-        let cmd = command::key_type(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_string(frame)
+    pub async fn info_field(&mut self, section: &str, field: &str) -> Result<Option<String>> {
+        let frame = self.request_cmd(&command::info(section)).await?;
+        Ok(command::frame_to_bytes(frame)?.and_then(|info| command::find_info_field(&info, field)))
     }
 
-    /// Sets a timeout on a key in seconds (EXPIRE).
+    /// Sets a server configuration parameter at runtime (CONFIG SET).
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to set expiration on.
-    /// * `seconds` - Expiration time in seconds.
+    /// * `param` - The parameter name, e.g. `maxmemory`.
+    /// * `value` - The new value, e.g. `100mb`.
+    pub async fn config_set(&mut self, param: &str, value: &str) -> Result<()> {
+        let cmd = command::config_set(param, value);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)?;
+        Ok(())
+    }
+
+    /// Gets the values of all specified keys (MGET).
+    ///
+    /// For every key that does not exist, the corresponding element will be `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Slice of key names to retrieve.
     ///
     /// # Returns
     ///
-    /// `true` if the timeout was set, `false` if the key does not exist.
+    /// A vector of `Option<Bytes>`, one for each key. `None` for keys that do not exist.
     ///
     /// # Example
     ///
@@ -711,28 +1356,34 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.set("mykey", Bytes::from("value")).await?;
-    /// let was_set = client.expire("mykey", 60).await?;
-    /// assert!(was_set);
+    /// client.set("key1", Bytes::from("value1")).await?;
+    /// client.set("key2", Bytes::from("value2")).await?;
+    /// let values = client.mget(&["key1", "key2", "key3"]).await?;
+    /// assert_eq!(values[0], Some(Bytes::from("value1")));
+    /// assert_eq!(values[1], Some(Bytes::from("value2")));
+    /// assert_eq!(values[2], None);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn expire(&mut self, key: &str, seconds: u64) -> Result<bool> {
-        let cmd = command::expire(key.to_string(), seconds);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bool(frame)
+    pub async fn mget(&mut self, keys: &[&str]) -> Result<Vec<Option<Bytes>>> {
+        let cmd = command::mget(keys.iter().map(|k| Bytes::copy_from_slice(k.as_bytes())));
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_bytes(frame)
     }
 
-    /// Sets an absolute Unix timestamp expiration on a key (EXPIREAT).
+    /// Gets the values of the specified keys as a map, skipping missing keys (MGET).
+    ///
+    /// Handy when hydrating a cache: only keys that exist end up in the map. The
+    /// map does not preserve the order of `keys`; use [`mget`](Self::mget) for
+    /// positional results.
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to set expiration on.
-    /// * `timestamp` - Unix timestamp in seconds.
+    /// * `keys` - Slice of key names to retrieve.
     ///
     /// # Returns
     ///
-    /// `true` if the timeout was set, `false` if the key does not exist.
+    /// A HashMap of the existing keys to their values.
     ///
     /// # Example
     ///
@@ -741,27 +1392,30 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.set("mykey", Bytes::from("value")).await?;
-    /// let timestamp = 1735689600; // Some future timestamp
-    /// let was_set = client.expireat("mykey", timestamp).await?;
+    /// client.set("key1", Bytes::from("value1")).await?;
+    /// let values = client.mget_map(&["key1", "missing"]).await?;
+    /// assert_eq!(values.get("key1"), Some(&Bytes::from("value1")));
+    /// assert!(!values.contains_key("missing"));
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn expireat(&mut self, key: &str, timestamp: u64) -> Result<bool> {
-        let cmd = command::expireat(key.to_string(), timestamp);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bool(frame)
+    pub async fn mget_map(
+        &mut self,
+        keys: &[&str],
+    ) -> Result<std::collections::HashMap<String, Bytes>> {
+        let values = self.mget(keys).await?;
+        Ok(keys
+            .iter()
+            .zip(values)
+            .filter_map(|(key, value)| value.map(|value| (key.to_string(), value)))
+            .collect())
     }
 
-    /// Returns the remaining time to live of a key in seconds (TTL).
+    /// Sets multiple key-value pairs atomically (MSET).
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to check.
-    ///
-    /// # Returns
-    ///
-    /// TTL in seconds, -2 if the key does not exist, -1 if the key has no expiration.
+    /// * `pairs` - Slice of (key, value) tuples to set.
     ///
     /// # Example
     ///
@@ -770,57 +1424,103 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.setex("mykey", 60, Bytes::from("value")).await?;
-    /// let ttl = client.ttl("mykey").await?;
-    /// assert!(ttl > 0 && ttl <= 60);
+    /// client.mset(&[
+    ///     ("key1", Bytes::from("value1")),
+    ///     ("key2", Bytes::from("value2")),
+    /// ]).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn ttl(&mut self, key: &str) -> Result<i64> {
-        let cmd = command::ttl(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn mset(&mut self, pairs: &[(&str, Bytes)]) -> Result<()> {
+        let pairs_vec = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        let cmd = command::mset(pairs_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)?;
+        Ok(())
     }
 
-    /// Removes the expiration from a key (PERSIST).
+    /// Sets multiple keys, each with its own expiration (SET ... EX/PX).
+    ///
+    /// MSET cannot carry an expiration, so one SET per item is sent instead, all
+    /// pipelined in a single round-trip. Unlike MSET this is not atomic: if one SET
+    /// fails, the others still apply.
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to persist.
+    /// * `items` - The keys with their value and time to live; `None` sets the key
+    ///   without expiration. Sub-second durations are sent with millisecond precision.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// `true` if the expiration was removed, `false` if the key does not exist or has no expiration.
+    /// Returns the error of the first SET the server rejected, e.g. for a zero TTL.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
     /// # use bytes::Bytes;
+    /// # use std::time::Duration;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.setex("mykey", 60, Bytes::from("value")).await?;
-    /// let was_persisted = client.persist("mykey").await?;
-    /// assert!(was_persisted);
+    /// client.mset_with_ttl(&[
+    ///     ("session:1", Bytes::from("alice"), Some(Duration::from_secs(3600))),
+    ///     ("config", Bytes::from("v2"), None),
+    /// ]).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn persist(&mut self, key: &str) -> Result<bool> {
-        let cmd = command::persist(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bool(frame)
+    pub async fn mset_with_ttl(&mut self, items: &[(&str, Bytes, Option<Duration>)]) -> Result<()> {
+        let frames = items
+            .iter()
+            .map(|(key, value, ttl)| {
+                let cmd = match ttl {
+                    Some(ttl) => command::set_with_expiry(key.to_string(), value.clone(), *ttl),
+                    None => command::set(key.to_string(), value.clone()),
+                };
+                cmd.into_frame()
+            })
+            .collect();
+        let responses = self.connection.send_pipeline(frames).await?;
+
+        let mut first_error = None;
+        for ((key, _, _), frame) in items.iter().zip(responses) {
+            match command::parse_frame_response(frame) {
+                Ok(_) => self.remember_type(key, KeyType::String),
+                Err(e) => {
+                    first_error.get_or_insert(e.with_command("SET"));
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
     }
 
-    /// Renames a key (RENAME).
+    /// Gets the values of all specified binary keys (MGET).
+    ///
+    /// Same as [`mget`](Self::mget), but keys are not required to be UTF-8.
     ///
     /// # Arguments
     ///
-    /// * `key` - The key to rename.
-    /// * `newkey` - The new key name.
+    /// * `keys` - Slice of raw key names to retrieve.
     ///
-    /// # Errors
+    /// # Returns
     ///
-    /// Returns an error if the key does not exist.
+    /// A vector of `Option<Bytes>`, one for each key. `None` for keys that do not exist.
+    pub async fn mget_bytes(&mut self, keys: &[Bytes]) -> Result<Vec<Option<Bytes>>> {
+        let cmd = command::mget_bytes(keys.iter().cloned());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_bytes(frame)
+    }
+
+    /// Sets multiple binary key-value pairs atomically (MSET).
+    ///
+    /// Same as [`mset`](Self::mset), but keys are not required to be UTF-8.
+    ///
+    /// # Arguments
+    ///
+    /// * `pairs` - Slice of raw (key, value) tuples to set.
     ///
     /// # Example
     ///
@@ -829,27 +1529,30 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.set("oldkey", Bytes::from("value")).await?;
-    /// client.rename("oldkey", "newkey").await?;
+    /// let key = Bytes::from_static(&[0xff, 0x00, 0x01]);
+    /// client.mset_bytes(&[(key.clone(), Bytes::from("value"))]).await?;
+    /// let values = client.mget_bytes(&[key]).await?;
+    /// assert_eq!(values[0], Some(Bytes::from("value")));
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn rename(&mut self, key: &str, newkey: &str) -> Result<()> {
-        let cmd = command::rename(key.to_string(), newkey.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+    pub async fn mset_bytes(&mut self, pairs: &[(Bytes, Bytes)]) -> Result<()> {
+        let cmd = command::mset_bytes(pairs.to_vec());
+        let frame = self.request(cmd.into_frame()).await?;
         command::parse_frame_response(frame)?;
         Ok(())
     }
 
-    /// Iterates the set of keys in the database using a cursor (SCAN).
+    /// Sets the value of a key only if it does not exist (SETNX).
     ///
     /// # Arguments
     ///
-    /// * `cursor` - The cursor value (use 0 to start iteration).
+    /// * `key` - The key to set.
+    /// * `value` - The value to set.
     ///
     /// # Returns
     ///
-    /// A tuple of (next_cursor, keys). When next_cursor is 0, the iteration is complete.
+    /// `true` if the key was set, `false` if the key already existed.
     ///
     /// # Example
     ///
@@ -858,65 +1561,59 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let mut cursor = 0;
-    /// loop {
-    ///     let (next_cursor, keys) = client.scan(cursor).await?;
-    ///     for key in keys {
-    ///         println!("Key: {}", key);
-    ///     }
-    ///     cursor = next_cursor;
-    ///     if cursor == 0 {
-    ///         break;
-    ///     }
-    /// }
+    /// let was_set = client.setnx("mykey", Bytes::from("value")).await?;
+    /// assert!(was_set);
+    /// let was_set_again = client.setnx("mykey", Bytes::from("newvalue")).await?;
+    /// assert!(!was_set_again);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn scan(&mut self, cursor: u64) -> Result<(u64, Vec<String>)> {
-        let cmd = command::scan(cursor);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_scan_response(frame)
+    pub async fn setnx(&mut self, key: &str, value: Bytes) -> Result<bool> {
+        let cmd = command::setnx(key.to_string(), value);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bool(frame)
     }
 
-    /// Sets a field in a hash (HSET).
+    /// Sets the value of a key with a relative or absolute expiration (SET ... EX/PX/EXAT/PXAT).
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
-    /// * `field` - The field name.
+    /// * `key` - The key to set.
     /// * `value` - The value to set.
-    ///
-    /// # Returns
-    ///
-    /// `true` if the field was newly created, `false` if it was updated.
+    /// * `expiry` - When the key expires.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use muxis::Client;
+    /// # use muxis::{Client, Expiry};
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.hset("myhash", "field1", Bytes::from("value1")).await?;
+    /// client
+    ///     .set_with_expiry_opt("offer", Bytes::from("50%"), Expiry::UnixSeconds(1_900_000_000))
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hset(&mut self, key: &str, field: &str, value: Bytes) -> Result<bool> {
-        let cmd = command::hset(key.to_string(), field.to_string(), value);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bool(frame)
+    pub async fn set_with_expiry_opt(
+        &mut self,
+        key: &str,
+        value: Bytes,
+        expiry: Expiry,
+    ) -> Result<()> {
+        let cmd = command::set_with_expiry_opt(key.to_string(), value, expiry);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)?;
+        Ok(())
     }
 
-    /// Gets a field value from a hash (HGET).
+    /// Sets the value of a key with an expiration in seconds (SET ... EX).
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
-    /// * `field` - The field name.
-    ///
-    /// # Returns
-    ///
-    /// `Some(Bytes)` if the field exists, or `None` if it does not.
+    /// * `key` - The key to set.
+    /// * `seconds` - Expiration time in seconds.
+    /// * `value` - The value to set.
     ///
     /// # Example
     ///
@@ -925,22 +1622,26 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let value = client.hget("myhash", "field1").await?;
+    /// client.setex("mykey", 10, Bytes::from("value")).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hget(&mut self, key: &str, field: &str) -> Result<Option<Bytes>> {
-        let cmd = command::hget(key.to_string(), field.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bytes(frame)
+    pub async fn setex(&mut self, key: &str, seconds: u64, value: Bytes) -> Result<()> {
+        let cmd = command::setex(key.to_string(), seconds, value);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)?;
+        Ok(())
     }
 
-    /// Sets multiple fields in a hash (HMSET).
+    /// Gets the value of a key and deletes it atomically (GETDEL).
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
-    /// * `fields` - Slice of (field, value) tuples.
+    /// * `key` - The key to get and delete.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Bytes)` if the key exists, or `None` if it does not.
     ///
     /// # Example
     ///
@@ -949,280 +1650,522 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.hmset("myhash", &[
-    ///     ("field1", Bytes::from("value1")),
-    ///     ("field2", Bytes::from("value2")),
-    /// ]).await?;
+    /// client.set("mykey", Bytes::from("value")).await?;
+    /// let value = client.getdel("mykey").await?;
+    /// assert_eq!(value, Some(Bytes::from("value")));
+    /// let value_again = client.get("mykey").await?;
+    /// assert_eq!(value_again, None);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hmset(&mut self, key: &str, fields: &[(&str, Bytes)]) -> Result<()> {
-        let fields_vec = fields
-            .iter()
-            .map(|(f, v)| (f.to_string(), v.clone()))
-            .collect();
-        let cmd = command::hmset(key.to_string(), fields_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::parse_frame_response(frame)?;
-        Ok(())
+    pub async fn getdel(&mut self, key: &str) -> Result<Option<Bytes>> {
+        let cmd = command::getdel(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        let value = command::frame_to_bytes(frame)?;
+        self.remember_type(key, KeyType::None);
+        Ok(value)
     }
 
-    /// Gets multiple field values from a hash (HMGET).
+    /// Gets the value of a key and updates its expiry atomically (GETEX).
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
-    /// * `fields` - Slice of field names.
+    /// * `key` - The key to get.
+    /// * `expiry` - New time to live, or `None` to remove any existing expiry.
     ///
     /// # Returns
     ///
-    /// A vector of `Option<Bytes>`, one for each field. `None` for fields that do not exist.
+    /// `Some(Bytes)` if the key exists, or `None` if it does not.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
+    /// # use std::time::Duration;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let values = client.hmget("myhash", &["field1", "field2"]).await?;
+    /// let value = client.getex("session", Some(Duration::from_secs(60))).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hmget(&mut self, key: &str, fields: &[&str]) -> Result<Vec<Option<Bytes>>> {
-        let fields_vec = fields.iter().map(|f| f.to_string()).collect();
-        let cmd = command::hmget(key.to_string(), fields_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_bytes(frame)
+    pub async fn getex(&mut self, key: &str, expiry: Option<Duration>) -> Result<Option<Bytes>> {
+        let cmd = command::getex(key.to_string(), expiry);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bytes(frame)
     }
 
-    /// Gets all fields and values from a hash (HGETALL).
+    /// Sets a key and returns its previous value atomically (SET ... GET).
+    ///
+    /// Useful for swapping a cached value while reading what was there. Unlike
+    /// GETSET, an expiry can be set in the same command.
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
+    /// * `key` - The key to set.
+    /// * `value` - The new value.
+    /// * `expiry` - Time to live for the new value, or `None` to store it without
+    ///   an expiry (clearing any existing one).
     ///
     /// # Returns
     ///
-    /// A HashMap of field names to values.
+    /// `Some(Bytes)` with the previous value, or `None` if the key did not exist.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
     /// # use bytes::Bytes;
+    /// # use std::time::Duration;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let all_fields = client.hgetall("myhash").await?;
+    /// let previous = client
+    ///     .getset_expiring("config", Bytes::from("v2"), Some(Duration::from_secs(300)))
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hgetall(&mut self, key: &str) -> Result<std::collections::HashMap<String, Bytes>> {
-        let cmd = command::hgetall(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_hashmap(frame)
+    pub async fn getset_expiring(
+        &mut self,
+        key: &str,
+        value: Bytes,
+        expiry: Option<Duration>,
+    ) -> Result<Option<Bytes>> {
+        let cmd = command::set_get(key.to_string(), value, expiry);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bytes(frame)
     }
 
-    /// Deletes one or more fields from a hash (HDEL).
+    /// Atomically replaces the value of a key if it equals `expected`.
+    ///
+    /// Runs a small Lua script (loaded once, then invoked by digest), so the
+    /// comparison and the write happen in one atomic step and one round-trip. A
+    /// missing key never matches. Any existing expiry is removed by the write, as
+    /// with a plain SET.
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
-    /// * `fields` - Slice of field names to delete.
+    /// * `key` - The key to update.
+    /// * `expected` - The value the key must currently hold.
+    /// * `new` - The value to store.
     ///
     /// # Returns
     ///
-    /// The number of fields that were deleted.
+    /// `true` if the value was swapped, `false` if the current value differed.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let count = client.hdel("myhash", &["field1", "field2"]).await?;
+    /// let swapped = client
+    ///     .compare_and_set("state", Bytes::from("pending"), Bytes::from("done"))
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hdel(&mut self, key: &str, fields: &[&str]) -> Result<i64> {
-        let fields_vec = fields.iter().map(|f| f.to_string()).collect();
-        let cmd = command::hdel(key.to_string(), fields_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn compare_and_set(
+        &mut self,
+        key: &str,
+        expected: Bytes,
+        new: Bytes,
+    ) -> Result<bool> {
+        self.check_type(key, KeyType::String)?;
+        let frame = COMPARE_AND_SET
+            .invoke(self, &[key], &[expected, new])
+            .await?;
+        let swapped = command::frame_to_bool(frame)?;
+        if swapped {
+            self.remember_type(key, KeyType::String);
+        }
+        Ok(swapped)
     }
 
-    /// Checks if a field exists in a hash (HEXISTS).
+    /// Marks keys to be watched for a later MULTI/EXEC (WATCH).
+    ///
+    /// If any watched key is modified before EXEC, the transaction aborts. Use this
+    /// to build optimistic-locking flows by hand; [`transaction`](Self::transaction)
+    /// covers the common case and retries on conflicts.
+    ///
+    /// WATCH is scoped to the connection, not to this handle. Clones of a `Client`
+    /// share one multiplexed connection, so a watch set here applies to commands
+    /// and transactions sent from every clone, and an EXEC, DISCARD or UNWATCH from
+    /// any of them clears it.
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
-    /// * `field` - The field name.
+    /// * `keys` - The keys to watch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `keys` is empty.
+    pub async fn watch(&mut self, keys: &[&str]) -> Result<()> {
+        if keys.is_empty() {
+            return Err(Error::InvalidArgument {
+                message: "WATCH requires at least one key".to_string(),
+            });
+        }
+        let cmd = command::watch(keys.iter().map(|k| Bytes::copy_from_slice(k.as_bytes())));
+        command::parse_frame_response(self.request_cmd(&cmd).await?)?;
+        Ok(())
+    }
+
+    /// Forgets all keys watched on the connection (UNWATCH).
+    ///
+    /// Like [`watch`](Self::watch), this affects every clone of the client.
+    pub async fn unwatch(&mut self) -> Result<()> {
+        command::parse_frame_response(self.request_cmd(&command::unwatch()).await?)?;
+        Ok(())
+    }
+
+    /// Runs a MULTI/EXEC transaction, retrying it while the watched keys change.
+    ///
+    /// WATCHes `keys`, calls `f` to queue the commands, then sends MULTI, the
+    /// commands and EXEC in one write. If another client modified a watched key in
+    /// the meantime, EXEC aborts and the whole sequence, including `f`, runs again,
+    /// up to [`TRANSACTION_ATTEMPTS`] times. A closure that queues nothing ends the
+    /// transaction early with an empty result.
+    ///
+    /// The watch applies to the connection, which clones of this client share: a
+    /// transaction or UNWATCH on a clone running at the same time clears it.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to watch; may be empty.
+    /// * `f` - Queues the commands of one attempt.
     ///
     /// # Returns
     ///
-    /// `true` if the field exists, `false` otherwise.
+    /// One reply per queued command. A command failing at run time yields a
+    /// [`Frame::Error`] in its slot; the others still run.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TransactionAborted`] if every attempt conflicted, and an
+    /// [`Error::Command`] naming the command if the server refused to queue one.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let exists = client.hexists("myhash", "field1").await?;
+    /// let replies = client
+    ///     .transaction(&["balance"], |txn| {
+    ///         txn.incr("balance").set("updated", Bytes::from("now"));
+    ///     })
+    ///     .await?;
+    /// assert_eq!(replies.len(), 2);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hexists(&mut self, key: &str, field: &str) -> Result<bool> {
-        let cmd = command::hexists(key.to_string(), field.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bool(frame)
+    pub async fn transaction<F>(&mut self, keys: &[&str], mut f: F) -> Result<Vec<Frame>>
+    where
+        F: FnMut(&mut TxnBuilder),
+    {
+        for attempt in 1..=TRANSACTION_ATTEMPTS {
+            if !keys.is_empty() {
+                self.watch(keys).await?;
+            }
+
+            let mut txn = TxnBuilder::new();
+            f(&mut txn);
+            if txn.is_empty() {
+                if !keys.is_empty() {
+                    self.unwatch().await?;
+                }
+                return Ok(Vec::new());
+            }
+
+            let cmds = txn.into_cmds();
+            let mut replies = self.connection.send_batch(&cmds).await?;
+            // Queueing errors (wrong arity, unknown command) make EXEC abort too
+            for (cmd, reply) in cmds.iter().zip(&replies) {
+                if let Frame::Error(e) = reply {
+                    let name = String::from_utf8_lossy(cmd.name()).into_owned();
+                    return Err(Error::Server {
+                        message: String::from_utf8_lossy(e).into_owned(),
+                    }
+                    .with_command(&name));
+                }
+            }
+            match replies.pop() {
+                Some(Frame::Array(results)) => return Ok(results),
+                Some(Frame::Null) | Some(Frame::BulkString(None)) => {
+                    tracing::debug!(attempt, "watched key changed, retrying transaction");
+                }
+                _ => {
+                    return Err(Error::Protocol {
+                        message: "unexpected reply to EXEC".to_string(),
+                    })
+                }
+            }
+        }
+        Err(Error::TransactionAborted {
+            attempts: TRANSACTION_ATTEMPTS,
+        })
     }
 
-    /// Gets the number of fields in a hash (HLEN).
+    /// Appends a value to a key (APPEND).
+    ///
+    /// If the key does not exist, it is created and set as an empty string, then the value
+    /// is appended.
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
+    /// * `key` - The key to append to.
+    /// * `value` - The value to append.
     ///
     /// # Returns
     ///
-    /// The number of fields in the hash.
+    /// The length of the string after the append operation.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let count = client.hlen("myhash").await?;
+    /// client.set("mykey", Bytes::from("Hello")).await?;
+    /// let len = client.append("mykey", Bytes::from(" World")).await?;
+    /// assert_eq!(len, 11);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hlen(&mut self, key: &str) -> Result<i64> {
-        let cmd = command::hlen(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+    pub async fn append(&mut self, key: &str, value: Bytes) -> Result<i64> {
+        let cmd = command::append(key.to_string(), value);
+        let frame = self.request(cmd.into_frame()).await?;
         command::frame_to_int(frame)
     }
 
-    /// Gets all field names from a hash (HKEYS).
+    /// Appends a value to a key, keeping at most the last `max_len` bytes.
+    ///
+    /// Suited to append-only log keys: once the value grows past `max_len`, the
+    /// oldest bytes are dropped. The append and the trim run in one Lua script, so
+    /// concurrent writers never observe an untrimmed value. The key's expiry is
+    /// kept (`SET ... KEEPTTL`, Redis 6.0+).
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
+    /// * `key` - The key to append to.
+    /// * `value` - The value to append.
+    /// * `max_len` - The maximum length of the stored value, in bytes.
     ///
     /// # Returns
     ///
-    /// A vector of field names.
+    /// The length of the string after the append and any trimming.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `max_len` is not positive.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let keys = client.hkeys("myhash").await?;
+    /// let len = client
+    ///     .append_capped("log", Bytes::from("started\n"), 64 * 1024)
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hkeys(&mut self, key: &str) -> Result<Vec<String>> {
-        let cmd = command::hkeys(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_string(frame)
+    pub async fn append_capped(&mut self, key: &str, value: Bytes, max_len: i64) -> Result<i64> {
+        if max_len <= 0 {
+            return Err(Error::InvalidArgument {
+                message: format!("max_len must be positive, got {}", max_len),
+            });
+        }
+        self.check_type(key, KeyType::String)?;
+        let frame = APPEND_CAPPED
+            .invoke(self, &[key], &[value, Bytes::from(max_len.to_string())])
+            .await?;
+        let len = command::frame_to_int(frame)?;
+        self.remember_type(key, KeyType::String);
+        Ok(len)
     }
 
-    /// Gets all values from a hash (HVALS).
+    /// Counts a hit in a fixed-window rate limiter.
+    ///
+    /// Runs INCR and, on the first hit of a window, sets the window as the key's
+    /// expiry, in a single Lua script. Unlike an INCR followed by a separate EXPIRE,
+    /// a failure between the two cannot leave a counter that never expires. The
+    /// window is sent with millisecond precision.
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
+    /// * `key` - The counter key, e.g. `ratelimit:<user>`.
+    /// * `window` - The length of the window, starting at its first hit.
     ///
     /// # Returns
     ///
-    /// A vector of values.
+    /// The number of hits in the current window, including this one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `window` is shorter than a millisecond.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use std::time::Duration;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let values = client.hvals("myhash").await?;
+    /// let hits = client.rate_limit_incr("ratelimit:alice", Duration::from_secs(60)).await?;
+    /// if hits > 100 {
+    ///     println!("too many requests");
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hvals(&mut self, key: &str) -> Result<Vec<String>> {
-        let cmd = command::hvals(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_string(frame)
+    pub async fn rate_limit_incr(&mut self, key: &str, window: Duration) -> Result<i64> {
+        let millis = window.as_millis();
+        if millis == 0 {
+            return Err(Error::InvalidArgument {
+                message: format!("window must be at least 1ms, got {:?}", window),
+            });
+        }
+        self.check_type(key, KeyType::String)?;
+        let frame = RATE_LIMIT_INCR
+            .invoke(self, &[key], &[Bytes::from(millis.to_string())])
+            .await?;
+        let count = command::frame_to_int(frame)?;
+        self.remember_type(key, KeyType::String);
+        Ok(count)
     }
 
-    /// Increments a hash field by an integer (HINCRBY).
+    /// Returns the length of the string value stored at key (STRLEN).
+    ///
+    /// If the key does not exist, returns 0.
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
-    /// * `field` - The field name.
-    /// * `increment` - The amount to increment by.
+    /// * `key` - The key to check.
     ///
     /// # Returns
     ///
-    /// The value of the field after the increment.
+    /// The length of the string at key, or 0 if the key does not exist.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let new_value = client.hincrby("myhash", "counter", 5).await?;
+    /// client.set("mykey", Bytes::from("Hello World")).await?;
+    /// let len = client.strlen("mykey").await?;
+    /// assert_eq!(len, 11);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hincrby(&mut self, key: &str, field: &str, increment: i64) -> Result<i64> {
-        let cmd = command::hincrby(key.to_string(), field.to_string(), increment);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+    pub async fn strlen(&mut self, key: &str) -> Result<i64> {
+        let cmd = command::strlen(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
         command::frame_to_int(frame)
     }
 
-    /// Increments a hash field by a float (HINCRBYFLOAT).
+    /// Counts the set bits in a string (BITCOUNT).
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
-    /// * `field` - The field name.
-    /// * `increment` - The amount to increment by.
+    /// * `key` - The key holding the bitmap.
+    /// * `range` - Optional `(start, end)` range, inclusive; negative values count
+    ///   from the end.
+    /// * `unit` - Whether the range is in bytes or bits (Redis 7.0+); the default is
+    ///   bytes. Requires a range.
     ///
     /// # Returns
     ///
-    /// The value of the field after the increment.
+    /// The number of bits set to 1.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `unit` is given without a range, or if
+    /// the server is older than 7.0 and rejects the unit.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use muxis::Client;
+    /// # use muxis::{BitUnit, Client};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let new_value = client.hincrbyfloat("myhash", "price", 2.5).await?;
+    /// let first_byte = client.bitcount("visits", Some((0, 7)), Some(BitUnit::Bit)).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hincrbyfloat(&mut self, key: &str, field: &str, increment: f64) -> Result<f64> {
-        let cmd = command::hincrbyfloat(key.to_string(), field.to_string(), increment);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_float(frame)
+    pub async fn bitcount(
+        &mut self,
+        key: &str,
+        range: Option<(i64, i64)>,
+        unit: Option<BitUnit>,
+    ) -> Result<i64> {
+        check_bit_unit(range, unit)?;
+        let cmd = command::bitcount(key.to_string(), range, unit);
+        let frame = self
+            .request(cmd.into_frame())
+            .await
+            .map_err(|e| match unit {
+                Some(_) => map_unsupported_option(e, "BITCOUNT BYTE/BIT", "7.0"),
+                None => e,
+            })?;
+        command::frame_to_int(frame)
     }
 
-    /// Sets a field in a hash only if it does not exist (HSETNX).
+    /// Finds the first bit set to 1 or 0 in a string (BITPOS).
     ///
     /// # Arguments
     ///
-    /// * `key` - The hash key.
-    /// * `field` - The field name.
-    /// * `value` - The value to set.
+    /// * `key` - The key holding the bitmap.
+    /// * `bit` - `true` to look for a 1 bit, `false` for a 0 bit.
+    /// * `range` - Optional `(start, end)` range, inclusive; negative values count
+    ///   from the end.
+    /// * `unit` - Whether the range is in bytes or bits (Redis 7.0+); the default is
+    ///   bytes. Requires a range.
     ///
     /// # Returns
     ///
-    /// `true` if the field was set, `false` if it already existed.
+    /// The bit offset, or `None` if no such bit was found.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `unit` is given without a range, or if
+    /// the server is older than 7.0 and rejects the unit.
+    pub async fn bitpos(
+        &mut self,
+        key: &str,
+        bit: bool,
+        range: Option<(i64, i64)>,
+        unit: Option<BitUnit>,
+    ) -> Result<Option<i64>> {
+        check_bit_unit(range, unit)?;
+        let cmd = command::bitpos(key.to_string(), bit, range, unit);
+        let frame = self
+            .request(cmd.into_frame())
+            .await
+            .map_err(|e| match unit {
+                Some(_) => map_unsupported_option(e, "BITPOS BYTE/BIT", "7.0"),
+                None => e,
+            })?;
+        let pos = command::frame_to_int(frame)?;
+        Ok((pos >= 0).then_some(pos))
+    }
+
+    /// Checks if one or more keys exist (EXISTS).
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Slice of key names to check.
+    ///
+    /// # Returns
+    ///
+    /// The number of keys that exist.
     ///
     /// # Example
     ///
@@ -1231,26 +2174,30 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let was_set = client.hsetnx("myhash", "field1", Bytes::from("value")).await?;
+    /// client.set("key1", Bytes::from("value1")).await?;
+    /// let count = client.exists(&["key1", "key2", "key3"]).await?;
+    /// assert_eq!(count, 1);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn hsetnx(&mut self, key: &str, field: &str, value: Bytes) -> Result<bool> {
-        let cmd = command::hsetnx(key.to_string(), field.to_string(), value);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bool(frame)
+    pub async fn exists(&mut self, keys: &[&str]) -> Result<i64> {
+        let cmd = command::exists(keys.iter().map(|k| Bytes::copy_from_slice(k.as_bytes())));
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
     }
 
-    /// Pushes values to the head of a list (LPUSH).
+    /// Checks if a single key exists (EXISTS).
+    ///
+    /// Matches the single-key form of `ClusterClient::exists`. Use [`exists`](Self::exists)
+    /// to count several keys at once.
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
-    /// * `values` - Slice of values to push.
+    /// * `key` - The key to check.
     ///
     /// # Returns
     ///
-    /// The length of the list after the push operation.
+    /// `true` if the key exists.
     ///
     /// # Example
     ///
@@ -1259,27 +2206,29 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let len = client.lpush("mylist", &[Bytes::from("value1"), Bytes::from("value2")]).await?;
+    /// client.set("key1", Bytes::from("value1")).await?;
+    /// assert!(client.exists_one("key1").await?);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn lpush(&mut self, key: &str, values: &[Bytes]) -> Result<i64> {
-        let values_vec = values.to_vec();
-        let cmd = command::lpush(key.to_string(), values_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn exists_one(&mut self, key: &str) -> Result<bool> {
+        let cmd = command::exists([key.to_string()]);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame).map(|n| n > 0)
     }
 
-    /// Pushes values to the tail of a list (RPUSH).
+    /// Checks the existence of each key individually (EXISTS).
+    ///
+    /// Unlike [`exists`](Self::exists), which only returns a total count, this issues
+    /// one EXISTS per key in a single pipelined round-trip and reports each key.
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
-    /// * `values` - Slice of values to push.
+    /// * `keys` - The keys to check.
     ///
     /// # Returns
     ///
-    /// The length of the list after the push operation.
+    /// One boolean per key, in the same order as `keys`.
     ///
     /// # Example
     ///
@@ -1288,187 +2237,290 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let len = client.rpush("mylist", &[Bytes::from("value1"), Bytes::from("value2")]).await?;
+    /// client.set("key1", Bytes::from("value")).await?;
+    /// let exists = client.exists_each(&["key1", "missing"]).await?;
+    /// assert_eq!(exists, vec![true, false]);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn rpush(&mut self, key: &str, values: &[Bytes]) -> Result<i64> {
-        let values_vec = values.to_vec();
-        let cmd = command::rpush(key.to_string(), values_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn exists_each(&mut self, keys: &[&str]) -> Result<Vec<bool>> {
+        let frames = keys
+            .iter()
+            .map(|k| command::exists([k.to_string()]).into_frame())
+            .collect();
+        let responses = self.connection.send_pipeline(frames).await?;
+        responses
+            .into_iter()
+            .map(|frame| {
+                command::parse_frame_response(frame)
+                    .and_then(command::frame_to_int)
+                    .map(|n| n > 0)
+                    .map_err(|e| e.with_command("EXISTS"))
+            })
+            .collect()
     }
 
-    /// Removes and returns the first element of a list (LPOP).
+    /// Returns the type of value stored at key (TYPE).
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
+    /// * `key` - The key to check.
     ///
     /// # Returns
     ///
-    /// `Some(Bytes)` if the list exists and has elements, or `None` otherwise.
+    /// The type as a string: "string", "list", "set", "zset", "hash", "stream", or "none".
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let value = client.lpop("mylist").await?;
+    /// client.set("mykey", Bytes::from("value")).await?;
+    /// let key_type = client.key_type("mykey").await?;
+    /// assert_eq!(key_type, "string");
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn lpop(&mut self, key: &str) -> Result<Option<Bytes>> {
-        let cmd = command::lpop(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bytes(frame)
+    pub async fn key_type(&mut self, key: &str) -> Result<String> {
+        let cmd = command::key_type(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        let key_type = command::frame_to_string(frame)?;
+        self.remember_type(key, KeyType::from(key_type.as_str()));
+        Ok(key_type)
     }
 
-    /// Removes and returns the last element of a list (RPOP).
+    /// Returns the type of value stored at key as a [`KeyType`] (TYPE).
+    ///
+    /// Unlike [`key_type`](Self::key_type), the reply is parsed into an enum so callers
+    /// can dispatch on it without string comparisons.
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
+    /// * `key` - The key to check.
     ///
     /// # Returns
     ///
-    /// `Some(Bytes)` if the list exists and has elements, or `None` otherwise.
+    /// The key type, [`KeyType::None`] if the key does not exist, or [`KeyType::Other`]
+    /// for types unknown to this client (e.g., module types).
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use muxis::Client;
+    /// # use muxis::{Client, KeyType};
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let value = client.rpop("mylist").await?;
+    /// client.set("mykey", Bytes::from("value")).await?;
+    /// assert_eq!(client.key_type_enum("mykey").await?, KeyType::String);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn rpop(&mut self, key: &str) -> Result<Option<Bytes>> {
-        let cmd = command::rpop(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bytes(frame)
+    pub async fn key_type_enum(&mut self, key: &str) -> Result<KeyType> {
+        let cmd = command::key_type(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        let key_type = command::frame_to_key_type(frame)?;
+        self.remember_type(key, key_type.clone());
+        Ok(key_type)
     }
 
-    /// Returns the length of a list (LLEN).
+    /// Fetches the whole value stored at a key, whatever its type.
+    ///
+    /// Calls TYPE first, then the matching read command: GET, LRANGE, SMEMBERS,
+    /// HGETALL or ZRANGE ... WITHSCORES. Meant for generic tools such as data
+    /// browsers; the full collection is loaded into memory.
+    ///
+    /// The two calls are not atomic. A key deleted in between is reported as
+    /// [`RedisValue::None`]; a key whose type changed in between fails with the
+    /// server's WRONGTYPE error.
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
+    /// * `key` - The key to fetch.
     ///
     /// # Returns
     ///
-    /// The length of the list, or 0 if the key does not exist.
+    /// The value, or [`RedisValue::None`] if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] for streams and module types, which have
+    /// no [`RedisValue`] representation.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use muxis::Client;
+    /// # use muxis::{Client, RedisValue};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let len = client.llen("mylist").await?;
+    /// match client.fetch_any("mykey").await? {
+    ///     RedisValue::Hash(pairs) => println!("hash with {} fields", pairs.len()),
+    ///     RedisValue::None => println!("no such key"),
+    ///     other => println!("{:?}", other),
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn llen(&mut self, key: &str) -> Result<i64> {
-        let cmd = command::llen(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn fetch_any(&mut self, key: &str) -> Result<RedisValue> {
+        let value = match self.key_type_enum(key).await? {
+            KeyType::String => match self.get(key).await? {
+                Some(value) => RedisValue::String(value),
+                None => RedisValue::None,
+            },
+            KeyType::List => RedisValue::List(self.lrange(key, 0, -1).await?),
+            KeyType::Set => {
+                let cmd = command::smembers(key.to_string());
+                let frame = self.request(cmd.into_frame()).await?;
+                RedisValue::Set(command::frame_to_vec_bytes_list(frame)?)
+            }
+            KeyType::Hash => RedisValue::Hash(self.hgetall_pairs(key).await?),
+            KeyType::ZSet => {
+                let cmd = command::zrange(key.to_string(), 0, -1).arg("WITHSCORES");
+                let frame = self.request_cmd(&cmd).await?;
+                RedisValue::ZSet(command::frame_to_scored_bytes(frame)?)
+            }
+            KeyType::None => RedisValue::None,
+            other => {
+                return Err(Error::InvalidArgument {
+                    message: format!("cannot fetch a {} value", other.as_str()),
+                })
+            }
+        };
+        Ok(value)
     }
 
-    /// Returns a range of elements from a list (LRANGE).
+    /// Sets a timeout on a key in seconds (EXPIRE).
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
-    /// * `start` - Start index (0-based, negative values count from the end).
-    /// * `stop` - Stop index (inclusive).
+    /// * `key` - The key to set expiration on.
+    /// * `seconds` - Expiration time in seconds.
     ///
     /// # Returns
     ///
-    /// A vector of elements in the specified range.
+    /// `true` if the timeout was set, `false` if the key does not exist.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let elements = client.lrange("mylist", 0, -1).await?;
+    /// client.set("mykey", Bytes::from("value")).await?;
+    /// let was_set = client.expire("mykey", 60).await?;
+    /// assert!(was_set);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn lrange(&mut self, key: &str, start: i64, stop: i64) -> Result<Vec<Bytes>> {
-        let cmd = command::lrange(key.to_string(), start, stop);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_bytes_list(frame)
+    pub async fn expire(&mut self, key: &str, seconds: u64) -> Result<bool> {
+        let cmd = command::expire(key.to_string(), seconds);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bool(frame)
     }
 
-    /// Returns an element from a list by index (LINDEX).
+    /// Sets a timeout on a key and returns its remaining TTL (EXPIRE + TTL).
+    ///
+    /// Both commands are pipelined in a single round-trip, which saves the extra
+    /// TTL call commonly made to confirm a refreshed expiration.
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
-    /// * `index` - The index (0-based, negative values count from the end).
+    /// * `key` - The key to set expiration on.
+    /// * `seconds` - Expiration time in seconds.
     ///
     /// # Returns
     ///
-    /// `Some(Bytes)` if the index is valid, or `None` otherwise.
+    /// `Some(ttl)` with the remaining time to live in seconds, or `None` if the key
+    /// does not exist.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let element = client.lindex("mylist", 0).await?;
+    /// client.set("session", Bytes::from("value")).await?;
+    /// let ttl = client.expire_and_ttl("session", 60).await?;
+    /// assert_eq!(ttl, Some(60));
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn lindex(&mut self, key: &str, index: i64) -> Result<Option<Bytes>> {
-        let cmd = command::lindex(key.to_string(), index);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bytes(frame)
+    pub async fn expire_and_ttl(&mut self, key: &str, seconds: u64) -> Result<Option<i64>> {
+        let frames = vec![
+            command::expire(key.to_string(), seconds).into_frame(),
+            command::ttl(key.to_string()).into_frame(),
+        ];
+        let mut responses = self.connection.send_pipeline(frames).await?.into_iter();
+        let (Some(expire), Some(ttl)) = (responses.next(), responses.next()) else {
+            return Err(Error::Protocol {
+                message: "missing reply in EXPIRE/TTL pipeline".to_string(),
+            });
+        };
+        let expired = command::parse_frame_response(expire)
+            .and_then(command::frame_to_bool)
+            .map_err(|e| e.with_command("EXPIRE"))?;
+        if !expired {
+            return Ok(None);
+        }
+        command::parse_frame_response(ttl)
+            .and_then(command::frame_to_int)
+            .map(Some)
+            .map_err(|e| e.with_command("TTL"))
     }
 
-    /// Sets the value of an element in a list by index (LSET).
+    /// Sets a timeout on each key individually (EXPIRE).
+    ///
+    /// One EXPIRE per key is sent in a single pipelined round-trip, which makes
+    /// TTL sweeps over many keys cheap.
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
-    /// * `index` - The index (0-based, negative values count from the end).
-    /// * `value` - The value to set.
+    /// * `items` - The keys with their expiration time in seconds.
+    ///
+    /// # Returns
+    ///
+    /// One boolean per key, in the same order as `items`: `true` if the timeout was
+    /// set, `false` if the key does not exist.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.lset("mylist", 0, Bytes::from("newvalue")).await?;
+    /// let set = client.expire_many(&[("cache:1", 60), ("cache:2", 300)]).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn lset(&mut self, key: &str, index: i64, value: Bytes) -> Result<()> {
-        let cmd = command::lset(key.to_string(), index, value);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::parse_frame_response(frame)?;
-        Ok(())
+    pub async fn expire_many(&mut self, items: &[(&str, u64)]) -> Result<Vec<bool>> {
+        let frames = items
+            .iter()
+            .map(|(key, seconds)| command::expire(key.to_string(), *seconds).into_frame())
+            .collect();
+        let responses = self.connection.send_pipeline(frames).await?;
+        responses
+            .into_iter()
+            .map(|frame| {
+                command::parse_frame_response(frame)
+                    .and_then(command::frame_to_bool)
+                    .map_err(|e| e.with_command("EXPIRE"))
+            })
+            .collect()
     }
 
-    /// Removes elements from a list (LREM).
+    /// Sets an absolute Unix timestamp expiration on a key (EXPIREAT).
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
-    /// * `count` - Number of elements to remove (positive: head to tail, negative: tail to head, 0: all).
-    /// * `value` - The value to remove.
+    /// * `key` - The key to set expiration on.
+    /// * `timestamp` - Unix timestamp in seconds.
     ///
     /// # Returns
     ///
-    /// The number of elements removed.
+    /// `true` if the timeout was set, `false` if the key does not exist.
     ///
     /// # Example
     ///
@@ -1477,106 +2529,120 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let removed = client.lrem("mylist", 0, Bytes::from("value")).await?;
+    /// client.set("mykey", Bytes::from("value")).await?;
+    /// let timestamp = 1735689600; // Some future timestamp
+    /// let was_set = client.expireat("mykey", timestamp).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn lrem(&mut self, key: &str, count: i64, value: Bytes) -> Result<i64> {
-        let cmd = command::lrem(key.to_string(), count, value);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn expireat(&mut self, key: &str, timestamp: u64) -> Result<bool> {
+        let cmd = command::expireat(key.to_string(), timestamp);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bool(frame)
     }
 
-    /// Trims a list to the specified range (LTRIM).
+    /// Returns the remaining time to live of a key in seconds (TTL).
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
-    /// * `start` - Start index (0-based, negative values count from the end).
-    /// * `stop` - Stop index (inclusive).
+    /// * `key` - The key to check.
+    ///
+    /// # Returns
+    ///
+    /// TTL in seconds, -2 if the key does not exist, -1 if the key has no expiration.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// client.ltrim("mylist", 0, 9).await?;
+    /// client.setex("mykey", 60, Bytes::from("value")).await?;
+    /// let ttl = client.ttl("mykey").await?;
+    /// assert!(ttl > 0 && ttl <= 60);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn ltrim(&mut self, key: &str, start: i64, stop: i64) -> Result<()> {
-        let cmd = command::ltrim(key.to_string(), start, stop);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::parse_frame_response(frame)?;
-        Ok(())
+    pub async fn ttl(&mut self, key: &str) -> Result<i64> {
+        let cmd = command::ttl(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
     }
 
-    /// Atomically removes the last element from a list and pushes it to another list (RPOPLPUSH).
-    ///
-    /// # Arguments
+    /// Returns the remaining time to live of a key as a [`Ttl`] (TTL).
     ///
-    /// * `source` - The source list key.
-    /// * `destination` - The destination list key.
+    /// Unlike [`ttl`](Self::ttl), a missing key and a key without expiration are
+    /// reported as their own variants instead of `-2` and `-1`.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// `Some(Bytes)` if the operation succeeded, or `None` if source is empty.
+    /// * `key` - The key to check.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// # use muxis::Client;
+    /// # use muxis::{Client, Ttl};
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let element = client.rpoplpush("source", "destination").await?;
+    /// match client.ttl_typed("mykey").await? {
+    ///     Ttl::Seconds(seconds) => println!("expires in {}s", seconds),
+    ///     Ttl::NoExpiry => println!("never expires"),
+    ///     Ttl::Missing => println!("no such key"),
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn rpoplpush(&mut self, source: &str, destination: &str) -> Result<Option<Bytes>> {
-        let cmd = command::rpoplpush(source.to_string(), destination.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bytes(frame)
+    pub async fn ttl_typed(&mut self, key: &str) -> Result<Ttl> {
+        let cmd = command::ttl(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_ttl(frame)
     }
 
-    /// Removes and returns the first element from one of multiple lists, blocking if needed (BLPOP).
+    /// Removes the expiration from a key (PERSIST).
     ///
     /// # Arguments
     ///
-    /// * `keys` - Slice of list keys to check.
-    /// * `timeout` - Timeout in seconds (0 means block indefinitely).
+    /// * `key` - The key to persist.
     ///
     /// # Returns
     ///
-    /// `Some((key, value))` if an element was popped, or `None` if timeout occurred.
+    /// `true` if the expiration was removed, `false` if the key does not exist or has no expiration.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let result = client.blpop(&["list1", "list2"], 5).await?;
+    /// client.setex("mykey", 60, Bytes::from("value")).await?;
+    /// let was_persisted = client.persist("mykey").await?;
+    /// assert!(was_persisted);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn blpop(&mut self, keys: &[&str], timeout: u64) -> Result<Option<(String, Bytes)>> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::blpop(keys_vec, timeout);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_blocking_pop(frame)
+    pub async fn persist(&mut self, key: &str) -> Result<bool> {
+        let cmd = command::persist(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bool(frame)
     }
 
-    /// Removes and returns the last element from one of multiple lists, blocking if needed (BRPOP).
+    /// Returns the logarithmic access frequency counter of a key (OBJECT FREQ).
+    ///
+    /// Requires an LFU `maxmemory-policy` (`allkeys-lfu` or `volatile-lfu`).
     ///
     /// # Arguments
     ///
-    /// * `keys` - Slice of list keys to check.
-    /// * `timeout` - Timeout in seconds (0 means block indefinitely).
+    /// * `key` - The key to inspect.
     ///
     /// # Returns
     ///
-    /// `Some((key, value))` if an element was popped, or `None` if timeout occurred.
+    /// The access frequency counter, or `None` if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the server is not using an LFU policy.
     ///
     /// # Example
     ///
@@ -1584,59 +2650,64 @@ impl Client {
     /// # use muxis::Client;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let result = client.brpop(&["list1", "list2"], 5).await?;
+    /// let freq = client.object_freq("mykey").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn brpop(&mut self, keys: &[&str], timeout: u64) -> Result<Option<(String, Bytes)>> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::brpop(keys_vec, timeout);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_blocking_pop(frame)
+    pub async fn object_freq(&mut self, key: &str) -> Result<Option<i64>> {
+        let cmd = command::object_freq(key.to_string());
+        let frame = self
+            .request(cmd.into_frame())
+            .await
+            .map_err(command::map_object_stat_error)?;
+        command::frame_to_object_stat(frame)
     }
 
-    /// Returns the index of the first matching element in a list (LPOS).
+    /// Returns the number of seconds since a key was last accessed (OBJECT IDLETIME).
+    ///
+    /// Not available when an LFU `maxmemory-policy` is selected.
     ///
     /// # Arguments
     ///
-    /// * `key` - The list key.
-    /// * `element` - The element to search for.
+    /// * `key` - The key to inspect.
     ///
     /// # Returns
     ///
-    /// `Some(i64)` with the index if found, or `None` if not found.
+    /// The idle time in seconds, or `None` if the key does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the server is using an LFU policy.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
-    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let position = client.lpos("mylist", Bytes::from("value")).await?;
+    /// let idle = client.object_idletime("mykey").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn lpos(&mut self, key: &str, element: Bytes) -> Result<Option<i64>> {
-        let cmd = command::lpos(key.to_string(), element);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        match frame {
-            Frame::Null | Frame::BulkString(None) => Ok(None),
-            Frame::Integer(i) => Ok(Some(i)),
-            _ => command::frame_to_int(frame).map(Some),
-        }
+    pub async fn object_idletime(&mut self, key: &str) -> Result<Option<i64>> {
+        let cmd = command::object_idletime(key.to_string());
+        let frame = self
+            .request(cmd.into_frame())
+            .await
+            .map_err(command::map_object_stat_error)?;
+        command::frame_to_object_stat(frame)
     }
 
-    /// Adds one or more members to a set (SADD).
+    /// Renames a key (RENAME).
     ///
     /// # Arguments
     ///
-    /// * `key` - The set key.
-    /// * `members` - Slice of members to add.
+    /// * `key` - The key to rename.
+    /// * `newkey` - The new key name.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// The number of members that were added to the set.
+    /// Returns an error if the key does not exist.
     ///
     /// # Example
     ///
@@ -1645,27 +2716,31 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let added = client.sadd("myset", &[Bytes::from("a"), Bytes::from("b")]).await?;
+    /// client.set("oldkey", Bytes::from("value")).await?;
+    /// client.rename("oldkey", "newkey").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn sadd(&mut self, key: &str, members: &[Bytes]) -> Result<i64> {
-        let members_vec = members.to_vec();
-        let cmd = command::sadd(key.to_string(), members_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn rename(&mut self, key: &str, newkey: &str) -> Result<()> {
+        let cmd = command::rename(key.to_string(), newkey.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)?;
+        if let Some(cache) = &self.type_cache {
+            cache.forget(key);
+            cache.forget(newkey);
+        }
+        Ok(())
     }
 
-    /// Removes one or more members from a set (SREM).
+    /// Iterates the set of keys in the database using a cursor (SCAN).
     ///
     /// # Arguments
     ///
-    /// * `key` - The set key.
-    /// * `members` - Slice of members to remove.
+    /// * `cursor` - The cursor value (use 0 to start iteration).
     ///
     /// # Returns
     ///
-    /// The number of members that were removed from the set.
+    /// A tuple of (next_cursor, keys). When next_cursor is 0, the iteration is complete.
     ///
     /// # Example
     ///
@@ -1674,26 +2749,42 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let removed = client.srem("myset", &[Bytes::from("a")]).await?;
+    /// let mut cursor = 0;
+    /// loop {
+    ///     let (next_cursor, keys) = client.scan(cursor).await?;
+    ///     for key in keys {
+    ///         println!("Key: {}", key);
+    ///     }
+    ///     cursor = next_cursor;
+    ///     if cursor == 0 {
+    ///         break;
+    ///     }
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn srem(&mut self, key: &str, members: &[Bytes]) -> Result<i64> {
-        let members_vec = members.to_vec();
-        let cmd = command::srem(key.to_string(), members_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn scan(&mut self, cursor: u64) -> Result<(u64, Vec<String>)> {
+        let cmd = command::scan(cursor);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_scan_response(frame)
     }
 
-    /// Removes and returns a random member from a set (SPOP).
+    /// Deletes every key matching a glob-style pattern.
+    ///
+    /// Walks the keyspace with `SCAN MATCH` instead of the blocking `KEYS`, and
+    /// removes each page of matches with `UNLINK` before fetching the next, so only
+    /// one batch is held in memory. Keys created while the scan runs may or may not
+    /// be deleted.
+    ///
+    /// Only the connected node is scanned; this is not cluster-aware.
     ///
     /// # Arguments
     ///
-    /// * `key` - The set key.
+    /// * `pattern` - The glob-style pattern, e.g. `session:*`.
     ///
     /// # Returns
     ///
-    /// `Some(Bytes)` if the set exists and has members, or `None` otherwise.
+    /// The number of keys deleted.
     ///
     /// # Example
     ///
@@ -1701,25 +2792,53 @@ impl Client {
     /// # use muxis::Client;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let member = client.spop("myset").await?;
+    /// let deleted = client.del_pattern("session:*").await?;
+    /// println!("removed {} sessions", deleted);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn spop(&mut self, key: &str) -> Result<Option<Bytes>> {
-        let cmd = command::spop(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bytes(frame)
+    pub async fn del_pattern(&mut self, pattern: &str) -> Result<u64> {
+        let mut cursor = 0;
+        let mut deleted = 0;
+        loop {
+            let cmd = command::scan_match(cursor, pattern.to_string(), DEL_PATTERN_BATCH);
+            let frame = self.request(cmd.into_frame()).await?;
+            let (next_cursor, keys) = command::frame_to_scan_response(frame)?;
+
+            if !keys.is_empty() {
+                for key in &keys {
+                    self.remember_type(key, KeyType::None);
+                }
+                let frame = self.request_cmd(&command::unlink(keys)).await?;
+                deleted += command::frame_to_int(frame)?.max(0) as u64;
+            }
+
+            cursor = next_cursor;
+            if cursor == 0 {
+                return Ok(deleted);
+            }
+        }
     }
 
-    /// Returns all members of a set (SMEMBERS).
+    /// Estimates the number of keys matching a glob-style pattern.
+    ///
+    /// Drives `SCAN MATCH` for at most `max_scan` iterations and counts the keys
+    /// returned, stopping early once the scan completes. The result is approximate:
+    /// when the limit is reached before the end of the keyspace only part of it is
+    /// counted, and SCAN may report a key more than once, e.g. while the server
+    /// rehashes. Each iteration is a cheap, non-blocking call, which makes this
+    /// suitable for dashboards.
+    ///
+    /// Only the connected node is scanned; this is not cluster-aware.
     ///
     /// # Arguments
     ///
-    /// * `key` - The set key.
+    /// * `pattern` - The glob-style pattern, e.g. `session:*`.
+    /// * `max_scan` - The maximum number of SCAN calls to make.
     ///
     /// # Returns
     ///
-    /// A vector of all members in the set.
+    /// The number of matching keys seen.
     ///
     /// # Example
     ///
@@ -1727,26 +2846,39 @@ impl Client {
     /// # use muxis::Client;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let members = client.smembers("myset").await?;
+    /// let sessions = client.count_keys("session:*", 100).await?;
+    /// println!("about {} sessions", sessions);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn smembers(&mut self, key: &str) -> Result<Vec<String>> {
-        let cmd = command::smembers(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_string(frame)
+    pub async fn count_keys(&mut self, pattern: &str, max_scan: u64) -> Result<u64> {
+        let mut cursor = 0;
+        let mut count = 0;
+        for _ in 0..max_scan {
+            let cmd = command::scan_match(cursor, pattern.to_string(), COUNT_KEYS_BATCH);
+            let frame = self.request(cmd.into_frame()).await?;
+            let (next_cursor, keys) = command::frame_to_scan_response(frame)?;
+            count += keys.len() as u64;
+
+            cursor = next_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
+        Ok(count)
     }
 
-    /// Checks if a member exists in a set (SISMEMBER).
+    /// Sets a field in a hash (HSET).
     ///
     /// # Arguments
     ///
-    /// * `key` - The set key.
-    /// * `member` - The member to check.
+    /// * `key` - The hash key.
+    /// * `field` - The field name.
+    /// * `value` - The value to set.
     ///
     /// # Returns
     ///
-    /// `true` if the member exists in the set, `false` otherwise.
+    /// `true` if the field was newly created, `false` if it was updated.
     ///
     /// # Example
     ///
@@ -1755,107 +2887,122 @@ impl Client {
     /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let exists = client.sismember("myset", Bytes::from("member")).await?;
+    /// client.hset("myhash", "field1", Bytes::from("value1")).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn sismember(&mut self, key: &str, member: Bytes) -> Result<bool> {
-        let cmd = command::sismember(key.to_string(), member);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bool(frame)
+    pub async fn hset(&mut self, key: &str, field: &str, value: Bytes) -> Result<bool> {
+        self.check_type(key, KeyType::Hash)?;
+        let cmd = command::hset(key.to_string(), field.to_string(), value);
+        let frame = self.request(cmd.into_frame()).await?;
+        let added = command::frame_to_bool(frame)?;
+        self.remember_type(key, KeyType::Hash);
+        Ok(added)
     }
 
-    /// Returns the cardinality (size) of a set (SCARD).
+    /// Gets a field value from a hash (HGET).
     ///
     /// # Arguments
     ///
-    /// * `key` - The set key.
+    /// * `key` - The hash key.
+    /// * `field` - The field name.
     ///
     /// # Returns
     ///
-    /// The number of members in the set, or 0 if the key does not exist.
+    /// `Some(Bytes)` if the field exists, or `None` if it does not.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let size = client.scard("myset").await?;
+    /// let value = client.hget("myhash", "field1").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn scard(&mut self, key: &str) -> Result<i64> {
-        let cmd = command::scard(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn hget(&mut self, key: &str, field: &str) -> Result<Option<Bytes>> {
+        let cmd = command::hget(key.to_string(), field.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bytes(frame)
     }
 
-    /// Returns a random member from a set (SRANDMEMBER).
+    /// Sets multiple fields in a hash (HMSET).
     ///
     /// # Arguments
     ///
-    /// * `key` - The set key.
-    ///
-    /// # Returns
-    ///
-    /// `Some(String)` with a random member, or `None` if the set is empty.
+    /// * `key` - The hash key.
+    /// * `fields` - Slice of (field, value) tuples.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let member = client.srandmember("myset").await?;
+    /// client.hmset("myhash", &[
+    ///     ("field1", Bytes::from("value1")),
+    ///     ("field2", Bytes::from("value2")),
+    /// ]).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn srandmember(&mut self, key: &str) -> Result<Option<String>> {
-        let cmd = command::srandmember(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        match frame {
-            Frame::Null => Ok(None),
-            _ => command::frame_to_string(frame).map(Some),
-        }
+    pub async fn hmset(&mut self, key: &str, fields: &[(&str, Bytes)]) -> Result<()> {
+        let fields_vec = fields
+            .iter()
+            .map(|(f, v)| (f.to_string(), v.clone()))
+            .collect();
+        let cmd = command::hmset(key.to_string(), fields_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)?;
+        Ok(())
     }
 
-    /// Returns the difference between the first set and all successive sets (SDIFF).
+    /// Gets multiple field values from a hash (HMGET).
     ///
     /// # Arguments
     ///
-    /// * `keys` - Slice of set keys.
+    /// * `key` - The hash key.
+    /// * `fields` - Slice of field names.
     ///
     /// # Returns
     ///
-    /// A vector of members in the difference.
+    /// A vector of `Option<Bytes>`, one for each field. `None` for fields that do not exist.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let diff = client.sdiff(&["set1", "set2"]).await?;
+    /// let values = client.hmget("myhash", &["field1", "field2"]).await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn sdiff(&mut self, keys: &[&str]) -> Result<Vec<String>> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::sdiff(keys_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_string(frame)
+    pub async fn hmget(&mut self, key: &str, fields: &[&str]) -> Result<Vec<Option<Bytes>>> {
+        let fields_vec = fields.iter().map(|f| f.to_string()).collect();
+        let cmd = command::hmget(key.to_string(), fields_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_bytes(frame)
     }
 
-    /// Returns the intersection of all given sets (SINTER).
+    /// Gets multiple field values from a hash, paired with their field names (HMGET).
+    ///
+    /// Like [`hmget`](Self::hmget), but each value is returned next to the field it
+    /// belongs to, so missing fields are easy to identify.
     ///
     /// # Arguments
     ///
-    /// * `keys` - Slice of set keys.
+    /// * `key` - The hash key.
+    /// * `fields` - Slice of field names.
     ///
     /// # Returns
     ///
-    /// A vector of members in the intersection.
+    /// One `(field, value)` pair per requested field, in the same order as `fields`.
+    /// The value is `None` if the field does not exist.
     ///
     /// # Example
     ///
@@ -1863,26 +3010,48 @@ impl Client {
     /// # use muxis::Client;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let inter = client.sinter(&["set1", "set2"]).await?;
+    /// for (field, value) in client.hmget_pairs("myhash", &["field1", "field2"]).await? {
+    ///     println!("{}: {:?}", field, value);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn sinter(&mut self, keys: &[&str]) -> Result<Vec<String>> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::sinter(keys_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_string(frame)
+    pub async fn hmget_pairs(
+        &mut self,
+        key: &str,
+        fields: &[&str],
+    ) -> Result<Vec<(String, Option<Bytes>)>> {
+        let values = self.hmget(key, fields).await?;
+        if values.len() != fields.len() {
+            return Err(Error::Protocol {
+                message: "HMGET reply length does not match the requested fields".to_string(),
+            });
+        }
+        Ok(fields
+            .iter()
+            .map(|field| field.to_string())
+            .zip(values)
+            .collect())
     }
 
-    /// Returns the union of all given sets (SUNION).
+    /// Iterates the field names of a hash using a cursor (HSCAN ... NOVALUES).
+    ///
+    /// Only field names are transferred, which halves the bandwidth for large
+    /// hashes. Requires Redis 7.4 or later.
     ///
     /// # Arguments
     ///
-    /// * `keys` - Slice of set keys.
+    /// * `key` - The hash key.
+    /// * `cursor` - The cursor value (use 0 to start iteration).
     ///
     /// # Returns
     ///
-    /// A vector of members in the union.
+    /// A tuple of (next_cursor, fields). When next_cursor is 0, the iteration is complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the server is older than 7.4 and
+    /// rejects NOVALUES.
     ///
     /// # Example
     ///
@@ -1890,55 +3059,66 @@ impl Client {
     /// # use muxis::Client;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let union = client.sunion(&["set1", "set2"]).await?;
+    /// let mut cursor = 0;
+    /// loop {
+    ///     let (next_cursor, fields) = client.hscan_keys("myhash", cursor).await?;
+    ///     println!("{:?}", fields);
+    ///     cursor = next_cursor;
+    ///     if cursor == 0 {
+    ///         break;
+    ///     }
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn sunion(&mut self, keys: &[&str]) -> Result<Vec<String>> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::sunion(keys_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_string(frame)
+    pub async fn hscan_keys(&mut self, key: &str, cursor: u64) -> Result<(u64, Vec<String>)> {
+        let cmd = command::hscan(key.to_string(), cursor, true);
+        let frame = self
+            .request(cmd.into_frame())
+            .await
+            .map_err(|e| map_unsupported_option(e, "HSCAN NOVALUES", "7.4"))?;
+        command::frame_to_scan_response(frame)
     }
 
-    /// Stores the difference between sets in a destination key (SDIFFSTORE).
+    /// Gets all fields and values from a hash (HGETALL).
     ///
     /// # Arguments
     ///
-    /// * `destination` - The destination key.
-    /// * `keys` - Slice of set keys.
+    /// * `key` - The hash key.
     ///
     /// # Returns
     ///
-    /// The number of members in the resulting set.
+    /// A HashMap of field names to values.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use bytes::Bytes;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let count = client.sdiffstore("dest", &["set1", "set2"]).await?;
+    /// let all_fields = client.hgetall("myhash").await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn sdiffstore(&mut self, destination: &str, keys: &[&str]) -> Result<i64> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::sdiffstore(destination.to_string(), keys_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn hgetall(&mut self, key: &str) -> Result<std::collections::HashMap<String, Bytes>> {
+        let cmd = command::hgetall(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_hashmap(frame)
     }
 
-    /// Stores the intersection of sets in a destination key (SINTERSTORE).
+    /// Gets all fields and values from a hash as ordered pairs (HGETALL).
+    ///
+    /// Unlike [`hgetall`](Self::hgetall), field names are kept as raw bytes, so
+    /// non-UTF8 fields survive intact, and pairs keep the order of the server reply.
     ///
     /// # Arguments
     ///
-    /// * `destination` - The destination key.
-    /// * `keys` - Slice of set keys.
+    /// * `key` - The hash key.
     ///
     /// # Returns
     ///
-    /// The number of members in the resulting set.
+    /// A vector of `(field, value)` pairs.
     ///
     /// # Example
     ///
@@ -1946,201 +3126,3796 @@ impl Client {
     /// # use muxis::Client;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let count = client.sinterstore("dest", &["set1", "set2"]).await?;
+    /// for (field, value) in client.hgetall_pairs("myhash").await? {
+    ///     println!("{:?} => {:?}", field, value);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn sinterstore(&mut self, destination: &str, keys: &[&str]) -> Result<i64> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::sinterstore(destination.to_string(), keys_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub async fn hgetall_pairs(&mut self, key: &str) -> Result<Vec<(Bytes, Bytes)>> {
+        let cmd = command::hgetall(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_pairs(frame)
     }
 
-    /// Stores the union of sets in a destination key (SUNIONSTORE).
+    /// Streams all fields and values of a hash, one HSCAN page at a time.
+    ///
+    /// Prefer this over [`hgetall`](Self::hgetall) for hashes that may be very
+    /// large: only one page is held in memory, and each HSCAN call blocks the
+    /// server briefly instead of building the whole reply at once. Unlike HGETALL
+    /// the result is not a snapshot; fields changed during the iteration may be
+    /// missed or, rarely, yielded twice.
+    ///
+    /// Other requests on this client can run between pages. The stream ends after
+    /// the first error.
     ///
     /// # Arguments
     ///
-    /// * `destination` - The destination key.
-    /// * `keys` - Slice of set keys.
+    /// * `key` - The hash key.
     ///
     /// # Returns
     ///
-    /// The number of members in the resulting set.
+    /// A stream of `(field, value)` pairs.
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use muxis::Client;
+    /// # use futures::StreamExt;
     /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
     /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
-    /// let count = client.sunionstore("dest", &["set1", "set2"]).await?;
+    /// let mut fields = Box::pin(client.hgetall_stream("bighash"));
+    /// while let Some(pair) = fields.next().await {
+    ///     let (field, value) = pair?;
+    ///     println!("{} => {:?}", field, value);
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn sunionstore(&mut self, destination: &str, keys: &[&str]) -> Result<i64> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::sunionstore(destination.to_string(), keys_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
-    }
-
-    /// Adds members with scores to a sorted set (ZADD).
-    pub async fn zadd(&mut self, key: &str, members: &[(f64, Bytes)]) -> Result<i64> {
-        let members_vec = members.to_vec();
-        let cmd = command::zadd(key.to_string(), members_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    pub fn hgetall_stream(
+        &mut self,
+        key: &str,
+    ) -> impl Stream<Item = Result<(String, Bytes)>> + Send + 'static {
+        let connection = self.connection.clone();
+        let key = Bytes::copy_from_slice(key.as_bytes());
+        // The state is the cursor of the next page, or None once the scan is done
+        stream::unfold(Some(0), move |cursor| {
+            let connection = connection.clone();
+            let key = key.clone();
+            async move {
+                let cmd = command::hscan(key, cursor?, false);
+                let page = connection
+                    .send_cmd(&cmd)
+                    .await
+                    .and_then(command::parse_frame_response)
+                    .and_then(command::frame_to_hscan_page)
+                    .map_err(|e| e.with_command("HSCAN"));
+                let (items, next) = match page {
+                    Ok((next, pairs)) => (
+                        pairs.into_iter().map(Ok).collect::<Vec<_>>(),
+                        (next != 0).then_some(next),
+                    ),
+                    Err(e) => (vec![Err(e)], None),
+                };
+                Some((stream::iter(items), next))
+            }
+        })
+        .flatten()
     }
 
-    /// Removes members from a sorted set (ZREM).
-    pub async fn zrem(&mut self, key: &str, members: &[Bytes]) -> Result<i64> {
-        let members_vec = members.to_vec();
-        let cmd = command::zrem(key.to_string(), members_vec);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
+    /// Deletes one or more fields from a hash (HDEL).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The hash key.
+    /// * `fields` - Slice of field names to delete.
+    ///
+    /// # Returns
+    ///
+    /// The number of fields that were deleted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let count = client.hdel("myhash", &["field1", "field2"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hdel(&mut self, key: &str, fields: &[&str]) -> Result<i64> {
+        let fields_vec = fields.iter().map(|f| f.to_string()).collect();
+        let cmd = command::hdel(key.to_string(), fields_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Checks if a field exists in a hash (HEXISTS).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The hash key.
+    /// * `field` - The field name.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field exists, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let exists = client.hexists("myhash", "field1").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hexists(&mut self, key: &str, field: &str) -> Result<bool> {
+        let cmd = command::hexists(key.to_string(), field.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bool(frame)
+    }
+
+    /// Gets the number of fields in a hash (HLEN).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The hash key.
+    ///
+    /// # Returns
+    ///
+    /// The number of fields in the hash.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let count = client.hlen("myhash").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hlen(&mut self, key: &str) -> Result<i64> {
+        let cmd = command::hlen(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Gets all field names from a hash (HKEYS).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The hash key.
+    ///
+    /// # Returns
+    ///
+    /// A vector of field names.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let keys = client.hkeys("myhash").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hkeys(&mut self, key: &str) -> Result<Vec<String>> {
+        let cmd = command::hkeys(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Gets all values from a hash (HVALS).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The hash key.
+    ///
+    /// # Returns
+    ///
+    /// A vector of values.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let values = client.hvals("myhash").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hvals(&mut self, key: &str) -> Result<Vec<String>> {
+        let cmd = command::hvals(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Increments a hash field by an integer (HINCRBY).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The hash key.
+    /// * `field` - The field name.
+    /// * `increment` - The amount to increment by.
+    ///
+    /// # Returns
+    ///
+    /// The value of the field after the increment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Overflow`] if the result does not fit in an i64.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let new_value = client.hincrby("myhash", "counter", 5).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hincrby(&mut self, key: &str, field: &str, increment: i64) -> Result<i64> {
+        let cmd = command::hincrby(key.to_string(), field.to_string(), increment);
+        let frame = self.request(cmd.into_frame()).await?;
         command::frame_to_int(frame)
     }
 
-    /// Returns a range of members from a sorted set by index (ZRANGE).
-    pub async fn zrange(&mut self, key: &str, start: i64, stop: i64) -> Result<Vec<String>> {
-        let cmd = command::zrange(key.to_string(), start, stop);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_string(frame)
+    /// Increments a hash field by a float (HINCRBYFLOAT).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The hash key.
+    /// * `field` - The field name.
+    /// * `increment` - The amount to increment by.
+    ///
+    /// # Returns
+    ///
+    /// The value of the field after the increment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] without contacting the server if `increment`
+    /// is NaN or infinite.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let new_value = client.hincrbyfloat("myhash", "price", 2.5).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hincrbyfloat(&mut self, key: &str, field: &str, increment: f64) -> Result<f64> {
+        check_finite("increment", increment)?;
+        let cmd = command::hincrbyfloat(key.to_string(), field.to_string(), increment);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_float(frame)
+    }
+
+    /// Sets a field in a hash only if it does not exist (HSETNX).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The hash key.
+    /// * `field` - The field name.
+    /// * `value` - The value to set.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field was set, `false` if it already existed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let was_set = client.hsetnx("myhash", "field1", Bytes::from("value")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hsetnx(&mut self, key: &str, field: &str, value: Bytes) -> Result<bool> {
+        let cmd = command::hsetnx(key.to_string(), field.to_string(), value);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bool(frame)
+    }
+
+    /// Pushes values to the head of a list (LPUSH).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `values` - Slice of values to push.
+    ///
+    /// # Returns
+    ///
+    /// The length of the list after the push operation.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let len = client.lpush("mylist", &[Bytes::from("value1"), Bytes::from("value2")]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lpush(&mut self, key: &str, values: &[Bytes]) -> Result<i64> {
+        self.check_type(key, KeyType::List)?;
+        let values_vec = values.to_vec();
+        let cmd = command::lpush(key.to_string(), values_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        let len = command::frame_to_int(frame)?;
+        self.remember_type(key, KeyType::List);
+        Ok(len)
+    }
+
+    /// Pushes values to the tail of a list (RPUSH).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `values` - Slice of values to push.
+    ///
+    /// # Returns
+    ///
+    /// The length of the list after the push operation.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let len = client.rpush("mylist", &[Bytes::from("value1"), Bytes::from("value2")]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rpush(&mut self, key: &str, values: &[Bytes]) -> Result<i64> {
+        self.check_type(key, KeyType::List)?;
+        let values_vec = values.to_vec();
+        let cmd = command::rpush(key.to_string(), values_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        let len = command::frame_to_int(frame)?;
+        self.remember_type(key, KeyType::List);
+        Ok(len)
+    }
+
+    /// Pushes values to the head of a list, only if the list exists (LPUSHX).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `values` - Slice of values to push.
+    ///
+    /// # Returns
+    ///
+    /// The length of the list after the push operation, or 0 if the key does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let len = client.lpushx("mylist", &[Bytes::from("value1")]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lpushx(&mut self, key: &str, values: &[Bytes]) -> Result<i64> {
+        let values_vec = values.to_vec();
+        let cmd = command::lpushx(key.to_string(), values_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Pushes values to the tail of a list, only if the list exists (RPUSHX).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `values` - Slice of values to push.
+    ///
+    /// # Returns
+    ///
+    /// The length of the list after the push operation, or 0 if the key does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let len = client.rpushx("mylist", &[Bytes::from("value1")]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rpushx(&mut self, key: &str, values: &[Bytes]) -> Result<i64> {
+        let values_vec = values.to_vec();
+        let cmd = command::rpushx(key.to_string(), values_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Removes and returns the first element of a list (LPOP).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Bytes)` if the list exists and has elements, or `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let value = client.lpop("mylist").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lpop(&mut self, key: &str) -> Result<Option<Bytes>> {
+        let cmd = command::lpop(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bytes(frame)
+    }
+
+    /// Removes and returns the last element of a list (RPOP).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Bytes)` if the list exists and has elements, or `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let value = client.rpop("mylist").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rpop(&mut self, key: &str) -> Result<Option<Bytes>> {
+        let cmd = command::rpop(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bytes(frame)
+    }
+
+    /// Removes and returns up to `count` elements from the head of a list (LPOP).
+    ///
+    /// Requires Redis 6.2 or later.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `count` - The maximum number of elements to pop.
+    ///
+    /// # Returns
+    ///
+    /// The popped elements in order, or an empty vector if the list does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let values = client.lpop_count("mylist", 10).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lpop_count(&mut self, key: &str, count: u64) -> Result<Vec<Bytes>> {
+        let cmd = command::lpop_count(key.to_string(), count);
+        let frame = self.request_cmd(&cmd).await?;
+        command::frame_to_popped(frame)
+    }
+
+    /// Removes and returns up to `count` elements from the tail of a list (RPOP).
+    ///
+    /// Requires Redis 6.2 or later.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `count` - The maximum number of elements to pop.
+    ///
+    /// # Returns
+    ///
+    /// The popped elements, last element first, or an empty vector if the list
+    /// does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let values = client.rpop_count("mylist", 10).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rpop_count(&mut self, key: &str, count: u64) -> Result<Vec<Bytes>> {
+        let cmd = command::rpop_count(key.to_string(), count);
+        let frame = self.request_cmd(&cmd).await?;
+        command::frame_to_popped(frame)
+    }
+
+    /// Returns the length of a list (LLEN).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    ///
+    /// # Returns
+    ///
+    /// The length of the list, or 0 if the key does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let len = client.llen("mylist").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn llen(&mut self, key: &str) -> Result<i64> {
+        let cmd = command::llen(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Returns a range of elements from a list (LRANGE).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `start` - Start index (0-based, negative values count from the end).
+    /// * `stop` - Stop index (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// A vector of elements in the specified range.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let elements = client.lrange("mylist", 0, -1).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lrange(&mut self, key: &str, start: i64, stop: i64) -> Result<Vec<Bytes>> {
+        let cmd = command::lrange(key.to_string(), start, stop);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_bytes_list(frame)
+    }
+
+    /// Streams a range of elements from a list (LRANGE).
+    ///
+    /// Unlike [`lrange`](Self::lrange), elements are yielded as they are decoded,
+    /// so a huge list is never held in memory at once. Other requests on this
+    /// client are answered only after the stream has been read (or dropped), so
+    /// consume it promptly.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `start` - Start index (0-based, negative values count from the end).
+    /// * `stop` - Stop index (inclusive).
+    ///
+    /// # Returns
+    ///
+    /// A stream of the elements in the specified range.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use futures::StreamExt;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let mut elements = Box::pin(client.lrange_stream("mylist", 0, -1));
+    /// while let Some(element) = elements.next().await {
+    ///     println!("{:?}", element?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lrange_stream(
+        &mut self,
+        key: &str,
+        start: i64,
+        stop: i64,
+    ) -> impl Stream<Item = Result<Bytes>> + Send + 'static {
+        let connection = self.connection.clone();
+        let frame = command::lrange(key.to_string(), start, stop).into_frame();
+        stream::once(async move { connection.send_streamed(frame).await })
+            .flat_map(|result| match result {
+                Ok(receiver) => stream::unfold(receiver, |mut receiver| async move {
+                    let item = receiver.recv().await?;
+                    Some((item, receiver))
+                })
+                .left_stream(),
+                Err(e) => stream::iter([Err(e)]).right_stream(),
+            })
+            .map(|item| match item? {
+                Frame::BulkString(Some(element)) => Ok(element),
+                _ => Err(Error::Protocol {
+                    message: "unexpected element in LRANGE reply".to_string(),
+                }),
+            })
+    }
+
+    /// Returns an element from a list by index (LINDEX).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `index` - The index (0-based, negative values count from the end).
+    ///
+    /// # Returns
+    ///
+    /// `Some(Bytes)` if the index is valid, or `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let element = client.lindex("mylist", 0).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lindex(&mut self, key: &str, index: i64) -> Result<Option<Bytes>> {
+        let cmd = command::lindex(key.to_string(), index);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bytes(frame)
+    }
+
+    /// Sets the value of an element in a list by index (LSET).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `index` - The index (0-based, negative values count from the end).
+    /// * `value` - The value to set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// client.lset("mylist", 0, Bytes::from("newvalue")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lset(&mut self, key: &str, index: i64, value: Bytes) -> Result<()> {
+        let cmd = command::lset(key.to_string(), index, value);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)?;
+        Ok(())
+    }
+
+    /// Removes elements from a list (LREM).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `count` - Number of elements to remove (positive: head to tail, negative: tail to head, 0: all).
+    /// * `value` - The value to remove.
+    ///
+    /// # Returns
+    ///
+    /// The number of elements removed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let removed = client.lrem("mylist", 0, Bytes::from("value")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lrem(&mut self, key: &str, count: i64, value: Bytes) -> Result<i64> {
+        let cmd = command::lrem(key.to_string(), count, value);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Trims a list to the specified range (LTRIM).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `start` - Start index (0-based, negative values count from the end).
+    /// * `stop` - Stop index (inclusive).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// client.ltrim("mylist", 0, 9).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn ltrim(&mut self, key: &str, start: i64, stop: i64) -> Result<()> {
+        let cmd = command::ltrim(key.to_string(), start, stop);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::parse_frame_response(frame)?;
+        Ok(())
+    }
+
+    /// Atomically removes the last element from a list and pushes it to another list (RPOPLPUSH).
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source list key.
+    /// * `destination` - The destination list key.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Bytes)` if the operation succeeded, or `None` if source is empty.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let element = client.rpoplpush("source", "destination").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rpoplpush(&mut self, source: &str, destination: &str) -> Result<Option<Bytes>> {
+        let cmd = command::rpoplpush(source.to_string(), destination.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bytes(frame)
+    }
+
+    /// Removes and returns the first element from one of multiple lists, blocking if needed (BLPOP).
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Slice of list keys to check.
+    /// * `timeout` - Timeout in seconds (0 means block indefinitely).
+    ///
+    /// # Returns
+    ///
+    /// `Some((key, value))` if an element was popped, or `None` if timeout occurred.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let result = client.blpop(&["list1", "list2"], 5).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn blpop(&mut self, keys: &[&str], timeout: u64) -> Result<Option<(String, Bytes)>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::blpop(keys_vec, timeout);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_blocking_pop(frame)
+    }
+
+    /// Removes and returns the first element of a single list, blocking if needed (BLPOP).
+    ///
+    /// Like [`blpop`](Self::blpop) with one key, without echoing the key back.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `timeout` - Timeout in seconds (0 means block indefinitely).
+    ///
+    /// # Returns
+    ///
+    /// The popped element, or `None` if timeout occurred.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// while let Some(job) = client.blpop_one("jobs", 5).await? {
+    ///     println!("processing {:?}", job);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn blpop_one(&mut self, key: &str, timeout: u64) -> Result<Option<Bytes>> {
+        let cmd = command::blpop(vec![key.to_string()], timeout);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_blocking_pop_value(frame)
+    }
+
+    /// Removes and returns the last element from one of multiple lists, blocking if needed (BRPOP).
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Slice of list keys to check.
+    /// * `timeout` - Timeout in seconds (0 means block indefinitely).
+    ///
+    /// # Returns
+    ///
+    /// `Some((key, value))` if an element was popped, or `None` if timeout occurred.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let result = client.brpop(&["list1", "list2"], 5).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn brpop(&mut self, keys: &[&str], timeout: u64) -> Result<Option<(String, Bytes)>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::brpop(keys_vec, timeout);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_blocking_pop(frame)
+    }
+
+    /// Returns the index of the first matching element in a list (LPOS).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The list key.
+    /// * `element` - The element to search for.
+    ///
+    /// # Returns
+    ///
+    /// `Some(i64)` with the index if found, or `None` if not found.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let position = client.lpos("mylist", Bytes::from("value")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn lpos(&mut self, key: &str, element: Bytes) -> Result<Option<i64>> {
+        let cmd = command::lpos(key.to_string(), element);
+        let frame = self.request(cmd.into_frame()).await?;
+        match frame {
+            Frame::Null | Frame::BulkString(None) => Ok(None),
+            Frame::Integer(i) => Ok(Some(i)),
+            _ => command::frame_to_int(frame).map(Some),
+        }
+    }
+
+    /// Adds one or more members to a set (SADD).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The set key.
+    /// * `members` - Slice of members to add.
+    ///
+    /// # Returns
+    ///
+    /// The number of members that were added to the set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let added = client.sadd("myset", &[Bytes::from("a"), Bytes::from("b")]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sadd(&mut self, key: &str, members: &[Bytes]) -> Result<i64> {
+        self.check_type(key, KeyType::Set)?;
+        let members_vec = members.to_vec();
+        let cmd = command::sadd(key.to_string(), members_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        let added = command::frame_to_int(frame)?;
+        self.remember_type(key, KeyType::Set);
+        Ok(added)
+    }
+
+    /// Removes one or more members from a set (SREM).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The set key.
+    /// * `members` - Slice of members to remove.
+    ///
+    /// # Returns
+    ///
+    /// The number of members that were removed from the set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let removed = client.srem("myset", &[Bytes::from("a")]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn srem(&mut self, key: &str, members: &[Bytes]) -> Result<i64> {
+        let members_vec = members.to_vec();
+        let cmd = command::srem(key.to_string(), members_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Removes and returns a random member from a set (SPOP).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The set key.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Bytes)` if the set exists and has members, or `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let member = client.spop("myset").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn spop(&mut self, key: &str) -> Result<Option<Bytes>> {
+        let cmd = command::spop(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bytes(frame)
+    }
+
+    /// Returns all members of a set (SMEMBERS).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The set key.
+    ///
+    /// # Returns
+    ///
+    /// A vector of all members in the set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let members = client.smembers("myset").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn smembers(&mut self, key: &str) -> Result<Vec<String>> {
+        let cmd = command::smembers(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Checks if a member exists in a set (SISMEMBER).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The set key.
+    /// * `member` - The member to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the member exists in the set, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let exists = client.sismember("myset", Bytes::from("member")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sismember(&mut self, key: &str, member: Bytes) -> Result<bool> {
+        let cmd = command::sismember(key.to_string(), member);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bool(frame)
+    }
+
+    /// Returns the cardinality (size) of a set (SCARD).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The set key.
+    ///
+    /// # Returns
+    ///
+    /// The number of members in the set, or 0 if the key does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let size = client.scard("myset").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn scard(&mut self, key: &str) -> Result<i64> {
+        let cmd = command::scard(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Returns a random member from a set (SRANDMEMBER).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The set key.
+    ///
+    /// # Returns
+    ///
+    /// `Some(String)` with a random member, or `None` if the set is empty.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let member = client.srandmember("myset").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn srandmember(&mut self, key: &str) -> Result<Option<String>> {
+        let cmd = command::srandmember(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        match frame {
+            Frame::Null => Ok(None),
+            _ => command::frame_to_string(frame).map(Some),
+        }
+    }
+
+    /// Returns the difference between the first set and all successive sets (SDIFF).
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Slice of set keys.
+    ///
+    /// # Returns
+    ///
+    /// A vector of members in the difference.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let diff = client.sdiff(&["set1", "set2"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sdiff(&mut self, keys: &[&str]) -> Result<Vec<String>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::sdiff(keys_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Returns the intersection of all given sets (SINTER).
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Slice of set keys.
+    ///
+    /// # Returns
+    ///
+    /// A vector of members in the intersection.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let inter = client.sinter(&["set1", "set2"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sinter(&mut self, keys: &[&str]) -> Result<Vec<String>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::sinter(keys_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Returns the union of all given sets (SUNION).
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Slice of set keys.
+    ///
+    /// # Returns
+    ///
+    /// A vector of members in the union.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let union = client.sunion(&["set1", "set2"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sunion(&mut self, keys: &[&str]) -> Result<Vec<String>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::sunion(keys_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Stores the difference between sets in a destination key (SDIFFSTORE).
+    ///
+    /// # Arguments
+    ///
+    /// * `destination` - The destination key.
+    /// * `keys` - Slice of set keys.
+    ///
+    /// # Returns
+    ///
+    /// The number of members in the resulting set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let count = client.sdiffstore("dest", &["set1", "set2"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sdiffstore(&mut self, destination: &str, keys: &[&str]) -> Result<i64> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::sdiffstore(destination.to_string(), keys_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Stores the intersection of sets in a destination key (SINTERSTORE).
+    ///
+    /// # Arguments
+    ///
+    /// * `destination` - The destination key.
+    /// * `keys` - Slice of set keys.
+    ///
+    /// # Returns
+    ///
+    /// The number of members in the resulting set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let count = client.sinterstore("dest", &["set1", "set2"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sinterstore(&mut self, destination: &str, keys: &[&str]) -> Result<i64> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::sinterstore(destination.to_string(), keys_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Stores the union of sets in a destination key (SUNIONSTORE).
+    ///
+    /// # Arguments
+    ///
+    /// * `destination` - The destination key.
+    /// * `keys` - Slice of set keys.
+    ///
+    /// # Returns
+    ///
+    /// The number of members in the resulting set.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let count = client.sunionstore("dest", &["set1", "set2"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sunionstore(&mut self, destination: &str, keys: &[&str]) -> Result<i64> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::sunionstore(destination.to_string(), keys_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Adds members with scores to a sorted set (ZADD).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] without contacting the server if a score is NaN.
+    pub async fn zadd(&mut self, key: &str, members: &[(f64, Bytes)]) -> Result<i64> {
+        for (score, _) in members {
+            check_score(*score)?;
+        }
+        self.check_type(key, KeyType::ZSet)?;
+        let members_vec = members.to_vec();
+        let cmd = command::zadd(key.to_string(), members_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        let added = command::frame_to_int(frame)?;
+        self.remember_type(key, KeyType::ZSet);
+        Ok(added)
+    }
+
+    /// Removes members from a sorted set (ZREM).
+    pub async fn zrem(&mut self, key: &str, members: &[Bytes]) -> Result<i64> {
+        let members_vec = members.to_vec();
+        let cmd = command::zrem(key.to_string(), members_vec);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Returns a range of members from a sorted set by index (ZRANGE).
+    pub async fn zrange(&mut self, key: &str, start: i64, stop: i64) -> Result<Vec<String>> {
+        let cmd = command::zrange(key.to_string(), start, stop);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Stores a range of a sorted set by index into another key (ZRANGESTORE, Redis 6.2+).
+    ///
+    /// `dest` is overwritten; an empty range deletes it.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The key to store the range in.
+    /// * `src` - The source sorted set.
+    /// * `start` - The first index; negative values count from the end.
+    /// * `stop` - The last index, inclusive.
+    ///
+    /// # Returns
+    ///
+    /// The number of members stored in `dest`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let stored = client.zrangestore("top3", "scores", -3, -1).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn zrangestore(
+        &mut self,
+        dest: &str,
+        src: &str,
+        start: i64,
+        stop: i64,
+    ) -> Result<i64> {
+        self.zrangestore_opts(
+            dest,
+            src,
+            &start.to_string(),
+            &stop.to_string(),
+            ZRangeOptions::new(),
+        )
+        .await
+    }
+
+    /// Stores a range of a sorted set into another key, by index, score or
+    /// lexicographic order (ZRANGESTORE, Redis 6.2+).
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The key to store the range in.
+    /// * `src` - The source sorted set.
+    /// * `start` - The first bound, interpreted according to `opts`.
+    /// * `stop` - The last bound, interpreted according to `opts`.
+    /// * `opts` - How to interpret the bounds, ordering and limit.
+    ///
+    /// # Returns
+    ///
+    /// The number of members stored in `dest`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if a limit is set for an index range, which
+    /// Redis does not support.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::{Client, ZRangeOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let opts = ZRangeOptions::new().by_score().rev().limit(0, 10);
+    /// client.zrangestore_opts("top10", "scores", "+inf", "-inf", opts).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn zrangestore_opts(
+        &mut self,
+        dest: &str,
+        src: &str,
+        start: &str,
+        stop: &str,
+        opts: ZRangeOptions,
+    ) -> Result<i64> {
+        if opts.limit.is_some() && opts.by.is_none() {
+            return Err(Error::InvalidArgument {
+                message: "LIMIT requires a score or lexicographic range".to_string(),
+            });
+        }
+        let cmd = command::zrangestore(
+            dest.to_string(),
+            src.to_string(),
+            start.to_string(),
+            stop.to_string(),
+            opts,
+        );
+        let frame = self.request_cmd(&cmd).await?;
+        let stored = command::frame_to_int(frame)?;
+        self.remember_type(
+            dest,
+            if stored > 0 {
+                KeyType::ZSet
+            } else {
+                KeyType::None
+            },
+        );
+        Ok(stored)
+    }
+
+    /// Returns members in a sorted set within a score range (ZRANGEBYSCORE).
+    pub async fn zrangebyscore(&mut self, key: &str, min: &str, max: &str) -> Result<Vec<String>> {
+        let cmd = command::zrangebyscore(key.to_string(), min.to_string(), max.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Returns the rank of a member in a sorted set (ZRANK).
+    pub async fn zrank(&mut self, key: &str, member: Bytes) -> Result<Option<i64>> {
+        let cmd = command::zrank(key.to_string(), member);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_optional_int(frame)
+    }
+
+    /// Returns the score of a member in a sorted set (ZSCORE).
+    pub async fn zscore(&mut self, key: &str, member: Bytes) -> Result<Option<f64>> {
+        let cmd = command::zscore(key.to_string(), member);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_optional_float(frame)
+    }
+
+    /// Returns the cardinality of a sorted set (ZCARD).
+    pub async fn zcard(&mut self, key: &str) -> Result<i64> {
+        let cmd = command::zcard(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Returns the count of members within a score range (ZCOUNT).
+    pub async fn zcount(&mut self, key: &str, min: &str, max: &str) -> Result<i64> {
+        let cmd = command::zcount(key.to_string(), min.to_string(), max.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Increments the score of a member in a sorted set (ZINCRBY).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] without contacting the server if `increment`
+    /// is NaN or infinite.
+    pub async fn zincrby(&mut self, key: &str, increment: f64, member: Bytes) -> Result<f64> {
+        check_finite("increment", increment)?;
+        let cmd = command::zincrby(key.to_string(), increment, member);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_float(frame)
+    }
+
+    /// Increments a member's score, optionally only if it exists or does not (ZADD INCR).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The sorted set key.
+    /// * `member` - The member whose score to increment.
+    /// * `delta` - The increment.
+    /// * `condition` - `Some(ZAddCondition::Xx)` to only touch existing members,
+    ///   `Some(ZAddCondition::Nx)` to only add new ones, or `None` for no restriction.
+    ///
+    /// # Returns
+    ///
+    /// The new score, or `None` if the condition prevented the update.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] without contacting the server if `delta` is NaN
+    /// or infinite.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::{Client, ZAddCondition};
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let score = client
+    ///     .zincr_member("limits", Bytes::from("user:1"), 1.0, Some(ZAddCondition::Xx))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn zincr_member(
+        &mut self,
+        key: &str,
+        member: Bytes,
+        delta: f64,
+        condition: Option<ZAddCondition>,
+    ) -> Result<Option<f64>> {
+        check_finite("delta", delta)?;
+        let cmd = command::zadd_incr(key.to_string(), delta, member, condition);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_optional_float(frame)
+    }
+
+    /// Sets a member's score only if it is higher than the current one (ZADD GT CH).
+    ///
+    /// Suited to leaderboards where scores only ever improve. A member that does
+    /// not exist yet is added with `score`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The sorted set key.
+    /// * `member` - The member to update.
+    /// * `score` - The candidate score.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the member was added or its score changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] without contacting the server if `score` is NaN.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::Client;
+    /// # use bytes::Bytes;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let improved = client
+    ///     .zupdate_if_greater("highscores", Bytes::from("alice"), 1200.0)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn zupdate_if_greater(
+        &mut self,
+        key: &str,
+        member: Bytes,
+        score: f64,
+    ) -> Result<bool> {
+        check_score(score)?;
+        let cmd = command::zadd_gt(key.to_string(), score, member);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bool(frame)
+    }
+
+    /// Sets a member's score only if it is lower than the current one (ZADD LT CH).
+    ///
+    /// The counterpart of [`zupdate_if_greater`](Self::zupdate_if_greater), e.g. for
+    /// best lap times. A member that does not exist yet is added with `score`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The sorted set key.
+    /// * `member` - The member to update.
+    /// * `score` - The candidate score.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the member was added or its score changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] without contacting the server if `score` is NaN.
+    pub async fn zupdate_if_less(&mut self, key: &str, member: Bytes, score: f64) -> Result<bool> {
+        check_score(score)?;
+        let cmd = command::zadd_lt(key.to_string(), score, member);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bool(frame)
+    }
+
+    /// Returns a range of members in reverse order (ZREVRANGE).
+    pub async fn zrevrange(&mut self, key: &str, start: i64, stop: i64) -> Result<Vec<String>> {
+        let cmd = command::zrevrange(key.to_string(), start, stop);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Returns the reverse rank of a member (ZREVRANK).
+    pub async fn zrevrank(&mut self, key: &str, member: Bytes) -> Result<Option<i64>> {
+        let cmd = command::zrevrank(key.to_string(), member);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_optional_int(frame)
+    }
+
+    /// Removes members by rank range (ZREMRANGEBYRANK).
+    pub async fn zremrangebyrank(&mut self, key: &str, start: i64, stop: i64) -> Result<i64> {
+        let cmd = command::zremrangebyrank(key.to_string(), start, stop);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Removes members by score range (ZREMRANGEBYSCORE).
+    pub async fn zremrangebyscore(&mut self, key: &str, min: &str, max: &str) -> Result<i64> {
+        let cmd = command::zremrangebyscore(key.to_string(), min.to_string(), max.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Removes and returns the member with the lowest score (ZPOPMIN).
+    pub async fn zpopmin(&mut self, key: &str) -> Result<Option<(String, f64)>> {
+        let cmd = command::zpopmin(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_zpop_result(frame)
+    }
+
+    /// Removes and returns the member with the highest score (ZPOPMAX).
+    pub async fn zpopmax(&mut self, key: &str) -> Result<Option<(String, f64)>> {
+        let cmd = command::zpopmax(key.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_zpop_result(frame)
+    }
+
+    /// Blocking ZPOPMIN (BZPOPMIN).
+    pub async fn bzpopmin(
+        &mut self,
+        keys: &[&str],
+        timeout: u64,
+    ) -> Result<Option<(String, String, f64)>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::bzpopmin(keys_vec, timeout);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bzpop_result(frame)
+    }
+
+    /// Blocking ZPOPMAX (BZPOPMAX).
+    pub async fn bzpopmax(
+        &mut self,
+        keys: &[&str],
+        timeout: u64,
+    ) -> Result<Option<(String, String, f64)>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::bzpopmax(keys_vec, timeout);
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_bzpop_result(frame)
+    }
+
+    /// Returns the members of the union of sorted sets (ZUNION, Redis 6.2+).
+    pub async fn zunion(&mut self, keys: &[&str]) -> Result<Vec<String>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::zunion(keys_vec, None, None, false);
+        let frame = self.request_cmd(&cmd).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Returns the members of the union of sorted sets with their scores
+    /// (ZUNION ... WITHSCORES, Redis 6.2+).
+    pub async fn zunion_withscores(&mut self, keys: &[&str]) -> Result<Vec<(String, f64)>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::zunion(keys_vec, None, None, true);
+        let frame = self.request_cmd(&cmd).await?;
+        command::frame_to_scored_members(frame)
+    }
+
+    /// Returns the union of sorted sets with weighted, aggregated scores
+    /// (ZUNION ... WEIGHTS ... AGGREGATE ... WITHSCORES, Redis 6.2+).
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The sorted sets to combine.
+    /// * `weights` - One multiplier per key, applied to its scores.
+    /// * `aggregate` - How the scores of a member present in several sets combine.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] without contacting the server if the number
+    /// of weights differs from the number of keys or a weight is NaN.
+    pub async fn zunion_weighted(
+        &mut self,
+        keys: &[&str],
+        weights: &[f64],
+        aggregate: Aggregate,
+    ) -> Result<Vec<(String, f64)>> {
+        check_weights(keys, weights)?;
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::zunion(keys_vec, Some(weights.to_vec()), Some(aggregate), true);
+        let frame = self.request_cmd(&cmd).await?;
+        command::frame_to_scored_members(frame)
+    }
+
+    /// Returns the members of the intersection of sorted sets (ZINTER, Redis 6.2+).
+    pub async fn zinter(&mut self, keys: &[&str]) -> Result<Vec<String>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::zinter(keys_vec, None, None, false);
+        let frame = self.request_cmd(&cmd).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Returns the members of the intersection of sorted sets with their scores
+    /// (ZINTER ... WITHSCORES, Redis 6.2+).
+    pub async fn zinter_withscores(&mut self, keys: &[&str]) -> Result<Vec<(String, f64)>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::zinter(keys_vec, None, None, true);
+        let frame = self.request_cmd(&cmd).await?;
+        command::frame_to_scored_members(frame)
+    }
+
+    /// Returns the intersection of sorted sets with weighted, aggregated scores
+    /// (ZINTER ... WEIGHTS ... AGGREGATE ... WITHSCORES, Redis 6.2+).
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The sorted sets to combine.
+    /// * `weights` - One multiplier per key, applied to its scores.
+    /// * `aggregate` - How the scores of a member present in several sets combine.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] without contacting the server if the number
+    /// of weights differs from the number of keys or a weight is NaN.
+    pub async fn zinter_weighted(
+        &mut self,
+        keys: &[&str],
+        weights: &[f64],
+        aggregate: Aggregate,
+    ) -> Result<Vec<(String, f64)>> {
+        check_weights(keys, weights)?;
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::zinter(keys_vec, Some(weights.to_vec()), Some(aggregate), true);
+        let frame = self.request_cmd(&cmd).await?;
+        command::frame_to_scored_members(frame)
+    }
+
+    /// Returns the members of the difference of sorted sets (ZDIFF, Redis 6.2+).
+    ///
+    /// Members of the first set that are in none of the others.
+    pub async fn zdiff(&mut self, keys: &[&str]) -> Result<Vec<String>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::zdiff(keys_vec, false);
+        let frame = self.request_cmd(&cmd).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Returns the members of the difference of sorted sets with their scores
+    /// (ZDIFF ... WITHSCORES, Redis 6.2+).
+    pub async fn zdiff_withscores(&mut self, keys: &[&str]) -> Result<Vec<(String, f64)>> {
+        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
+        let cmd = command::zdiff(keys_vec, true);
+        let frame = self.request_cmd(&cmd).await?;
+        command::frame_to_scored_members(frame)
+    }
+
+    /// Returns count of members between lexicographical range (ZLEXCOUNT).
+    pub async fn zlexcount(&mut self, key: &str, min: &str, max: &str) -> Result<i64> {
+        let cmd = command::zlexcount(key.to_string(), min.to_string(), max.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+
+    /// Returns members between lexicographical range (ZRANGEBYLEX).
+    pub async fn zrangebylex(&mut self, key: &str, min: &str, max: &str) -> Result<Vec<String>> {
+        let cmd = command::zrangebylex(key.to_string(), min.to_string(), max.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_vec_string(frame)
+    }
+
+    /// Removes members between lexicographical range (ZREMRANGEBYLEX).
+    pub async fn zremrangebylex(&mut self, key: &str, min: &str, max: &str) -> Result<i64> {
+        let cmd = command::zremrangebylex(key.to_string(), min.to_string(), max.to_string());
+        let frame = self.request(cmd.into_frame()).await?;
+        command::frame_to_int(frame)
+    }
+}
+
+/// Bounds a single client call by a deadline.
+///
+/// If `future` does not complete within `duration`, it is dropped and
+/// [`Error::Timeout`] is returned. Dropping a pending command abandons its
+/// multiplexer slot: the late reply is read and discarded, so later commands
+/// on the same connection still receive their own replies. A blocking command
+/// that never replies keeps the connection busy, though, so prefer the server-side
+/// timeout argument of blocking commands where one exists.
+///
+/// # Arguments
+///
+/// * `duration` - Maximum time to wait for `future`
+/// * `future` - The call to bound
+///
+/// # Example
+///
+/// ```no_run
+/// # use muxis::Client;
+/// # use std::time::Duration;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+/// match muxis::timeout(Duration::from_secs(1), client.blpop(&["jobs"], 0)).await {
+///     Ok(item) => println!("popped {:?}", item),
+///     Err(muxis::Error::Timeout) => println!("no job within a second"),
+///     Err(e) => return Err(e.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn timeout<T, F>(duration: Duration, future: F) -> Result<T>
+where
+    F: std::future::Future<Output = Result<T>>,
+{
+    tokio::time::timeout(duration, future)
+        .await
+        .map_err(|_| Error::Timeout)?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Spawns a single-connection mock server answering every command with `handler`.
+    async fn spawn_mock_server<F>(handler: F) -> String
+    where
+        F: Fn(&[Frame]) -> Frame + Send + 'static,
+    {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = connection::Connection::new(socket);
+            while let Ok(Frame::Array(args)) = conn.read_frame().await {
+                if conn.write_frame(&handler(&args)).await.is_err() {
+                    return;
+                }
+            }
+        });
+        format!("redis://{}", addr)
+    }
+
+    /// Returns the client and server ends of an in-memory connection.
+    ///
+    /// Tests on paused time use it instead of TCP: tokio auto-advances the clock
+    /// whenever the runtime parks, which over a socket can happen while a frame is
+    /// still in flight. An in-memory pipe wakes the peer task directly.
+    fn memory_pipe() -> (
+        connection::Connection<tokio::io::DuplexStream>,
+        connection::Connection<tokio::io::DuplexStream>,
+    ) {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        (
+            connection::Connection::new(client),
+            connection::Connection::new(server),
+        )
+    }
+
+    /// Builds a client over `connection`, skipping the connection handshake.
+    fn client_over(connection: multiplexed::MultiplexedConnection) -> Client {
+        Client {
+            connection,
+            endpoint: Arc::new(Endpoint {
+                address: "memory".to_string(),
+                is_tls: false,
+                settings: ConnectionSettings::default(),
+            }),
+            type_cache: None,
+        }
+    }
+
+    /// Like `spawn_mock_server`, over a `memory_pipe` for tests on paused time.
+    fn memory_mock_connection<F>(handler: F) -> multiplexed::MultiplexedConnection
+    where
+        F: Fn(&[Frame]) -> Frame + Send + 'static,
+    {
+        let (client, mut server) = memory_pipe();
+        tokio::spawn(async move {
+            while let Ok(Frame::Array(args)) = server.read_frame().await {
+                if server.write_frame(&handler(&args)).await.is_err() {
+                    return;
+                }
+            }
+        });
+        multiplexed::MultiplexedConnection::new(client, 1024)
+    }
+
+    /// A client over a `memory_mock_connection`.
+    fn memory_mock_client<F>(handler: F) -> Client
+    where
+        F: Fn(&[Frame]) -> Frame + Send + 'static,
+    {
+        client_over(memory_mock_connection(handler))
+    }
+
+    #[tokio::test]
+    async fn test_non_finite_floats_rejected_before_sending() {
+        let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let addr = spawn_mock_server(move |args| {
+            log.lock().unwrap().push(args[0].clone());
+            match args[0].as_bytes() {
+                Some(b"ZADD") => Frame::Integer(1),
+                _ => Frame::BulkString(Some(Bytes::from("1"))),
+            }
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(
+                client.hincrbyfloat("h", "f", value).await,
+                Err(Error::InvalidArgument { .. })
+            ));
+            assert!(matches!(
+                client.zincrby("z", value, Bytes::from("m")).await,
+                Err(Error::InvalidArgument { .. })
+            ));
+            assert!(matches!(
+                client
+                    .zincr_member("z", Bytes::from("m"), value, None)
+                    .await,
+                Err(Error::InvalidArgument { .. })
+            ));
+        }
+        assert!(matches!(
+            client
+                .zadd(
+                    "z",
+                    &[(1.0, Bytes::from("a")), (f64::NAN, Bytes::from("b"))]
+                )
+                .await,
+            Err(Error::InvalidArgument { .. })
+        ));
+        assert!(received.lock().unwrap().is_empty());
+
+        // Infinite scores are valid for ZADD, finite increments go through
+        assert_eq!(client.hincrbyfloat("h", "f", 0.5).await.unwrap(), 1.0);
+        assert_eq!(
+            client
+                .zadd("z", &[(f64::INFINITY, Bytes::from("top"))])
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_current_database_tracks_select() {
+        let addr = spawn_mock_server(|args| match &args[1] {
+            Frame::BulkString(Some(db)) if db.as_ref() == b"99" => {
+                Frame::Error(b"ERR DB index is out of range".to_vec())
+            }
+            _ => Frame::SimpleString(b"OK".to_vec()),
+        })
+        .await;
+
+        let mut client = builder::ClientBuilder::new()
+            .address(addr)
+            .database(3)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(client.current_database(), 3);
+
+        let clone = client.clone();
+        client.select(5).await.unwrap();
+        assert_eq!(client.current_database(), 5);
+        assert_eq!(clone.current_database(), 5);
+
+        assert!(client.select(99).await.is_err());
+        assert_eq!(client.current_database(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_exists_each() {
+        let addr = spawn_mock_server(|args| match &args[1] {
+            Frame::BulkString(Some(key)) if key.starts_with(b"present") => Frame::Integer(1),
+            _ => Frame::Integer(0),
+        })
+        .await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        let exists = client
+            .exists_each(&["present:1", "missing:1", "present:2", "missing:2"])
+            .await
+            .unwrap();
+        assert_eq!(exists, vec![true, false, true, false]);
+
+        assert!(client.exists_each(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expire_many() {
+        let addr = spawn_mock_server(|args| match (&args[1], &args[2]) {
+            (Frame::BulkString(Some(key)), Frame::BulkString(Some(seconds)))
+                if key.starts_with(b"present") && seconds.as_ref() != b"0" =>
+            {
+                Frame::Integer(1)
+            }
+            _ => Frame::Integer(0),
+        })
+        .await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        let set = client
+            .expire_many(&[
+                ("present:1", 60),
+                ("missing:1", 60),
+                ("present:2", 300),
+                ("missing:2", 300),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(set, vec![true, false, true, false]);
+
+        assert!(client.expire_many(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_any_string() {
+        let addr = spawn_mock_server(|args| match args[0].as_bytes() {
+            Some(b"TYPE") => Frame::SimpleString(b"string".to_vec()),
+            Some(b"GET") => Frame::BulkString(Some(Bytes::from("hello"))),
+            _ => Frame::Error(b"ERR unexpected command".to_vec()),
+        })
+        .await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        assert_eq!(
+            client.fetch_any("greeting").await.unwrap(),
+            RedisValue::String(Bytes::from("hello"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_any_hash() {
+        let addr = spawn_mock_server(|args| {
+            let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+            match (args[0].as_bytes(), args[1].as_bytes()) {
+                (Some(b"TYPE"), Some(b"user:1")) => Frame::SimpleString(b"hash".to_vec()),
+                (Some(b"TYPE"), _) => Frame::SimpleString(b"none".to_vec()),
+                (Some(b"HGETALL"), _) => {
+                    Frame::Array(vec![bulk("name"), bulk("ada"), bulk("lang"), bulk("en")])
+                }
+                _ => Frame::Error(b"ERR unexpected command".to_vec()),
+            }
+        })
+        .await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        assert_eq!(
+            client.fetch_any("user:1").await.unwrap(),
+            RedisValue::Hash(vec![
+                (Bytes::from("name"), Bytes::from("ada")),
+                (Bytes::from("lang"), Bytes::from("en")),
+            ])
+        );
+        assert_eq!(client.fetch_any("user:2").await.unwrap(), RedisValue::None);
+    }
+
+    #[tokio::test]
+    async fn test_mset_with_ttl_sends_expiry_per_key() {
+        let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = sent.clone();
+        let addr = spawn_mock_server(move |args| {
+            log.lock().unwrap().push(args.to_vec());
+            Frame::SimpleString(b"OK".to_vec())
+        })
+        .await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        client
+            .mset_with_ttl(&[
+                ("plain", Bytes::from("a"), None),
+                ("seconds", Bytes::from("b"), Some(Duration::from_secs(60))),
+                (
+                    "millis",
+                    Bytes::from("c"),
+                    Some(Duration::from_millis(1500)),
+                ),
+            ])
+            .await
+            .unwrap();
+
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![
+                vec![bulk("SET"), bulk("plain"), bulk("a")],
+                vec![
+                    bulk("SET"),
+                    bulk("seconds"),
+                    bulk("b"),
+                    bulk("EX"),
+                    bulk("60")
+                ],
+                vec![
+                    bulk("SET"),
+                    bulk("millis"),
+                    bulk("c"),
+                    bulk("PX"),
+                    bulk("1500")
+                ],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mset_with_ttl_reports_rejected_set() {
+        let addr = spawn_mock_server(|args| match args.len() {
+            5 => Frame::Error(b"ERR invalid expire time in 'set' command".to_vec()),
+            _ => Frame::SimpleString(b"OK".to_vec()),
+        })
+        .await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        let error = client
+            .mset_with_ttl(&[
+                ("ok", Bytes::from("a"), None),
+                ("bad", Bytes::from("b"), Some(Duration::ZERO)),
+            ])
+            .await
+            .unwrap_err();
+        assert_eq!(error.command(), Some("SET"));
+        assert!(matches!(error.inner(), Error::Server { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_ttl_typed() {
+        let addr = spawn_mock_server(|args| match args[1].as_bytes() {
+            Some(b"persistent") => Frame::Integer(-1),
+            Some(b"missing") => Frame::Integer(-2),
+            Some(b"session") => Frame::Integer(42),
+            _ => Frame::Integer(-3),
+        })
+        .await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        assert_eq!(client.ttl_typed("persistent").await.unwrap(), Ttl::NoExpiry);
+        assert_eq!(client.ttl_typed("missing").await.unwrap(), Ttl::Missing);
+        assert_eq!(client.ttl_typed("session").await.unwrap(), Ttl::Seconds(42));
+        assert!(client.ttl_typed("other").await.is_err());
+        assert_eq!(client.ttl("persistent").await.unwrap(), -1);
+    }
+
+    #[tokio::test]
+    async fn test_connection_name_unique_sends_distinct_names() {
+        let mut names = Vec::new();
+        for _ in 0..2 {
+            let sent = Arc::new(std::sync::Mutex::new(None));
+            let log = sent.clone();
+            // SETNAME is part of HELLO with resp3 and a CLIENT SETNAME otherwise
+            let addr = spawn_mock_server(move |args| {
+                if let Some(at) = args.iter().position(|a| a.as_bytes() == Some(b"SETNAME")) {
+                    *log.lock().unwrap() = args[at + 1].as_str().map(|name| name.into_owned());
+                }
+                Frame::SimpleString(b"OK".to_vec())
+            })
+            .await;
+            builder::ClientBuilder::new()
+                .address(addr)
+                .connection_name_unique("myapp")
+                .build()
+                .await
+                .unwrap();
+            names.push(sent.lock().unwrap().take().unwrap());
+        }
+
+        assert!(names[0].starts_with("myapp-"), "{}", names[0]);
+        assert_ne!(names[0], names[1]);
+    }
+
+    #[tokio::test]
+    async fn test_watch_and_unwatch() {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let addr = spawn_mock_server(move |args| {
+            log.lock().unwrap().push(args.to_vec());
+            Frame::SimpleString(b"OK".to_vec())
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        assert!(matches!(
+            client.watch(&[]).await,
+            Err(Error::InvalidArgument { .. })
+        ));
+        client.watch(&["a", "b"]).await.unwrap();
+        // The watch is on the shared connection, so a clone can release it
+        client.clone().unwatch().await.unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(
+            *received,
+            vec![
+                command::watch(["a", "b"]).into_frame().to_array().unwrap(),
+                command::unwatch().into_frame().to_array().unwrap(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hgetall_stream_follows_hscan_cursor() {
+        let addr = spawn_mock_server(|args| {
+            let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+            assert_eq!(args[0].as_bytes(), Some(&b"HSCAN"[..]));
+            assert_eq!(args[1].as_bytes(), Some(&b"bighash"[..]));
+            match args[2].as_bytes() {
+                Some(b"0") => Frame::Array(vec![
+                    bulk("17"),
+                    Frame::Array(vec![bulk("a"), bulk("1"), bulk("b"), bulk("2")]),
+                ]),
+                Some(b"17") => {
+                    Frame::Array(vec![bulk("0"), Frame::Array(vec![bulk("c"), bulk("3")])])
+                }
+                other => panic!("unexpected cursor: {:?}", other),
+            }
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let pairs: Vec<_> = client
+            .hgetall_stream("bighash")
+            .map(|pair| pair.unwrap())
+            .collect()
+            .await;
+        assert_eq!(
+            pairs,
+            vec![
+                ("a".to_string(), Bytes::from("1")),
+                ("b".to_string(), Bytes::from("2")),
+                ("c".to_string(), Bytes::from("3")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exists_each_maps_error_replies() {
+        let addr = spawn_mock_server(|_| {
+            Frame::Error(b"LOADING Redis is loading the dataset in memory".to_vec())
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let err = client.exists_each(&["a", "b"]).await.unwrap_err();
+        assert_eq!(err.command(), Some("EXISTS"));
+        assert!(matches!(err.inner(), Error::Loading));
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_expire_and_ttl_maps_error_replies() {
+        let addr = spawn_mock_server(|args| match args[0].as_bytes() {
+            Some(b"EXPIRE") => Frame::Integer(1),
+            _ => Frame::Error(b"MASTERDOWN Link with MASTER is down".to_vec()),
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let err = client.expire_and_ttl("key", 60).await.unwrap_err();
+        assert_eq!(err.command(), Some("TTL"));
+        assert!(matches!(err.inner(), Error::MasterDown));
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_expire_many_maps_error_replies() {
+        let addr = spawn_mock_server(|_| {
+            Frame::Error(b"READONLY You can't write against a read only replica.".to_vec())
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let err = client
+            .expire_many(&[("a", 10), ("b", 20)])
+            .await
+            .unwrap_err();
+        assert_eq!(err.command(), Some("EXPIRE"));
+        assert!(matches!(err.inner(), Error::ReadOnly));
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_object_freq_without_lfu_policy_is_invalid_argument() {
+        let addr = spawn_mock_server(|_| {
+            Frame::Error(
+                b"ERR An LFU maxmemory policy is not selected, access frequency not tracked. \
+                  Please note that when switching between policies at runtime LRU and LFU data \
+                  will take some time to adjust."
+                    .to_vec(),
+            )
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        match client.object_freq("key").await {
+            Err(Error::InvalidArgument { message }) => {
+                assert!(
+                    message.contains("requires an LFU maxmemory-policy"),
+                    "{}",
+                    message
+                )
+            }
+            other => panic!("expected InvalidArgument, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exists_and_exists_one() {
+        let addr = spawn_mock_server(|args| {
+            let present = args[1..]
+                .iter()
+                .filter(|arg| matches!(arg, Frame::BulkString(Some(key)) if key.starts_with(b"present")))
+                .count();
+            Frame::Integer(present as i64)
+        })
+        .await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        assert_eq!(
+            client
+                .exists(&["present:1", "missing:1", "present:2"])
+                .await
+                .unwrap(),
+            2
+        );
+        assert!(client.exists_one("present:1").await.unwrap());
+        assert!(!client.exists_one("missing:1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_hgetall_pairs_round_trips_binary_fields() {
+        let addr = spawn_mock_server(|_| {
+            Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from_static(b"\xfe\xffraw"))),
+                Frame::BulkString(Some(Bytes::from("v1"))),
+                Frame::BulkString(Some(Bytes::from("plain"))),
+                Frame::BulkString(Some(Bytes::from("v2"))),
+            ])
+        })
+        .await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        let pairs = client.hgetall_pairs("myhash").await.unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                (Bytes::from_static(b"\xfe\xffraw"), Bytes::from("v1")),
+                (Bytes::from("plain"), Bytes::from("v2")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_zincr_member_blocked_by_condition() {
+        let addr = spawn_mock_server(|args| match &args[2] {
+            Frame::BulkString(Some(flag)) if flag.as_ref() == b"XX" => Frame::Null,
+            _ => Frame::BulkString(Some(Bytes::from("3.5"))),
+        })
+        .await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        let blocked = client
+            .zincr_member("zset", Bytes::from("m"), 1.0, Some(ZAddCondition::Xx))
+            .await
+            .unwrap();
+        assert_eq!(blocked, None);
+
+        let score = client
+            .zincr_member("zset", Bytes::from("m"), 1.0, Some(ZAddCondition::Nx))
+            .await
+            .unwrap();
+        assert_eq!(score, Some(3.5));
+    }
+
+    #[tokio::test]
+    async fn test_monitor_streams_lines() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = connection::Connection::new(socket);
+            let request = conn.read_frame().await.unwrap();
+            assert_eq!(
+                request,
+                Frame::Array(vec![Frame::BulkString(Some(Bytes::from("MONITOR")))])
+            );
+            for line in [
+                "OK",
+                "1339518083.107412 [0 127.0.0.1:60866] \"set\" \"a\" \"1\"",
+                "1339518087.877697 [0 127.0.0.1:60866] \"get\" \"a\"",
+            ] {
+                conn.write_frame(&Frame::SimpleString(line.as_bytes().to_vec()))
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let client = Client::connect(format!("redis://{}", addr)).await.unwrap();
+        let mut monitor = client.monitor().await.unwrap();
+        assert_eq!(
+            monitor.next().await.unwrap().unwrap(),
+            "1339518083.107412 [0 127.0.0.1:60866] \"set\" \"a\" \"1\""
+        );
+
+        let line = futures::StreamExt::next(&mut monitor)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(line.ends_with("\"get\" \"a\""));
+    }
+
+    #[tokio::test]
+    async fn test_advertise_lib_sends_setinfo() {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = received.clone();
+        let addr = spawn_mock_server(move |args| {
+            recorded.lock().unwrap().push(args.to_vec());
+            match &args[0] {
+                // Behave like a pre-7.2 server to check the error is ignored
+                Frame::BulkString(Some(name)) if name.as_ref() == b"CLIENT" => {
+                    Frame::Error(b"ERR unknown subcommand 'SETINFO'".to_vec())
+                }
+                _ => Frame::SimpleString(b"PONG".to_vec()),
+            }
+        })
+        .await;
+
+        let mut client = builder::ClientBuilder::new()
+            .address(addr)
+            .advertise_lib(true)
+            .build()
+            .await
+            .unwrap();
+        client.ping().await.unwrap();
+
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        let received = received.lock().unwrap();
+        assert_eq!(
+            received[..2],
+            [
+                vec![
+                    bulk("CLIENT"),
+                    bulk("SETINFO"),
+                    bulk("lib-name"),
+                    bulk("muxis")
+                ],
+                vec![
+                    bulk("CLIENT"),
+                    bulk("SETINFO"),
+                    bulk("lib-ver"),
+                    bulk(env!("CARGO_PKG_VERSION"))
+                ],
+            ]
+        );
+        assert_eq!(received[2], vec![bulk("PING")]);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_abandons_blocking_command() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = connection::Connection::new(socket);
+            while let Ok(Frame::Array(args)) = conn.read_frame().await {
+                let reply = match &args[0] {
+                    Frame::BulkString(Some(name)) if name.as_ref() == b"BLPOP" => {
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                        Frame::Null
+                    }
+                    _ => Frame::SimpleString(b"PONG".to_vec()),
+                };
+                if conn.write_frame(&reply).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        let mut client = Client::connect(format!("redis://{}", addr)).await.unwrap();
+        let result = timeout(Duration::from_millis(50), client.blpop(&["queue"], 0)).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+
+        // The late BLPOP reply must be discarded rather than handed to PING
+        let pong = client.ping().await.unwrap();
+        assert_eq!(pong, Bytes::from("PONG"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_dropped_request_reply_is_not_misrouted() {
+        let (client, mut server) = memory_pipe();
+        tokio::spawn(async move {
+            while let Ok(Frame::Array(mut args)) = server.read_frame().await {
+                let reply = args.remove(1);
+                if reply == Frame::BulkString(Some(Bytes::from("slow"))) {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                if server.write_frame(&reply).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        let mut client = client_over(multiplexed::MultiplexedConnection::new(client, 1024));
+        tokio::select! {
+            _ = client.echo("slow") => panic!("slow reply arrived before the deadline"),
+            _ = tokio::time::sleep(Duration::from_millis(20)) => {}
+        }
+
+        // The late "slow" reply belongs to the cancelled request and must be dropped
+        assert_eq!(client.echo("first").await.unwrap(), Bytes::from("first"));
+        assert_eq!(client.echo("second").await.unwrap(), Bytes::from("second"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_ready_resolves_once_server_answers() {
+        let pings = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counted = pings.clone();
+        let mut client = memory_mock_client(move |_| {
+            if counted.fetch_add(1, std::sync::atomic::Ordering::SeqCst) < 3 {
+                Frame::Error(b"LOADING Redis is loading the dataset in memory".to_vec())
+            } else {
+                Frame::SimpleString(b"PONG".to_vec())
+            }
+        });
+
+        let started = tokio::time::Instant::now();
+        client.wait_ready(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(pings.load(std::sync::atomic::Ordering::SeqCst), 4);
+        // Three failed polls, 50ms apart
+        assert_eq!(started.elapsed(), Duration::from_millis(150));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_wait_ready_times_out() {
+        let pings = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counted = pings.clone();
+        let mut client = memory_mock_client(move |_| {
+            counted.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Frame::Error(b"LOADING Redis is loading".to_vec())
+        });
+
+        let result = client.wait_ready(Duration::from_millis(120)).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        // Polled at 0, 50 and 100ms
+        assert_eq!(pings.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_yields_messages() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = connection::Connection::new(socket);
+            let _subscribe = conn.read_frame().await.unwrap();
+            let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+            for frame in [
+                Frame::Array(vec![bulk("subscribe"), bulk("a"), Frame::Integer(1)]),
+                Frame::Array(vec![bulk("subscribe"), bulk("b"), Frame::Integer(2)]),
+                Frame::Array(vec![bulk("message"), bulk("b"), bulk("hello")]),
+            ] {
+                conn.write_frame(&frame).await.unwrap();
+            }
+        });
+
+        let client = Client::connect(format!("redis://{}", addr)).await.unwrap();
+        let mut pubsub = client.subscribe(&["a", "b"]).await.unwrap();
+        assert_eq!(
+            pubsub.next_message().await.unwrap(),
+            Some(PubSubMessage {
+                channel: "b".to_string(),
+                payload: Bytes::from("hello"),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clone_of_subscribed_client_refuses_commands() {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let addr = spawn_mock_server(move |args| {
+            log.lock().unwrap().push(args[0].clone());
+            let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+            Frame::Array(vec![bulk("subscribe"), bulk("news"), Frame::Integer(1)])
+        })
+        .await;
+
+        let client = Client::connect(&addr).await.unwrap();
+        let mut clone = client.clone();
+        let _pubsub = client.subscribe(&["news"]).await.unwrap();
+
+        match clone.get("key").await {
+            Err(Error::InvalidArgument { message }) => assert!(message.contains("SUBSCRIBE")),
+            other => panic!("expected InvalidArgument, got {:?}", other),
+        }
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![Frame::BulkString(Some(Bytes::from("SUBSCRIBE")))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_auto_resubscribes_after_disconnect() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (subscribed_tx, mut subscribed_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+            for payload in ["before", "after"] {
+                let (socket, _) = listener.accept().await.unwrap();
+                let mut conn = connection::Connection::new(socket);
+                let subscribe = conn.read_frame().await.unwrap();
+                subscribed_tx.send(subscribe).unwrap();
+                for frame in [
+                    Frame::Array(vec![bulk("subscribe"), bulk("a"), Frame::Integer(1)]),
+                    Frame::Array(vec![bulk("subscribe"), bulk("b"), Frame::Integer(2)]),
+                    Frame::Array(vec![bulk("message"), bulk("a"), bulk(payload)]),
+                ] {
+                    conn.write_frame(&frame).await.unwrap();
+                }
+                if payload == "after" {
+                    // Keep the second connection open until the client goes away
+                    let _ = conn.read_frame().await;
+                }
+            }
+        });
+
+        let client = Client::connect(format!("redis://{}", addr)).await.unwrap();
+        let mut pubsub = client
+            .subscribe(&["a", "b"])
+            .await
+            .unwrap()
+            .with_auto_resubscribe(true);
+        let message = |payload: &str| {
+            Some(PubSubEvent::Message(PubSubMessage {
+                channel: "a".to_string(),
+                payload: Bytes::from(payload.to_string()),
+            }))
+        };
+
+        assert_eq!(pubsub.next_event().await.unwrap(), message("before"));
+        assert_eq!(
+            pubsub.next_event().await.unwrap(),
+            Some(PubSubEvent::Reconnected(ReconnectEvent {
+                channels: vec!["a".to_string(), "b".to_string()],
+            }))
+        );
+        assert_eq!(pubsub.next_event().await.unwrap(), message("after"));
+
+        let expected = command::subscribe(vec!["a".to_string(), "b".to_string()]).into_frame();
+        assert_eq!(subscribed_rx.recv().await, Some(expected.clone()));
+        assert_eq!(subscribed_rx.recv().await, Some(expected));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_ends_on_disconnect_by_default() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = connection::Connection::new(socket);
+            let _subscribe = conn.read_frame().await.unwrap();
+            let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+            conn.write_frame(&Frame::Array(vec![
+                bulk("subscribe"),
+                bulk("a"),
+                Frame::Integer(1),
+            ]))
+            .await
+            .unwrap();
+        });
+
+        let client = Client::connect(format!("redis://{}", addr)).await.unwrap();
+        let mut pubsub = client.subscribe(&["a"]).await.unwrap();
+        assert!(pubsub.next_event().await.is_err());
+        assert_eq!(pubsub.next_event().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_expire_and_ttl_single_round_trip() {
+        let addr = spawn_mock_server(|args| match args[0].as_bytes() {
+            Some(b"EXPIRE") if args[1].as_bytes() == Some(b"missing") => Frame::Integer(0),
+            Some(b"EXPIRE") => Frame::Integer(1),
+            Some(b"TTL") if args[1].as_bytes() == Some(b"missing") => Frame::Integer(-2),
+            Some(b"TTL") => Frame::Integer(60),
+            _ => Frame::Error(b"ERR unknown command".to_vec()),
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        assert_eq!(
+            client.expire_and_ttl("session", 60).await.unwrap(),
+            Some(60)
+        );
+        assert_eq!(client.expire_and_ttl("missing", 60).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_mget_map_skips_missing_keys() {
+        let addr = spawn_mock_server(|args| {
+            Frame::Array(
+                args[1..]
+                    .iter()
+                    .map(|key| match key.as_bytes() {
+                        Some(b"missing1") | Some(b"missing2") => Frame::BulkString(None),
+                        _ => key.clone(),
+                    })
+                    .collect(),
+            )
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let values = client
+            .mget_map(&["a", "missing1", "b", "missing2"])
+            .await
+            .unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values.get("a"), Some(&Bytes::from("a")));
+        assert_eq!(values.get("b"), Some(&Bytes::from("b")));
+        assert!(!values.contains_key("missing1"));
+        assert!(!values.contains_key("missing2"));
+    }
+
+    #[tokio::test]
+    async fn test_hmget_pairs_keeps_missing_fields() {
+        let addr = spawn_mock_server(|args| {
+            Frame::Array(
+                args[2..]
+                    .iter()
+                    .map(|field| match field.as_bytes() {
+                        Some(b"missing") => Frame::BulkString(None),
+                        _ => field.clone(),
+                    })
+                    .collect(),
+            )
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let pairs = client
+            .hmget_pairs("hash", &["name", "missing", "age"])
+            .await
+            .unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                ("name".to_string(), Some(Bytes::from("name"))),
+                ("missing".to_string(), None),
+                ("age".to_string(), Some(Bytes::from("age"))),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hscan_keys_maps_novalues_rejection() {
+        let novalues = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let supported = novalues.clone();
+        let addr = spawn_mock_server(move |args| {
+            assert_eq!(
+                args.last().and_then(Frame::as_bytes),
+                Some(&b"NOVALUES"[..])
+            );
+            if !supported.load(std::sync::atomic::Ordering::SeqCst) {
+                return Frame::Error(b"ERR syntax error".to_vec());
+            }
+            Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from("0"))),
+                Frame::Array(vec![
+                    Frame::BulkString(Some(Bytes::from("f1"))),
+                    Frame::BulkString(Some(Bytes::from("f2"))),
+                ]),
+            ])
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        assert_eq!(
+            client.hscan_keys("hash", 0).await.unwrap(),
+            (0, vec!["f1".to_string(), "f2".to_string()])
+        );
+
+        novalues.store(false, std::sync::atomic::Ordering::SeqCst);
+        match client.hscan_keys("hash", 0).await {
+            Err(Error::InvalidArgument { message }) => assert!(message.contains("7.4")),
+            other => panic!("expected InvalidArgument, got {:?}", other),
+        }
+    }
+
+    /// Mock server delaying every reply, so requests pile up in the queue.
+    async fn spawn_slow_server(delay: Duration) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = connection::Connection::new(socket);
+            while let Ok(Frame::Array(_)) = conn.read_frame().await {
+                tokio::time::sleep(delay).await;
+                let pong = Frame::SimpleString(b"PONG".to_vec());
+                if conn.write_frame(&pong).await.is_err() {
+                    return;
+                }
+            }
+        });
+        format!("redis://{}", addr)
+    }
+
+    async fn hammer(client: &Client, count: usize) -> Vec<Result<Bytes>> {
+        let tasks: Vec<_> = (0..count)
+            .map(|_| {
+                let mut client = client.clone();
+                tokio::spawn(async move { client.ping().await })
+            })
+            .collect();
+        let mut results = Vec::with_capacity(count);
+        for task in tasks {
+            results.push(task.await.unwrap());
+        }
+        results
+    }
+
+    #[tokio::test]
+    async fn test_tiny_queue_applies_backpressure_by_default() {
+        let addr = spawn_slow_server(Duration::from_millis(5)).await;
+        let client = crate::ClientBuilder::new()
+            .address(addr)
+            .queue_size(1)
+            .build()
+            .await
+            .unwrap();
+
+        let results = hammer(&client, 20).await;
+        assert!(results.iter().all(|r| r.is_ok()));
+
+        let metrics = client.metrics();
+        assert_eq!(metrics.total_commands, 20);
+        assert_eq!(metrics.total_errors, 0);
+        assert!(metrics.max_in_flight_seen >= 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_pings_idle_connection() {
+        let pings = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let seen = pings.clone();
+        let connection = memory_mock_connection(move |args| {
+            if args.first().and_then(Frame::as_bytes) == Some(b"PING") {
+                seen.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            }
+            Frame::SimpleString(b"PONG".to_vec())
+        });
+        let mut client = client_over(connection.with_keepalive(Some(Duration::from_millis(30))));
+
+        // One PING every 30ms while idle
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(pings.load(std::sync::atomic::Ordering::Relaxed), 6);
+        assert_eq!(client.ping().await.unwrap(), Bytes::from("PONG"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_timeout_fails_connection() {
+        let (connection, mut server) = memory_pipe();
+        tokio::spawn(async move {
+            while let Ok(Frame::Array(_)) = server.read_frame().await {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                let pong = Frame::SimpleString(b"PONG".to_vec());
+                if server.write_frame(&pong).await.is_err() {
+                    return;
+                }
+            }
+        });
+        let connection = multiplexed::MultiplexedConnection::new(connection, 1024)
+            .with_keepalive(Some(Duration::from_millis(30)));
+        let mut client = client_over(connection);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let err = tokio::time::timeout(Duration::from_secs(1), client.ping())
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(err, Error::Io { .. }), "{err:?}");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_timeout_fails_pending_commands() {
+        let (connection, mut server) = memory_pipe();
+        let (pinged_tx, pinged_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            // Answer the first command, then go silent once the keepalive PING arrives
+            server.read_frame().await.unwrap();
+            server
+                .write_frame(&Frame::SimpleString(b"PONG".to_vec()))
+                .await
+                .unwrap();
+            server.read_frame().await.unwrap();
+            pinged_tx.send(()).unwrap();
+            while server.read_frame().await.is_ok() {}
+        });
+        let connection = multiplexed::MultiplexedConnection::new(connection, 1024)
+            .with_keepalive(Some(Duration::from_millis(30)));
+        let mut client = client_over(connection);
+        client.ping().await.unwrap();
+
+        // Queue a command behind the unanswered keepalive PING, sent at 30ms
+        pinged_rx.await.unwrap();
+        let started = tokio::time::Instant::now();
+        let err = tokio::time::timeout(Duration::from_secs(1), client.ping())
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(err, Error::Io { .. }), "{err:?}");
+        // Failed at the PING deadline rather than waiting for its own reply
+        assert_eq!(started.elapsed(), Duration::from_millis(30));
+    }
+
+    #[tokio::test]
+    async fn test_tiny_queue_without_backpressure_returns_queue_full() {
+        let addr = spawn_slow_server(Duration::from_millis(20)).await;
+        let client = crate::ClientBuilder::new()
+            .address(addr)
+            .queue_size(1)
+            .backpressure(false)
+            .build()
+            .await
+            .unwrap();
+
+        let results = hammer(&client, 20).await;
+        let full = results
+            .iter()
+            .filter(|r| matches!(r, Err(Error::QueueFull)))
+            .count();
+        let ok = results.iter().filter(|r| r.is_ok()).count();
+        assert!(full > 0, "expected some QueueFull errors");
+        assert_eq!(full + ok, 20);
+
+        let metrics = client.metrics();
+        assert_eq!(metrics.total_commands, ok as u64);
+        assert_eq!(metrics.total_errors, full as u64);
+    }
+
+    #[tokio::test]
+    async fn test_strict_types_fails_fast_on_cached_string_key() {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let addr = spawn_mock_server(move |args| {
+            log.lock().unwrap().push(args[0].clone());
+            match args[0].as_bytes() {
+                Some(b"SET") => Frame::SimpleString(b"OK".to_vec()),
+                Some(b"DEL") => Frame::Integer(1),
+                _ => Frame::Integer(1),
+            }
+        })
+        .await;
+        let mut client = crate::ClientBuilder::new()
+            .address(addr)
+            .strict_types(true)
+            .build()
+            .await
+            .unwrap();
+
+        client.set("greeting", Bytes::from("hi")).await.unwrap();
+        match client.lpush("greeting", &[Bytes::from("x")]).await {
+            Err(Error::WrongType { key, actual }) => {
+                assert_eq!(key, "greeting");
+                assert_eq!(actual, "string");
+            }
+            other => panic!("expected WrongType, got {:?}", other),
+        }
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        assert_eq!(*received.lock().unwrap(), vec![bulk("SET")]);
+
+        // Deleting the key forgets its type
+        client.del("greeting").await.unwrap();
+        assert_eq!(
+            client.lpush("greeting", &[Bytes::from("x")]).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![bulk("SET"), bulk("DEL"), bulk("LPUSH")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strict_types_forgets_keys_after_getdel_and_reset() {
+        let addr = spawn_mock_server(|args| match args[0].as_bytes() {
+            Some(b"SET") => Frame::SimpleString(b"OK".to_vec()),
+            Some(b"GETDEL") => Frame::BulkString(Some(Bytes::from("hi"))),
+            Some(b"RESET") => Frame::SimpleString(b"RESET".to_vec()),
+            _ => Frame::Integer(1),
+        })
+        .await;
+        let mut client = crate::ClientBuilder::new()
+            .address(addr)
+            .strict_types(true)
+            .build()
+            .await
+            .unwrap();
+
+        client.set("greeting", Bytes::from("hi")).await.unwrap();
+        assert_eq!(
+            client.getdel("greeting").await.unwrap(),
+            Some(Bytes::from("hi"))
+        );
+        assert_eq!(
+            client.lpush("greeting", &[Bytes::from("x")]).await.unwrap(),
+            1
+        );
+
+        // RESET switches back to database 0, where the cached types do not apply
+        client.set("counter", Bytes::from("1")).await.unwrap();
+        client.reset().await.unwrap();
+        assert_eq!(
+            client.lpush("counter", &[Bytes::from("x")]).await.unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strict_types_disabled_by_default() {
+        let addr = spawn_mock_server(|args| match args[0].as_bytes() {
+            Some(b"SET") => Frame::SimpleString(b"OK".to_vec()),
+            _ => Frame::Error(
+                b"WRONGTYPE Operation against a key holding the wrong kind of value".to_vec(),
+            ),
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        client.set("greeting", Bytes::from("hi")).await.unwrap();
+        let error = client
+            .lpush("greeting", &[Bytes::from("x")])
+            .await
+            .unwrap_err();
+        assert_eq!(error.command(), Some("LPUSH"));
+    }
+
+    #[tokio::test]
+    async fn test_peer_addr_matches_listener() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepted = tokio::spawn(async move { listener.accept().await.unwrap() });
+
+        let client = Client::connect(format!("redis://{}", addr)).await.unwrap();
+        let (_socket, client_addr) = accepted.await.unwrap();
+        assert_eq!(client.peer_addr(), Some(addr));
+        assert_eq!(client.local_addr(), Some(client_addr));
+    }
+
+    #[tokio::test]
+    async fn test_validate_arity_rejects_short_commands() {
+        let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let addr = spawn_mock_server(move |args| {
+            log.lock().unwrap().push(args[0].clone());
+            Frame::SimpleString(b"OK".to_vec())
+        })
+        .await;
+        let mut client = crate::ClientBuilder::new()
+            .address(addr)
+            .validate_arity(true)
+            .build()
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            client.send_command(command::Cmd::new("GET")).await,
+            Err(Error::InvalidArgument { .. })
+        ));
+        assert!(matches!(
+            client
+                .send_command(command::Cmd::new("zadd").arg("z").arg("1"))
+                .await,
+            Err(Error::InvalidArgument { .. })
+        ));
+        assert!(received.lock().unwrap().is_empty());
+
+        client
+            .send_command(command::Cmd::new("GET").arg("key"))
+            .await
+            .unwrap();
+        client
+            .send_command(command::Cmd::new("MYMODULE.CMD"))
+            .await
+            .unwrap();
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_arity_not_validated_by_default() {
+        let addr = spawn_mock_server(|_| {
+            Frame::Error(b"ERR wrong number of arguments for 'get' command".to_vec())
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let err = client
+            .send_command(command::Cmd::new("GET"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.command(), Some("GET"));
     }
 
-    /// Returns members in a sorted set within a score range (ZRANGEBYSCORE).
-    pub async fn zrangebyscore(&mut self, key: &str, min: &str, max: &str) -> Result<Vec<String>> {
-        let cmd = command::zrangebyscore(key.to_string(), min.to_string(), max.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_string(frame)
+    #[tokio::test]
+    async fn test_zunion_withscores_pairs_members() {
+        let addr = spawn_mock_server(|args| {
+            let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+            assert_eq!(args[1], bulk("2"));
+            Frame::Array(vec![bulk("a"), bulk("1"), bulk("b"), bulk("5.5")])
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        assert_eq!(
+            client.zunion_withscores(&["z1", "z2"]).await.unwrap(),
+            vec![("a".to_string(), 1.0), ("b".to_string(), 5.5)]
+        );
+        assert!(matches!(
+            client
+                .zinter_weighted(&["z1", "z2"], &[1.0], Aggregate::Sum)
+                .await,
+            Err(Error::InvalidArgument { .. })
+        ));
     }
 
-    /// Returns the rank of a member in a sorted set (ZRANK).
-    pub async fn zrank(&mut self, key: &str, member: Bytes) -> Result<Option<i64>> {
-        let cmd = command::zrank(key.to_string(), member);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_optional_int(frame)
+    #[tokio::test]
+    async fn test_transaction_retries_after_watch_conflict() {
+        let execs = std::sync::atomic::AtomicUsize::new(0);
+        let addr = spawn_mock_server(move |args| {
+            match args[0].as_bytes().unwrap() {
+                b"WATCH" | b"MULTI" => Frame::SimpleString(b"OK".to_vec()),
+                b"EXEC" => {
+                    if execs.fetch_add(1, std::sync::atomic::Ordering::Relaxed) == 0 {
+                        // Another client touched the watched key
+                        Frame::Null
+                    } else {
+                        Frame::Array(vec![Frame::Integer(1)])
+                    }
+                }
+                _ => Frame::SimpleString(b"QUEUED".to_vec()),
+            }
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let mut runs = 0;
+        let replies = client
+            .transaction(&["counter"], |txn| {
+                runs += 1;
+                txn.incr("counter");
+            })
+            .await
+            .unwrap();
+        assert_eq!(runs, 2);
+        assert_eq!(replies, vec![Frame::Integer(1)]);
     }
 
-    /// Returns the score of a member in a sorted set (ZSCORE).
-    pub async fn zscore(&mut self, key: &str, member: Bytes) -> Result<Option<f64>> {
-        let cmd = command::zscore(key.to_string(), member);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_optional_float(frame)
+    #[tokio::test]
+    async fn test_transaction_gives_up_after_repeated_conflicts() {
+        let addr = spawn_mock_server(|args| match args[0].as_bytes().unwrap() {
+            b"EXEC" => Frame::Null,
+            b"WATCH" | b"MULTI" => Frame::SimpleString(b"OK".to_vec()),
+            _ => Frame::SimpleString(b"QUEUED".to_vec()),
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let err = client
+            .transaction(&["counter"], |txn| {
+                txn.incr("counter");
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::TransactionAborted {
+                attempts: TRANSACTION_ATTEMPTS
+            }
+        ));
     }
 
-    /// Returns the cardinality of a sorted set (ZCARD).
-    pub async fn zcard(&mut self, key: &str) -> Result<i64> {
-        let cmd = command::zcard(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    #[tokio::test]
+    async fn test_del_pattern_unlinks_each_scan_page() {
+        let unlinked = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = unlinked.clone();
+        let addr = spawn_mock_server(move |args| {
+            let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+            match args[0].as_bytes() {
+                Some(b"SCAN") => {
+                    assert_eq!(args[3], bulk("tmp:*"));
+                    let (next, keys) = match args[1].as_bytes() {
+                        Some(b"0") => ("12", vec![bulk("tmp:1"), bulk("tmp:2")]),
+                        _ => ("0", vec![bulk("tmp:3")]),
+                    };
+                    Frame::Array(vec![bulk(next), Frame::Array(keys)])
+                }
+                Some(b"UNLINK") => {
+                    let keys: Vec<Frame> = args[1..].to_vec();
+                    let n = keys.len() as i64;
+                    log.lock().unwrap().push(keys);
+                    Frame::Integer(n)
+                }
+                _ => Frame::Error(b"ERR unexpected command".to_vec()),
+            }
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        assert_eq!(client.del_pattern("tmp:*").await.unwrap(), 3);
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        assert_eq!(
+            *unlinked.lock().unwrap(),
+            vec![vec![bulk("tmp:1"), bulk("tmp:2")], vec![bulk("tmp:3")]]
+        );
     }
 
-    /// Returns the count of members within a score range (ZCOUNT).
-    pub async fn zcount(&mut self, key: &str, min: &str, max: &str) -> Result<i64> {
-        let cmd = command::zcount(key.to_string(), min.to_string(), max.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    #[tokio::test]
+    async fn test_count_keys_stops_at_limit_or_end_of_scan() {
+        let scans = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let calls = scans.clone();
+        let addr = spawn_mock_server(move |args| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+            assert_eq!(args[3], bulk("user:*"));
+            let (next, keys) = match args[1].as_bytes() {
+                Some(b"0") => ("7", vec![bulk("user:1"), bulk("user:2")]),
+                Some(b"7") => ("9", vec![]),
+                _ => ("0", vec![bulk("user:3")]),
+            };
+            Frame::Array(vec![bulk(next), Frame::Array(keys)])
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        assert_eq!(client.count_keys("user:*", 10).await.unwrap(), 3);
+        assert_eq!(scans.swap(0, std::sync::atomic::Ordering::SeqCst), 3);
+
+        assert_eq!(client.count_keys("user:*", 2).await.unwrap(), 2);
+        assert_eq!(scans.swap(0, std::sync::atomic::Ordering::SeqCst), 2);
+
+        assert_eq!(client.count_keys("user:*", 0).await.unwrap(), 0);
+        assert_eq!(scans.load(std::sync::atomic::Ordering::SeqCst), 0);
     }
 
-    /// Increments the score of a member in a sorted set (ZINCRBY).
-    pub async fn zincrby(&mut self, key: &str, increment: f64, member: Bytes) -> Result<f64> {
-        let cmd = command::zincrby(key.to_string(), increment, member);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_float(frame)
+    #[tokio::test]
+    async fn test_bit_unit_rejected_by_old_server() {
+        let addr = spawn_mock_server(|args| {
+            if args.len() == 5 {
+                Frame::Error(b"ERR syntax error".to_vec())
+            } else {
+                Frame::Integer(-1)
+            }
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        match client
+            .bitcount("bits", Some((0, 0)), Some(BitUnit::Bit))
+            .await
+        {
+            Err(Error::InvalidArgument { message }) => assert!(message.contains("7.0")),
+            other => panic!("expected InvalidArgument, got {:?}", other),
+        }
+        assert!(matches!(
+            client.bitpos("bits", true, None, Some(BitUnit::Bit)).await,
+            Err(Error::InvalidArgument { .. })
+        ));
+        assert_eq!(client.bitpos("bits", true, None, None).await.unwrap(), None);
     }
 
-    /// Returns a range of members in reverse order (ZREVRANGE).
-    pub async fn zrevrange(&mut self, key: &str, start: i64, stop: i64) -> Result<Vec<String>> {
-        let cmd = command::zrevrange(key.to_string(), start, stop);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_string(frame)
+    #[tokio::test]
+    async fn test_syntax_error_without_bit_unit_is_not_a_version_error() {
+        let addr = spawn_mock_server(|_| Frame::Error(b"ERR syntax error".to_vec())).await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let error = client
+            .bitcount("bits", Some((0, 0)), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(error.inner(), Error::Server { .. }), "{error:?}");
+        let error = client
+            .bitpos("bits", true, Some((0, 0)), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(error.inner(), Error::Server { .. }), "{error:?}");
     }
 
-    /// Returns the reverse rank of a member (ZREVRANK).
-    pub async fn zrevrank(&mut self, key: &str, member: Bytes) -> Result<Option<i64>> {
-        let cmd = command::zrevrank(key.to_string(), member);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_optional_int(frame)
+    #[tokio::test]
+    async fn test_server_error_names_failed_command() {
+        let addr = spawn_mock_server(|args| match args[0].as_bytes() {
+            Some(b"SET") | Some(b"INCR") => Frame::Error(b"OOM command not allowed".to_vec()),
+            _ => Frame::BulkString(None),
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let error = client.set("key", Bytes::from("value")).await.unwrap_err();
+        assert_eq!(error.command(), Some("SET"));
+        assert!(error.to_string().contains("SET"));
+        assert!(
+            matches!(error.inner(), Error::Server { message } if message == "OOM command not allowed")
+        );
+
+        // Commands built as frames are named too
+        let error = client.incr("key").await.unwrap_err();
+        assert_eq!(error.command(), Some("INCR"));
+        assert_eq!(client.get("key").await.unwrap(), None);
     }
 
-    /// Removes members by rank range (ZREMRANGEBYRANK).
-    pub async fn zremrangebyrank(&mut self, key: &str, start: i64, stop: i64) -> Result<i64> {
-        let cmd = command::zremrangebyrank(key.to_string(), start, stop);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    /// Mock server answering LRANGE with `len` one-byte elements, written in
+    /// chunks so the client decodes them while the reply is still arriving, and
+    /// any other command with PONG.
+    async fn spawn_large_lrange_server(len: usize) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (mut reader, mut writer) = connection::Connection::new(socket).split();
+            while let Ok(Frame::Array(args)) = reader.read_frame().await {
+                if args[0].as_bytes() != Some(b"LRANGE") {
+                    writer.write_encoded(b"+PONG\r\n").await.unwrap();
+                    writer.flush().await.unwrap();
+                    continue;
+                }
+                writer
+                    .write_encoded(format!("*{}\r\n", len).as_bytes())
+                    .await
+                    .unwrap();
+                for chunk in (0..len).collect::<Vec<_>>().chunks(1000) {
+                    let data: Vec<u8> = chunk
+                        .iter()
+                        .flat_map(|i| format!("$1\r\n{}\r\n", i % 10).into_bytes())
+                        .collect();
+                    writer.write_encoded(&data).await.unwrap();
+                    writer.flush().await.unwrap();
+                }
+            }
+        });
+        format!("redis://{}", addr)
     }
 
-    /// Removes members by score range (ZREMRANGEBYSCORE).
-    pub async fn zremrangebyscore(&mut self, key: &str, min: &str, max: &str) -> Result<i64> {
-        let cmd = command::zremrangebyscore(key.to_string(), min.to_string(), max.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    #[tokio::test]
+    async fn test_lrange_stream_counts_large_reply() {
+        let addr = spawn_large_lrange_server(100_000).await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let mut elements = Box::pin(client.lrange_stream("biglist", 0, -1));
+        let mut count = 0;
+        while let Some(element) = elements.next().await {
+            assert_eq!(element.unwrap(), Bytes::from((count % 10).to_string()));
+            count += 1;
+        }
+        assert_eq!(count, 100_000);
+        assert_eq!(client.ping().await.unwrap(), Bytes::from("PONG"));
     }
 
-    /// Removes and returns the member with the lowest score (ZPOPMIN).
-    pub async fn zpopmin(&mut self, key: &str) -> Result<Option<(String, f64)>> {
-        let cmd = command::zpopmin(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_zpop_result(frame)
+    #[tokio::test]
+    async fn test_lrange_stream_dropped_early_keeps_connection_usable() {
+        let addr = spawn_large_lrange_server(10_000).await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let first: Vec<_> = client
+            .lrange_stream("biglist", 0, -1)
+            .take(2)
+            .collect()
+            .await;
+        assert_eq!(first.len(), 2);
+        assert_eq!(client.ping().await.unwrap(), Bytes::from("PONG"));
     }
 
-    /// Removes and returns the member with the highest score (ZPOPMAX).
-    pub async fn zpopmax(&mut self, key: &str) -> Result<Option<(String, f64)>> {
-        let cmd = command::zpopmax(key.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_zpop_result(frame)
+    /// Mock server with a script cache: SCRIPT LOAD caches `return <n>` under
+    /// the digest `sha-<n>` and EVALSHA replies `<n>` plus the number of keys.
+    fn script_server(
+        cached: Arc<std::sync::Mutex<Vec<String>>>,
+        loads: Arc<std::sync::atomic::AtomicUsize>,
+    ) -> impl Fn(&[Frame]) -> Frame + Send + 'static {
+        move |args| match args[0].as_bytes() {
+            Some(b"SCRIPT") => {
+                loads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let code = args[2].as_str().unwrap_or_default().into_owned();
+                let sha = code.replace("return ", "sha-");
+                cached.lock().unwrap().push(sha.clone());
+                Frame::BulkString(Some(Bytes::from(sha)))
+            }
+            Some(b"EVALSHA") => {
+                let sha = args[1].as_str().unwrap_or_default().into_owned();
+                if !cached.lock().unwrap().contains(&sha) {
+                    return Frame::Error(b"NOSCRIPT No matching script".to_vec());
+                }
+                let n: i64 = sha.trim_start_matches("sha-").parse().unwrap();
+                let numkeys: i64 = args[2].as_str().unwrap().parse().unwrap();
+                Frame::Integer(n * 10 + numkeys)
+            }
+            _ => Frame::Error(b"ERR unknown command".to_vec()),
+        }
     }
 
-    /// Blocking ZPOPMIN (BZPOPMIN).
-    pub async fn bzpopmin(
-        &mut self,
-        keys: &[&str],
-        timeout: u64,
-    ) -> Result<Option<(String, String, f64)>> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::bzpopmin(keys_vec, timeout);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bzpop_result(frame)
+    #[tokio::test]
+    async fn test_script_invoke_loads_once() {
+        let cached = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let loads = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let addr = spawn_mock_server(script_server(cached, loads.clone())).await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let script = Script::new("return 7");
+        assert_eq!(script.hash(), None);
+        for _ in 0..3 {
+            let reply = script
+                .invoke(&mut client, &["k1", "k2"], &[Bytes::from("arg")])
+                .await
+                .unwrap();
+            assert_eq!(reply, Frame::Integer(72));
+        }
+        assert_eq!(script.hash(), Some("sha-7"));
+        assert_eq!(loads.load(std::sync::atomic::Ordering::SeqCst), 1);
     }
 
-    /// Blocking ZPOPMAX (BZPOPMAX).
-    pub async fn bzpopmax(
-        &mut self,
-        keys: &[&str],
-        timeout: u64,
-    ) -> Result<Option<(String, String, f64)>> {
-        let keys_vec = keys.iter().map(|k| k.to_string()).collect();
-        let cmd = command::bzpopmax(keys_vec, timeout);
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_bzpop_result(frame)
+    #[tokio::test]
+    async fn test_script_invoke_reloads_on_noscript() {
+        let cached = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let loads = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let addr = spawn_mock_server(script_server(cached.clone(), loads.clone())).await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let script = Script::new("return 4");
+        assert_eq!(
+            script.invoke(&mut client, &[], &[]).await.unwrap(),
+            Frame::Integer(40)
+        );
+
+        // Simulate SCRIPT FLUSH on the server
+        cached.lock().unwrap().clear();
+        assert_eq!(
+            script.invoke(&mut client, &["k"], &[]).await.unwrap(),
+            Frame::Integer(41)
+        );
+        assert_eq!(loads.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // Plain EVALSHA surfaces NOSCRIPT
+        match client.evalsha("sha-9", &[], &[]).await {
+            Err(e) => match e.inner() {
+                Error::Server { message } => assert!(message.starts_with("NOSCRIPT")),
+                other => panic!("expected NOSCRIPT, got {:?}", other),
+            },
+            other => panic!("expected NOSCRIPT, got {:?}", other),
+        }
     }
 
-    /// Returns count of members between lexicographical range (ZLEXCOUNT).
-    pub async fn zlexcount(&mut self, key: &str, min: &str, max: &str) -> Result<i64> {
-        let cmd = command::zlexcount(key.to_string(), min.to_string(), max.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    #[tokio::test]
+    async fn test_compare_and_set_swaps_only_on_match() {
+        let value = Arc::new(std::sync::Mutex::new(Bytes::from("old")));
+        let stored = value.clone();
+        let addr = spawn_mock_server(move |args| match args[0].as_bytes() {
+            Some(b"SCRIPT") => Frame::BulkString(Some(Bytes::from("cas-sha"))),
+            Some(b"EVALSHA") => {
+                assert_eq!(args[1].as_bytes(), Some(&b"cas-sha"[..]));
+                assert_eq!(args[3].as_bytes(), Some(&b"state"[..]));
+                let mut current = stored.lock().unwrap();
+                if args[4].as_bytes() == Some(&current[..]) {
+                    *current = Bytes::copy_from_slice(args[5].as_bytes().unwrap());
+                    Frame::Integer(1)
+                } else {
+                    Frame::Integer(0)
+                }
+            }
+            _ => Frame::Error(b"ERR unknown command".to_vec()),
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        assert!(client
+            .compare_and_set("state", Bytes::from("old"), Bytes::from("new"))
+            .await
+            .unwrap());
+        assert_eq!(*value.lock().unwrap(), Bytes::from("new"));
+
+        assert!(!client
+            .compare_and_set("state", Bytes::from("old"), Bytes::from("newer"))
+            .await
+            .unwrap());
+        assert_eq!(*value.lock().unwrap(), Bytes::from("new"));
     }
 
-    /// Returns members between lexicographical range (ZRANGEBYLEX).
-    pub async fn zrangebylex(&mut self, key: &str, min: &str, max: &str) -> Result<Vec<String>> {
-        let cmd = command::zrangebylex(key.to_string(), min.to_string(), max.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_vec_string(frame)
+    #[tokio::test]
+    async fn test_append_capped_trims_to_tail() {
+        let value = Arc::new(std::sync::Mutex::new(Vec::<u8>::new()));
+        let stored = value.clone();
+        let addr = spawn_mock_server(move |args| match args[0].as_bytes() {
+            Some(b"SCRIPT") => Frame::BulkString(Some(Bytes::from("append-sha"))),
+            Some(b"EVALSHA") => {
+                assert_eq!(args[3].as_bytes(), Some(&b"log"[..]));
+                let max: usize = args[5].as_str().unwrap().parse().unwrap();
+                let mut current = stored.lock().unwrap();
+                current.extend_from_slice(args[4].as_bytes().unwrap());
+                if current.len() > max {
+                    let excess = current.len() - max;
+                    current.drain(..excess);
+                }
+                Frame::Integer(current.len() as i64)
+            }
+            _ => Frame::Error(b"ERR unknown command".to_vec()),
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let len = client
+            .append_capped("log", Bytes::from("abc"), 5)
+            .await
+            .unwrap();
+        assert_eq!(len, 3);
+        assert_eq!(*value.lock().unwrap(), b"abc");
+
+        let len = client
+            .append_capped("log", Bytes::from("defg"), 5)
+            .await
+            .unwrap();
+        assert_eq!(len, 5);
+        assert_eq!(*value.lock().unwrap(), b"cdefg");
+
+        assert!(matches!(
+            client.append_capped("log", Bytes::from("x"), 0).await,
+            Err(Error::InvalidArgument { .. })
+        ));
     }
 
-    /// Removes members between lexicographical range (ZREMRANGEBYLEX).
-    pub async fn zremrangebylex(&mut self, key: &str, min: &str, max: &str) -> Result<i64> {
-        let cmd = command::zremrangebylex(key.to_string(), min.to_string(), max.to_string());
-        let frame = self.connection.send_command(cmd.into_frame()).await?;
-        command::frame_to_int(frame)
+    #[tokio::test]
+    async fn test_rate_limit_incr_expires_on_first_hit() {
+        let count = Arc::new(std::sync::atomic::AtomicI64::new(0));
+        let expiries = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let (hits, log) = (count.clone(), expiries.clone());
+        let addr = spawn_mock_server(move |args| match args[0].as_bytes() {
+            Some(b"SCRIPT") => {
+                let code = args[2].as_str().unwrap();
+                assert!(code.contains("INCR") && code.contains("PEXPIRE"));
+                Frame::BulkString(Some(Bytes::from("ratelimit-sha")))
+            }
+            Some(b"EVALSHA") => {
+                assert_eq!(args[3].as_bytes(), Some(&b"ratelimit:alice"[..]));
+                let count = hits.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                if count == 1 {
+                    log.lock()
+                        .unwrap()
+                        .push(args[4].as_str().unwrap().into_owned());
+                }
+                Frame::Integer(count)
+            }
+            _ => Frame::Error(b"ERR unknown command".to_vec()),
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let window = Duration::from_millis(1500);
+        assert_eq!(
+            client
+                .rate_limit_incr("ratelimit:alice", window)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(*expiries.lock().unwrap(), vec!["1500".to_string()]);
+
+        assert_eq!(
+            client
+                .rate_limit_incr("ratelimit:alice", window)
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            client
+                .rate_limit_incr("ratelimit:alice", window)
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(expiries.lock().unwrap().len(), 1);
+
+        assert!(matches!(
+            client
+                .rate_limit_incr("ratelimit:alice", Duration::ZERO)
+                .await,
+            Err(Error::InvalidArgument { .. })
+        ));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Records the fields of every span and event created while it is the default
+    /// subscriber.
+    struct SpanRecorder(Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl tracing::Subscriber for SpanRecorder {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut fields = String::new();
+            span.record(
+                &mut |field: &tracing::field::Field, value: &dyn std::fmt::Debug| {
+                    fields.push_str(&format!("{}={:?} ", field, value));
+                },
+            );
+            let mut spans = self.0.lock().unwrap();
+            spans.push(fields);
+            tracing::span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            let mut fields = String::new();
+            event.record(
+                &mut |field: &tracing::field::Field, value: &dyn std::fmt::Debug| {
+                    fields.push_str(&format!("{}={:?} ", field, value));
+                },
+            );
+            self.0.lock().unwrap().push(fields);
+        }
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[tokio::test]
+    async fn test_spans_redact_sensitive_arguments() {
+        let addr = spawn_mock_server(|args| match args[0].as_bytes() {
+            Some(b"EXEC") => Frame::Array(vec![Frame::SimpleString(b"OK".to_vec())]),
+            Some(b"MULTI") => Frame::SimpleString(b"OK".to_vec()),
+            Some(b"CONFIG") if args.len() == 4 => Frame::SimpleString(b"QUEUED".to_vec()),
+            _ => Frame::BulkString(Some(Bytes::from("OK"))),
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let spans = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let _guard = tracing::subscriber::set_default(SpanRecorder(spans.clone()));
+
+        client.auth("hunter2").await.unwrap();
+        client
+            .send_command(
+                command::Cmd::new("CONFIG")
+                    .arg("SET")
+                    .arg("requirepass")
+                    .arg("s3cret"),
+            )
+            .await
+            .unwrap();
+        client.get("user:1").await.unwrap();
+        client
+            .transaction(&[], |txn| {
+                txn.add(
+                    command::Cmd::new("CONFIG")
+                        .arg("SET")
+                        .arg("requirepass")
+                        .arg("s3cret"),
+                );
+            })
+            .await
+            .unwrap();
+
+        let spans = spans.lock().unwrap().join("\n");
+        assert!(spans.contains("AUTH <redacted>"), "{}", spans);
+        assert!(spans.contains("CONFIG <redacted>"), "{}", spans);
+        assert!(!spans.contains("hunter2"), "{}", spans);
+        assert!(!spans.contains("s3cret"), "{}", spans);
+        assert!(!spans.contains("requirepass"), "{}", spans);
+        assert!(spans.contains("user:1"), "{}", spans);
+    }
+
+    #[tokio::test]
+    async fn test_function_load_and_fcall_round_trip() {
+        let libraries = Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let loaded = libraries.clone();
+        let addr = spawn_mock_server(move |args| match args[0].as_bytes() {
+            Some(b"FUNCTION") => {
+                let replace = args[2].as_bytes() == Some(b"REPLACE");
+                let code = args.last().and_then(Frame::as_str).unwrap_or_default();
+                let name = code
+                    .strip_prefix("#!lua name=")
+                    .and_then(|rest| rest.split_whitespace().next())
+                    .unwrap_or_default()
+                    .to_string();
+                let mut loaded = loaded.lock().unwrap();
+                if loaded.contains(&name) && !replace {
+                    return Frame::Error(b"ERR Library 'mylib' already exists".to_vec());
+                }
+                loaded.push(name.clone());
+                Frame::BulkString(Some(Bytes::from(name)))
+            }
+            Some(b"FCALL") | Some(b"FCALL_RO") => {
+                let mut reply = vec![args[0].clone()];
+                reply.extend_from_slice(&args[1..]);
+                Frame::Array(reply)
+            }
+            _ => Frame::Error(b"ERR unknown command".to_vec()),
+        })
+        .await;
+        let mut client = Client::connect(&addr).await.unwrap();
+
+        let code = "#!lua name=mylib\nredis.register_function('echo', function() end)";
+        assert_eq!(client.function_load(code, false).await.unwrap(), "mylib");
+        let error = client.function_load(code, false).await.unwrap_err();
+        assert_eq!(error.command(), Some("FUNCTION"));
+        assert!(matches!(error.inner(), Error::Server { .. }));
+        assert_eq!(client.function_load(code, true).await.unwrap(), "mylib");
+
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        assert_eq!(
+            client
+                .fcall("echo", &["k"], &[Bytes::from("v")])
+                .await
+                .unwrap(),
+            Frame::Array(vec![
+                bulk("FCALL"),
+                bulk("echo"),
+                bulk("1"),
+                bulk("k"),
+                bulk("v")
+            ])
+        );
+        assert_eq!(
+            client.fcall_ro("echo", &[], &[]).await.unwrap(),
+            Frame::Array(vec![bulk("FCALL_RO"), bulk("echo"), bulk("0")])
+        );
+    }
+
+    #[test]
+    fn test_scheme_uses_tls() {
+        assert!(!scheme_uses_tls("redis").unwrap());
+        assert!(scheme_uses_tls("rediss").unwrap());
+        assert!(matches!(
+            scheme_uses_tls("http"),
+            Err(Error::InvalidArgument { .. })
+        ));
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    fn test_scheme_redis_tls_alias() {
+        assert!(scheme_uses_tls("redis+tls").unwrap());
+
+        let parsed = url::Url::parse("redis+tls://cache.example.com:6380").unwrap();
+        assert!(scheme_uses_tls(parsed.scheme()).unwrap());
+        assert_eq!(parsed.host_str(), Some("cache.example.com"));
+        assert_eq!(parsed.port(), Some(6380));
+    }
+
+    #[cfg(feature = "tls")]
+    #[tokio::test]
+    async fn test_invalid_tls_server_name_rejected() {
+        match Client::connect("rediss://-bad..example:6379").await {
+            Err(Error::Tls { message }) => assert!(message.contains("invalid server name")),
+            other => panic!("expected Error::Tls, got {:?}", other),
+        }
+    }
+
+    #[cfg(not(feature = "tls"))]
+    #[tokio::test]
+    async fn test_scheme_redis_tls_requires_feature() {
+        match Client::connect("redis+tls://127.0.0.1:6379").await {
+            Err(Error::InvalidArgument { message }) => assert!(message.contains("tls")),
+            other => panic!("expected InvalidArgument, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_cmd_round_trip() {
+        let addr = spawn_mock_server(|args| match &args[0] {
+            Frame::BulkString(Some(name)) if name.as_ref() == b"GET" => args[1].clone(),
+            _ => Frame::Error(b"ERR unknown command".to_vec()),
+        })
+        .await;
+
+        let client = Client::connect(&addr).await.unwrap();
+        let reply = client
+            .connection
+            .send_cmd(&command::get("hot-key"))
+            .await
+            .unwrap();
+        assert_eq!(reply, Frame::BulkString(Some(Bytes::from("hot-key"))));
+    }
+
+    #[tokio::test]
+    async fn test_reset_accepts_reset_reply() {
+        let addr = spawn_mock_server(|args| match &args[0] {
+            Frame::BulkString(Some(name)) if name.as_ref() == b"RESET" => {
+                Frame::SimpleString(b"RESET".to_vec())
+            }
+            _ => Frame::SimpleString(b"OK".to_vec()),
+        })
+        .await;
+
+        let mut client = builder::ClientBuilder::new()
+            .address(addr)
+            .database(4)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(client.current_database(), 4);
+
+        client.reset().await.unwrap();
+        assert_eq!(client.current_database(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_reset_rejects_unexpected_reply() {
+        let addr = spawn_mock_server(|_| Frame::SimpleString(b"OK".to_vec())).await;
+
+        let mut client = Client::connect(&addr).await.unwrap();
+        assert!(matches!(client.reset().await, Err(Error::Protocol { .. })));
+    }
 
     #[tokio::test]
     async fn test_client_connect() {
@@ -2148,4 +6923,165 @@ mod tests {
         // This will likely fail without a running Redis, so we assert result exists
         assert!(client.is_ok() || client.is_err());
     }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_succeeds_after_listener_starts() {
+        // Reserve a port, then release it so the first attempt is refused
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let server = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+            let (socket, _) = listener.accept().await.unwrap();
+            socket
+        });
+
+        let policy = RetryPolicy {
+            attempts: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(200),
+            timeout: Some(Duration::from_secs(5)),
+        };
+        let client = Client::connect_with_retry(format!("redis://{}", addr), policy).await;
+        assert!(client.is_ok());
+        assert!(server.await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_returns_last_error() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let policy = RetryPolicy {
+            attempts: 2,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+            timeout: None,
+        };
+        let result = Client::connect_with_retry(format!("redis://{}", addr), policy).await;
+        assert!(matches!(result, Err(Error::Io { .. })));
+    }
+
+    #[cfg(feature = "resp3")]
+    #[tokio::test]
+    async fn test_initialize_connection_uses_hello() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut conn = connection::Connection::new(socket);
+            let mut commands = Vec::new();
+            loop {
+                let frame = conn.read_frame().await.unwrap();
+                let args = match frame {
+                    Frame::Array(args) => args,
+                    other => panic!("unexpected frame: {:?}", other),
+                };
+                let name = match &args[0] {
+                    Frame::BulkString(Some(name)) => name.clone(),
+                    other => panic!("unexpected command name: {:?}", other),
+                };
+                if name.as_ref() == b"PING" {
+                    conn.write_frame(&Frame::SimpleString(b"PONG".to_vec()))
+                        .await
+                        .unwrap();
+                    return commands;
+                }
+                conn.write_frame(&Frame::SimpleString(b"OK".to_vec()))
+                    .await
+                    .unwrap();
+                commands.push(args);
+            }
+        });
+
+        let mut client = builder::ClientBuilder::new()
+            .address(format!("redis://{}", addr))
+            .username("user")
+            .password("secret")
+            .client_name("app")
+            .database(2)
+            .build()
+            .await
+            .unwrap();
+        client.ping().await.unwrap();
+
+        let commands = server.await.unwrap();
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            commands[0],
+            command::hello(
+                HELLO_PROTOCOL_VERSION,
+                Some(("user".into(), "secret".into())),
+                Some("app".into())
+            )
+            .into_frame()
+            .to_array()
+            .unwrap()
+        );
+        assert_eq!(commands[1][0], Frame::BulkString(Some("SELECT".into())));
+    }
+
+    #[cfg(feature = "resp3")]
+    #[tokio::test]
+    async fn test_initialize_connection_falls_back_without_hello() {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        let addr = spawn_mock_server(move |args| {
+            let name = args[0].as_str().unwrap_or_default().into_owned();
+            log.lock().unwrap().push(name.clone());
+            match name.as_str() {
+                "HELLO" => Frame::Error(
+                    b"ERR unknown command 'HELLO', with args beginning with: '3' ".to_vec(),
+                ),
+                "PING" => Frame::SimpleString(b"PONG".to_vec()),
+                _ => Frame::SimpleString(b"OK".to_vec()),
+            }
+        })
+        .await;
+
+        let mut client = builder::ClientBuilder::new()
+            .address(addr)
+            .username("user")
+            .password("secret")
+            .client_name("app")
+            .database(2)
+            .build()
+            .await
+            .unwrap();
+        client.ping().await.unwrap();
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            ["HELLO", "AUTH", "SELECT", "CLIENT", "PING"]
+        );
+    }
+
+    #[cfg(feature = "resp3")]
+    #[tokio::test]
+    async fn test_resp3_replies_parse_like_resp2() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        let mut client = memory_mock_client(move |args| match args[0].as_bytes() {
+            Some(b"HGETALL") => Frame::Map(vec![(bulk("name"), bulk("muxis"))]),
+            Some(b"ZSCORE") => Frame::Double(2.5),
+            _ => Frame::Boolean(true),
+        });
+
+        let fields = client.hgetall("user:1").await.unwrap();
+        assert_eq!(fields["name"], Bytes::from("muxis"));
+        assert_eq!(
+            client.zscore("board", Bytes::from("a")).await.unwrap(),
+            Some(2.5)
+        );
+        assert!(client.sismember("set", Bytes::from("a")).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_does_not_retry_invalid_address() {
+        let result = Client::connect_with_retry("not a url", RetryPolicy::default()).await;
+        assert!(matches!(result, Err(Error::InvalidArgument { .. })));
+    }
 }
@@ -0,0 +1,55 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use tokio::sync::mpsc;
+
+use crate::proto::error::{Error, Result};
+use crate::proto::frame::Frame;
+
+/// A stream of commands observed by the server, created by [`Client::monitor`].
+///
+/// Each item is one line of `MONITOR` output, such as
+/// `1339518083.107412 [0 127.0.0.1:60866] "keys" "*"`.
+///
+/// [`Client::monitor`]: crate::Client::monitor
+#[derive(Debug)]
+pub struct MonitorStream {
+    receiver: mpsc::Receiver<Result<Frame>>,
+}
+
+impl MonitorStream {
+    pub(crate) fn new(receiver: mpsc::Receiver<Result<Frame>>) -> Self {
+        Self { receiver }
+    }
+
+    /// Waits for the next monitored command line.
+    ///
+    /// Returns `Ok(None)` once the connection has closed.
+    pub async fn next(&mut self) -> Result<Option<String>> {
+        self.receiver.recv().await.map(into_line).transpose()
+    }
+}
+
+impl Stream for MonitorStream {
+    type Item = Result<String>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver
+            .poll_recv(cx)
+            .map(|frame| frame.map(into_line))
+    }
+}
+
+fn into_line(frame: Result<Frame>) -> Result<String> {
+    match frame? {
+        Frame::SimpleString(line) => Ok(String::from_utf8_lossy(&line).into_owned()),
+        Frame::BulkString(Some(line)) => Ok(String::from_utf8_lossy(&line).into_owned()),
+        Frame::Error(e) => Err(Error::Server {
+            message: String::from_utf8_lossy(&e).into_owned(),
+        }),
+        _ => Err(Error::Protocol {
+            message: "unexpected frame in MONITOR output".to_string(),
+        }),
+    }
+}
@@ -1,14 +1,121 @@
+use crate::core::command::{self, Cmd};
 use crate::core::connection::{Connection, ConnectionReader, ConnectionWriter};
+use crate::proto::codec::StreamStart;
 use crate::proto::frame::Frame;
+use bytes::BytesMut;
 use std::fmt;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::{mpsc, oneshot};
+use tokio::task::AbortHandle;
 use tracing::{debug, error, instrument};
 
+/// The payload of a request sent to the multiplexer.
+enum Payload {
+    /// A frame encoded by the writer task.
+    Frame(Frame),
+    /// A command already encoded by the caller.
+    Encoded(BytesMut),
+}
+
+impl std::fmt::Debug for Payload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Payload::Frame(frame) => command::Redacted::Frame(frame).fmt(f),
+            Payload::Encoded(data) => command::Redacted::Encoded(data).fmt(f),
+        }
+    }
+}
+
 /// A request sent to the multiplexer.
 struct Request {
-    frame: Frame,
+    payload: Payload,
+    waiter: Waiter,
+}
+
+/// Where the reader task delivers the response to a request.
+struct Waiter {
     response_tx: oneshot::Sender<crate::Result<Frame>>,
+    /// If set, every frame after a successful reply is forwarded here and the
+    /// connection stops serving regular requests (e.g. MONITOR).
+    push_tx: Option<mpsc::Sender<crate::Result<Frame>>>,
+    /// If set, the elements of an array reply are forwarded here one at a time and
+    /// `response_tx` only receives the element count as [`Frame::Integer`].
+    stream_tx: Option<mpsc::Sender<crate::Result<Frame>>>,
+    /// If set, this many replies are read and delivered together as one
+    /// [`Frame::Array`].
+    batch: Option<usize>,
+}
+
+impl Waiter {
+    fn reply(response_tx: oneshot::Sender<crate::Result<Frame>>) -> Self {
+        Self {
+            response_tx,
+            push_tx: None,
+            stream_tx: None,
+            batch: None,
+        }
+    }
+}
+
+/// Number of pushed frames buffered before the reader waits for the consumer.
+const PUSH_BUFFER_SIZE: usize = 1024;
+
+/// Number of streamed array elements buffered before the reader waits for the consumer.
+const STREAM_BUFFER_SIZE: usize = 1024;
+
+/// A snapshot of the counters kept by a multiplexed connection.
+///
+/// Useful to tell whether the request queue is a bottleneck: if
+/// `max_in_flight_seen` reaches the configured queue size, callers waited for room.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MuxMetrics {
+    /// The highest number of requests awaiting a reply at the same time.
+    pub max_in_flight_seen: usize,
+    /// The number of commands queued on the connection.
+    pub total_commands: u64,
+    /// The number of commands that failed, either with an error reply or because
+    /// the connection failed.
+    pub total_errors: u64,
+}
+
+/// Counters shared by all clones of a [`MultiplexedConnection`].
+#[derive(Debug, Default)]
+struct Counters {
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+    total_commands: AtomicU64,
+    total_errors: AtomicU64,
+}
+
+impl Counters {
+    /// Records a queued request; the returned guard marks it done when dropped.
+    fn start(&self) -> InFlight<'_> {
+        self.total_commands.fetch_add(1, Ordering::Relaxed);
+        let in_flight = self.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::Relaxed);
+        InFlight(self)
+    }
+
+    /// Counts a failed request or error reply.
+    fn record(&self, result: &crate::Result<Frame>) {
+        if matches!(result, Err(_) | Ok(Frame::Error(_))) {
+            self.total_errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// A request awaiting its reply, even if the caller was cancelled meanwhile.
+struct InFlight<'a>(&'a Counters);
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 /// A handle to a multiplexed connection.
@@ -18,6 +125,46 @@ struct Request {
 #[derive(Clone)]
 pub struct MultiplexedConnection {
     sender: mpsc::Sender<Request>,
+    database: Arc<AtomicU8>,
+    counters: Arc<Counters>,
+    /// Set once SUBSCRIBE or MONITOR switched the connection into push mode.
+    push_mode: Arc<AtomicBool>,
+    /// Set once a keepalive PING failed; the connection is then considered dead.
+    broken: Arc<AtomicBool>,
+    /// The reader and writer tasks, aborted when the connection is closed.
+    tasks: Arc<[AbortHandle; 2]>,
+    backpressure: bool,
+    peer_addr: Option<SocketAddr>,
+    local_addr: Option<SocketAddr>,
+}
+
+/// A handle to a multiplexed connection that does not keep it open.
+struct WeakConnection {
+    sender: mpsc::WeakSender<Request>,
+    database: Arc<AtomicU8>,
+    counters: Arc<Counters>,
+    push_mode: Arc<AtomicBool>,
+    broken: Arc<AtomicBool>,
+    tasks: Arc<[AbortHandle; 2]>,
+    peer_addr: Option<SocketAddr>,
+    local_addr: Option<SocketAddr>,
+}
+
+impl WeakConnection {
+    /// Returns a usable handle, unless every other handle has been dropped.
+    fn upgrade(&self) -> Option<MultiplexedConnection> {
+        Some(MultiplexedConnection {
+            sender: self.sender.upgrade()?,
+            database: self.database.clone(),
+            counters: self.counters.clone(),
+            push_mode: self.push_mode.clone(),
+            broken: self.broken.clone(),
+            tasks: self.tasks.clone(),
+            backpressure: true,
+            peer_addr: self.peer_addr,
+            local_addr: self.local_addr,
+        })
+    }
 }
 
 impl MultiplexedConnection {
@@ -31,42 +178,415 @@ impl MultiplexedConnection {
     where
         S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
     {
+        let peer_addr = connection.peer_addr();
+        let local_addr = connection.local_addr();
         let (reader, writer) = connection.split();
         let (request_tx, request_rx) = mpsc::channel(queue_size);
         // Waiter queue matches request queue size plus a buffer for in-flight IO
         let (waiter_tx, waiter_rx) = mpsc::channel(queue_size);
 
         // Spawn writer task
-        tokio::spawn(async move {
+        let writer_task = tokio::spawn(async move {
             run_writer(writer, request_rx, waiter_tx).await;
         });
 
         // Spawn reader task
-        tokio::spawn(async move {
-            run_reader(reader, waiter_rx).await;
+        let push_mode = Arc::new(AtomicBool::new(false));
+        let reader_push_mode = push_mode.clone();
+        let reader_task = tokio::spawn(async move {
+            run_reader(reader, waiter_rx, reader_push_mode).await;
         });
 
-        Self { sender: request_tx }
+        Self {
+            sender: request_tx,
+            database: Arc::new(AtomicU8::new(0)),
+            counters: Arc::new(Counters::default()),
+            push_mode,
+            broken: Arc::new(AtomicBool::new(false)),
+            tasks: Arc::new([writer_task.abort_handle(), reader_task.abort_handle()]),
+            backpressure: true,
+            peer_addr,
+            local_addr,
+        }
+    }
+
+    /// Returns the address of the server, if known.
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+
+    /// Returns the local address of the connection, if known.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Sets whether a full request queue makes callers wait.
+    ///
+    /// Enabled by default. When disabled, a command sent while `queue_size`
+    /// requests are already queued fails immediately with
+    /// [`Error::QueueFull`](crate::Error::QueueFull) instead.
+    pub fn with_backpressure(mut self, enabled: bool) -> Self {
+        self.backpressure = enabled;
+        self
+    }
+
+    /// Sends PING whenever the connection has been idle for `interval`.
+    ///
+    /// Keeps idle connections from being closed by the server's `timeout` or by
+    /// firewalls, and detects dead peers early. The PING goes through the same
+    /// request queue as user commands, so it never interleaves with their replies,
+    /// and it is skipped while commands are in flight. If the PING fails, or gets
+    /// no reply within `interval`, the connection is closed: requests still
+    /// waiting fail at once and every later command fails with a retryable
+    /// [`Error::Io`](crate::Error::Io). A PING queued behind a slow or blocking
+    /// command has no deadline. The task stops once all handles are dropped or the
+    /// connection enters push mode.
+    /// `None` or a zero interval disables the keepalive.
+    pub fn with_keepalive(self, interval: Option<Duration>) -> Self {
+        if let Some(interval) = interval.filter(|interval| !interval.is_zero()) {
+            tokio::spawn(run_keepalive(self.downgrade(), interval));
+        }
+        self
+    }
+
+    /// Marks the connection failed and stops its reader and writer tasks.
+    ///
+    /// Dropping the tasks closes the socket and fails every request still waiting
+    /// for a reply.
+    fn close(&self) {
+        self.broken.store(true, Ordering::Release);
+        for task in self.tasks.iter() {
+            task.abort();
+        }
+    }
+
+    fn downgrade(&self) -> WeakConnection {
+        WeakConnection {
+            sender: self.sender.downgrade(),
+            database: self.database.clone(),
+            counters: self.counters.clone(),
+            push_mode: self.push_mode.clone(),
+            broken: self.broken.clone(),
+            tasks: self.tasks.clone(),
+            peer_addr: self.peer_addr,
+            local_addr: self.local_addr,
+        }
+    }
+
+    /// Returns a snapshot of the connection's counters.
+    ///
+    /// Shared by all clones of this handle.
+    pub fn metrics(&self) -> MuxMetrics {
+        MuxMetrics {
+            max_in_flight_seen: self.counters.max_in_flight.load(Ordering::Relaxed),
+            total_commands: self.counters.total_commands.load(Ordering::Relaxed),
+            total_errors: self.counters.total_errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns true once SUBSCRIBE or MONITOR switched the connection into push
+    /// mode, after which it refuses regular commands.
+    ///
+    /// Shared by all clones of this handle.
+    pub fn is_push_mode(&self) -> bool {
+        self.push_mode.load(Ordering::Acquire)
+    }
+
+    /// Returns the logical database currently selected on this connection.
+    ///
+    /// Shared by all clones of this handle.
+    pub fn database(&self) -> u8 {
+        self.database.load(Ordering::Relaxed)
+    }
+
+    /// Records the logical database selected on this connection.
+    ///
+    /// This does not send SELECT; call it after the server acknowledged the change
+    /// so the database can be re-applied when the connection is re-established.
+    pub fn set_database(&self, db: u8) {
+        self.database.store(db, Ordering::Relaxed);
     }
 
     /// Sends a command to the server and awaits the response.
-    #[instrument(skip(self), level = "debug")]
+    ///
+    /// Cancellation safe: if the returned future is dropped (e.g. by a timeout in
+    /// `tokio::select!`), the command may still run on the server, but its reply is
+    /// discarded when it arrives instead of being delivered to a later request.
+    #[instrument(skip_all, level = "debug", fields(frame = ?command::Redacted::Frame(&frame)))]
     pub async fn send_command(&self, frame: Frame) -> crate::Result<Frame> {
+        self.send_payload(Payload::Frame(frame)).await
+    }
+
+    /// Sends a command to the server and awaits the response.
+    ///
+    /// The command is encoded directly from its arguments, skipping the
+    /// intermediate [`Frame`] built by [`send_command`](Self::send_command).
+    #[instrument(skip_all, level = "debug", fields(cmd = ?command::Redacted::Cmd(cmd)))]
+    pub async fn send_cmd(&self, cmd: &Cmd) -> crate::Result<Frame> {
+        let mut buf = BytesMut::new();
+        cmd.write_to(&mut buf);
+        self.send_payload(Payload::Encoded(buf)).await
+    }
+
+    /// Sends MONITOR and switches the connection into push mode.
+    ///
+    /// Once the server acknowledges MONITOR, every frame it sends is forwarded to the
+    /// returned receiver. The connection no longer answers regular commands, so
+    /// requests from other clones of this handle fail from then on.
+    pub async fn monitor(&self) -> crate::Result<mpsc::Receiver<crate::Result<Frame>>> {
+        match self.send_push(command::monitor().into_frame()).await? {
+            (Frame::SimpleString(_), push_rx) => Ok(push_rx),
+            _ => Err(crate::Error::Protocol {
+                message: "unexpected reply to MONITOR".to_string(),
+            }),
+        }
+    }
+
+    /// Sends SUBSCRIBE and switches the connection into push mode.
+    ///
+    /// The first subscription confirmation is consumed here; the confirmations for
+    /// the remaining channels and all published messages are forwarded to the
+    /// returned receiver. As with [`monitor`](Self::monitor), the connection no
+    /// longer answers regular commands.
+    pub async fn subscribe(
+        &self,
+        channels: Vec<String>,
+    ) -> crate::Result<mpsc::Receiver<crate::Result<Frame>>> {
+        if channels.is_empty() {
+            return Err(crate::Error::InvalidArgument {
+                message: "SUBSCRIBE requires at least one channel".to_string(),
+            });
+        }
+        match self
+            .send_push(command::subscribe(channels).into_frame())
+            .await?
+        {
+            (Frame::Array(_), push_rx) => Ok(push_rx),
+            _ => Err(crate::Error::Protocol {
+                message: "unexpected reply to SUBSCRIBE".to_string(),
+            }),
+        }
+    }
+
+    /// Sends a command whose successful reply switches the connection into push mode.
+    async fn send_push(
+        &self,
+        frame: Frame,
+    ) -> crate::Result<(Frame, mpsc::Receiver<crate::Result<Frame>>)> {
+        let (push_tx, push_rx) = mpsc::channel(PUSH_BUFFER_SIZE);
+        let (response_tx, response_rx) = oneshot::channel();
+        let request = Request {
+            payload: Payload::Frame(frame),
+            waiter: Waiter {
+                response_tx,
+                push_tx: Some(push_tx),
+                stream_tx: None,
+                batch: None,
+            },
+        };
+        match self.enqueue(request, response_rx).await? {
+            Frame::Error(e) => Err(crate::Error::Server {
+                message: String::from_utf8_lossy(&e).into_owned(),
+            }),
+            reply => Ok((reply, push_rx)),
+        }
+    }
+
+    /// Sends a command whose array reply is delivered one element at a time.
+    ///
+    /// The reader decodes and forwards each element as it arrives instead of
+    /// building the whole array, so memory use stays bounded for huge replies. A
+    /// nil reply yields an empty stream. While the elements are read, replies to
+    /// later requests on this connection wait, so consume the stream promptly;
+    /// dropping the receiver early discards the remaining elements.
+    pub async fn send_streamed(
+        &self,
+        frame: Frame,
+    ) -> crate::Result<mpsc::Receiver<crate::Result<Frame>>> {
+        let (stream_tx, stream_rx) = mpsc::channel(STREAM_BUFFER_SIZE);
         let (response_tx, response_rx) = oneshot::channel();
-        let request = Request { frame, response_tx };
+        let request = Request {
+            payload: Payload::Frame(frame),
+            waiter: Waiter {
+                response_tx,
+                push_tx: None,
+                stream_tx: Some(stream_tx),
+                batch: None,
+            },
+        };
+        match self.enqueue(request, response_rx).await? {
+            Frame::Integer(_) | Frame::Null | Frame::BulkString(None) => Ok(stream_rx),
+            Frame::Error(e) => Err(crate::Error::Server {
+                message: String::from_utf8_lossy(&e).into_owned(),
+            }),
+            _ => Err(crate::Error::Protocol {
+                message: "expected array reply for streamed command".to_string(),
+            }),
+        }
+    }
 
-        // Send request to writer task
-        self.sender
-            .send(request)
-            .await
-            .map_err(|_| crate::Error::Io {
-                source: std::io::Error::new(std::io::ErrorKind::BrokenPipe, "connection closed"),
-            })?;
+    /// Sends several commands in a single write and awaits all their replies.
+    ///
+    /// Unlike [`send_pipeline`](Self::send_pipeline), the commands are queued as
+    /// one request, so no command from another clone of this handle can land
+    /// between them. Needed for MULTI/EXEC, where anything sent in between would
+    /// join the transaction. Replies are returned in command order.
+    pub async fn send_batch(&self, cmds: &[Cmd]) -> crate::Result<Vec<Frame>> {
+        let mut buf = BytesMut::new();
+        for cmd in cmds {
+            cmd.write_to(&mut buf);
+        }
+        let (response_tx, response_rx) = oneshot::channel();
+        let request = Request {
+            payload: Payload::Encoded(buf),
+            waiter: Waiter {
+                response_tx,
+                push_tx: None,
+                stream_tx: None,
+                batch: Some(cmds.len()),
+            },
+        };
+        match self.enqueue(request, response_rx).await? {
+            Frame::Array(replies) => Ok(replies),
+            _ => Err(crate::Error::Protocol {
+                message: "expected one reply per batched command".to_string(),
+            }),
+        }
+    }
+
+    /// Returns the connection to its default state (RESET).
+    ///
+    /// The server deselects the database, discards any MULTI transaction, leaves
+    /// subscribe mode and drops authentication. Requires Redis 6.2 or later.
+    pub async fn reset(&self) -> crate::Result<()> {
+        match self.send_cmd(&command::reset()).await? {
+            Frame::SimpleString(s) if s == b"RESET" => {
+                self.set_database(0);
+                Ok(())
+            }
+            Frame::Error(e) => Err(crate::Error::Server {
+                message: String::from_utf8_lossy(&e).into_owned(),
+            }),
+            _ => Err(crate::Error::Protocol {
+                message: "unexpected reply to RESET".to_string(),
+            }),
+        }
+    }
+
+    async fn send_payload(&self, payload: Payload) -> crate::Result<Frame> {
+        let (response_tx, response_rx) = oneshot::channel();
+        let request = Request {
+            payload,
+            waiter: Waiter::reply(response_tx),
+        };
+        self.enqueue(request, response_rx).await
+    }
+
+    async fn enqueue(
+        &self,
+        request: Request,
+        response_rx: oneshot::Receiver<crate::Result<Frame>>,
+    ) -> crate::Result<Frame> {
+        let _in_flight = self.submit(request).await?;
 
         // Await response
-        response_rx.await.map_err(|_| crate::Error::Io {
+        let result = response_rx.await.map_err(|_| crate::Error::Io {
+            source: std::io::Error::new(std::io::ErrorKind::BrokenPipe, "connection closed"),
+        })?;
+        self.counters.record(&result);
+        result
+    }
+
+    /// Sends the keepalive PING, returning `None` if its reply misses `deadline`.
+    ///
+    /// A command queued just before the PING, such as a BLPOP, delays its reply for
+    /// as long as it blocks, so the deadline only applies when nothing is ahead of
+    /// it. Commands queued after the PING do not affect it.
+    async fn keepalive_ping(&self, deadline: Duration) -> Option<crate::Result<Frame>> {
+        let (response_tx, response_rx) = oneshot::channel();
+        let request = Request {
+            payload: Payload::Frame(command::ping().into_frame()),
+            waiter: Waiter::reply(response_tx),
+        };
+        let _in_flight = match self.submit(request).await {
+            Ok(in_flight) => in_flight,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let reply = if self.counters.in_flight.load(Ordering::Relaxed) > 1 {
+            response_rx.await
+        } else {
+            tokio::time::timeout(deadline, response_rx).await.ok()?
+        };
+        let result = reply.map_err(|_| crate::Error::Io {
             source: std::io::Error::new(std::io::ErrorKind::BrokenPipe, "connection closed"),
-        })?
+        });
+        Some(result.and_then(|result| {
+            self.counters.record(&result);
+            result
+        }))
+    }
+
+    /// Hands a request to the writer task, waiting for room unless backpressure
+    /// is disabled.
+    async fn submit(&self, request: Request) -> crate::Result<InFlight<'_>> {
+        let closed = || crate::Error::Io {
+            source: std::io::Error::new(std::io::ErrorKind::BrokenPipe, "connection closed"),
+        };
+        let sent = if self.broken.load(Ordering::Acquire) {
+            Err(crate::Error::Io {
+                source: std::io::Error::new(
+                    std::io::ErrorKind::BrokenPipe,
+                    "connection closed after a failed keepalive PING",
+                ),
+            })
+        } else if self.is_push_mode() {
+            Err(crate::Error::InvalidArgument {
+                message: "connection is in SUBSCRIBE or MONITOR mode and cannot send commands"
+                    .to_string(),
+            })
+        } else if self.backpressure {
+            self.sender.send(request).await.map_err(|_| closed())
+        } else {
+            self.sender.try_send(request).map_err(|e| match e {
+                mpsc::error::TrySendError::Full(_) => crate::Error::QueueFull,
+                mpsc::error::TrySendError::Closed(_) => closed(),
+            })
+        };
+        if let Err(e) = sent {
+            self.counters.total_errors.fetch_add(1, Ordering::Relaxed);
+            return Err(e);
+        }
+        Ok(self.counters.start())
+    }
+
+    /// Sends several commands back-to-back and awaits all responses.
+    ///
+    /// All requests are queued before any response is awaited, so the commands
+    /// share a single round-trip. Responses are returned in request order.
+    #[instrument(skip(self, frames), level = "debug")]
+    pub async fn send_pipeline(&self, frames: Vec<Frame>) -> crate::Result<Vec<Frame>> {
+        let mut receivers = Vec::with_capacity(frames.len());
+        for frame in frames {
+            let (response_tx, response_rx) = oneshot::channel();
+            let in_flight = self
+                .submit(Request {
+                    payload: Payload::Frame(frame),
+                    waiter: Waiter::reply(response_tx),
+                })
+                .await?;
+            receivers.push((response_rx, in_flight));
+        }
+
+        let mut responses = Vec::with_capacity(receivers.len());
+        for (response_rx, _in_flight) in receivers {
+            let result = response_rx.await.map_err(|_| crate::Error::Io {
+                source: std::io::Error::new(std::io::ErrorKind::BrokenPipe, "connection closed"),
+            })?;
+            self.counters.record(&result);
+            responses.push(result?);
+        }
+        Ok(responses)
     }
 }
 
@@ -74,30 +594,86 @@ impl fmt::Debug for MultiplexedConnection {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("MultiplexedConnection")
             .field("sender", &self.sender)
+            .field("peer_addr", &self.peer_addr)
+            .field("database", &self.database())
+            .field("push_mode", &self.is_push_mode())
+            .field("backpressure", &self.backpressure)
+            .field("metrics", &self.metrics())
             .finish()
     }
 }
 
+/// Pings the connection each time a full `interval` passes without commands.
+async fn run_keepalive(handle: WeakConnection, interval: Duration) {
+    let mut seen = handle.counters.total_commands.load(Ordering::Relaxed);
+    loop {
+        tokio::time::sleep(interval).await;
+        let Some(connection) = handle.upgrade() else {
+            return;
+        };
+        if connection.is_push_mode() {
+            return;
+        }
+        let idle = connection.counters.total_commands.load(Ordering::Relaxed) == seen
+            && connection.counters.in_flight.load(Ordering::Relaxed) == 0;
+        if idle {
+            match connection.keepalive_ping(interval).await {
+                // Any reply, even an error, shows the connection is alive
+                Some(Ok(_)) => {}
+                Some(Err(_)) if connection.is_push_mode() => return,
+                Some(Err(e)) => {
+                    debug!(error = %e, "keepalive PING failed, closing connection");
+                    connection.close();
+                    return;
+                }
+                None => {
+                    debug!("keepalive PING timed out, closing connection");
+                    connection.close();
+                    return;
+                }
+            }
+        }
+        seen = connection.counters.total_commands.load(Ordering::Relaxed);
+    }
+}
+
 async fn run_writer<S>(
     mut writer: ConnectionWriter<S>,
     mut request_rx: mpsc::Receiver<Request>,
-    waiter_tx: mpsc::Sender<oneshot::Sender<crate::Result<Frame>>>,
+    waiter_tx: mpsc::Sender<Waiter>,
 ) where
     S: AsyncRead + AsyncWrite + Unpin,
 {
     while let Some(req) = request_rx.recv().await {
-        debug!(?req.frame, "sending frame");
-        // Write frame to socket
-        if let Err(e) = writer.write_frame(&req.frame).await {
-            error!(error = ?e, "failed to write frame");
-            // Failed to write, notify client
-            let _ = req.response_tx.send(Err(crate::Error::Io { source: e }));
-            return; // Stop writer task
-        }
-
-        // Send waiter to reader task
-        // If this fails, it means reader task is dead
-        if waiter_tx.send(req.response_tx).await.is_err() {
+        // Queue every request already waiting, then flush them together
+        let mut next = Some(req);
+        while let Some(req) = next {
+            debug!(?req.payload, "sending frame");
+            let written = match &req.payload {
+                Payload::Frame(frame) => writer.write_frame(frame).await,
+                Payload::Encoded(data) => writer.write_encoded(data).await,
+            };
+            if let Err(e) = written {
+                error!(error = ?e, "failed to write frame");
+                // Failed to write, notify client
+                let _ = req
+                    .waiter
+                    .response_tx
+                    .send(Err(crate::Error::Io { source: e }));
+                return; // Stop writer task
+            }
+
+            // Send waiter to reader task
+            // If this fails, it means reader task is dead
+            if waiter_tx.send(req.waiter).await.is_err() {
+                return;
+            }
+            next = request_rx.try_recv().ok();
+        }
+
+        if let Err(e) = writer.flush().await {
+            // Waiters already queued see the failure when the reader hits the dead socket
+            error!(error = ?e, "failed to flush frames");
             return;
         }
     }
@@ -105,22 +681,58 @@ async fn run_writer<S>(
 
 async fn run_reader<S>(
     mut reader: ConnectionReader<S>,
-    mut waiter_rx: mpsc::Receiver<oneshot::Sender<crate::Result<Frame>>>,
+    mut waiter_rx: mpsc::Receiver<Waiter>,
+    push_mode: Arc<AtomicBool>,
 ) where
     S: AsyncRead + AsyncWrite + Unpin,
 {
     loop {
         // Wait for the next expected response waiter
-        let tx = match waiter_rx.recv().await {
-            Some(tx) => tx,
+        let Waiter {
+            response_tx: tx,
+            push_tx,
+            stream_tx,
+            batch,
+        } = match waiter_rx.recv().await {
+            Some(waiter) => waiter,
             None => return, // Writer closed, no more requests coming
         };
 
+        if let Some(count) = batch {
+            if run_batch(&mut reader, tx, count).await {
+                continue;
+            }
+            return;
+        }
+
+        if let Some(stream_tx) = stream_tx {
+            if run_stream(&mut reader, tx, stream_tx).await {
+                continue;
+            }
+            return;
+        }
+
         // Read the next frame from the connection
-        match reader.read_frame().await {
+        match read_reply(&mut reader, push_tx.is_none()).await {
             Ok(frame) => {
                 debug!(?frame, "received frame");
-                let _ = tx.send(Ok(frame));
+                let accepted = !matches!(frame, Frame::Error(_));
+                let push_tx = push_tx.filter(|_| accepted);
+                if push_tx.is_some() {
+                    // Refuse new requests before the caller learns of the switch
+                    push_mode.store(true, Ordering::Release);
+                }
+                // Waiters stay in write order even when their requester was cancelled,
+                // so a reply nobody awaits any more is dropped here, never passed on
+                if tx.send(Ok(frame)).is_err() {
+                    debug!("discarded reply for cancelled request");
+                }
+                if let Some(push_tx) = push_tx {
+                    // Requests queued meanwhile will never get a reply; fail them
+                    drop(waiter_rx);
+                    run_push(&mut reader, push_tx).await;
+                    return;
+                }
             }
             Err(e) => {
                 error!(error = ?e, "failed to read frame");
@@ -132,3 +744,222 @@ async fn run_reader<S>(
         }
     }
 }
+
+/// Reads the next reply in its RESP2 shape.
+///
+/// After a HELLO 3 handshake the server answers with RESP3 types, which are
+/// flattened so every consumer parses the same frames on either protocol. If
+/// `skip_pushes` is set, out-of-band RESP3 pushes are dropped rather than taken
+/// for the reply.
+async fn read_reply<S>(reader: &mut ConnectionReader<S>, skip_pushes: bool) -> crate::Result<Frame>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    loop {
+        let frame = reader.read_frame().await?;
+        if skip_pushes && frame.is_push() {
+            debug!(?frame, "skipped out-of-band push");
+            continue;
+        }
+        return Ok(frame.into_resp2());
+    }
+}
+
+/// Reads the `count` replies of a batch and delivers them as one array.
+///
+/// Returns false if the connection failed.
+async fn run_batch<S>(
+    reader: &mut ConnectionReader<S>,
+    tx: oneshot::Sender<crate::Result<Frame>>,
+    count: usize,
+) -> bool
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut replies = Vec::with_capacity(count);
+    for _ in 0..count {
+        match read_reply(reader, true).await {
+            Ok(frame) => replies.push(frame),
+            Err(e) => {
+                error!(error = ?e, "failed to read batched reply");
+                let _ = tx.send(Err(e));
+                return false;
+            }
+        }
+    }
+    debug!(count, "received batched replies");
+    if tx.send(Ok(Frame::Array(replies))).is_err() {
+        debug!("discarded reply for cancelled request");
+    }
+    true
+}
+
+/// Reads a reply whose array elements are forwarded to `stream_tx` one at a time.
+///
+/// Every element is read even after the receiver is dropped, so the next reply
+/// starts at the right place. Returns false if the connection failed.
+async fn run_stream<S>(
+    reader: &mut ConnectionReader<S>,
+    tx: oneshot::Sender<crate::Result<Frame>>,
+    stream_tx: mpsc::Sender<crate::Result<Frame>>,
+) -> bool
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let len = match reader.read_stream_start().await {
+        Ok(StreamStart::Array(len)) => len,
+        Ok(StreamStart::Frame(frame)) => {
+            debug!(?frame, "received frame");
+            if tx.send(Ok(frame.into_resp2())).is_err() {
+                debug!("discarded reply for cancelled request");
+            }
+            return true;
+        }
+        Err(e) => {
+            error!(error = ?e, "failed to read frame");
+            let _ = tx.send(Err(e));
+            return false;
+        }
+    };
+
+    debug!(len, "streaming array reply");
+    let _ = tx.send(Ok(Frame::Integer(len as i64)));
+    for _ in 0..len {
+        match reader.read_frame().await.map(Frame::into_resp2) {
+            // A dropped receiver means the consumer lost interest; keep draining
+            Ok(frame) => {
+                let _ = stream_tx.send(Ok(frame)).await;
+            }
+            Err(e) => {
+                error!(error = ?e, "failed to read streamed element");
+                let _ = stream_tx.send(Err(e)).await;
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Forwards every incoming frame to `push_tx` until the connection fails or the
+/// receiver is dropped.
+async fn run_push<S>(reader: &mut ConnectionReader<S>, push_tx: mpsc::Sender<crate::Result<Frame>>)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    loop {
+        // Every frame is pushed, so drain whatever one read delivered
+        match reader.read_frames().await {
+            Ok(frames) => {
+                for frame in frames {
+                    if push_tx.send(Ok(frame.into_resp2())).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(e) => {
+                let _ = push_tx.send(Err(e)).await;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    /// Returns a connection to a server running `serve` on the other end of an
+    /// in-memory pipe.
+    ///
+    /// Unlike a socket, the pipe wakes the peer task directly, so on paused time the
+    /// clock never auto-advances while a frame is in flight.
+    fn connect_in_memory<F, Fut>(serve: F) -> MultiplexedConnection
+    where
+        F: FnOnce(Connection<tokio::io::DuplexStream>) -> Fut,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(serve(Connection::new(server)));
+        MultiplexedConnection::new(Connection::new(client), 16)
+    }
+
+    /// Starts a server answering BLPOP after `delay` and anything else at once,
+    /// one command at a time, and returns a connection to it.
+    fn connect_to_blocking_server(delay: Duration) -> MultiplexedConnection {
+        connect_in_memory(move |mut conn| async move {
+            while let Ok(Frame::Array(args)) = conn.read_frame().await {
+                let reply = if args[0].as_bytes() == Some(b"BLPOP") {
+                    tokio::time::sleep(delay).await;
+                    Frame::Array(vec![
+                        Frame::BulkString(Some(Bytes::from("jobs"))),
+                        Frame::BulkString(Some(Bytes::from("job-1"))),
+                    ])
+                } else {
+                    Frame::SimpleString(b"PONG".to_vec())
+                };
+                if conn.write_frame(&reply).await.is_err() {
+                    return;
+                }
+            }
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_ping_waits_for_blocking_command_ahead() {
+        let connection = connect_to_blocking_server(Duration::from_millis(150));
+        let blocking = connection.clone();
+        let blpop = tokio::spawn(async move {
+            blocking
+                .send_cmd(&command::blpop(vec!["jobs".to_string()], 0))
+                .await
+        });
+        while connection.counters.in_flight.load(Ordering::Relaxed) == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        // The PING reply comes long after its 30ms deadline, behind the BLPOP
+        let reply = connection.keepalive_ping(Duration::from_millis(30)).await;
+        assert_eq!(
+            reply.unwrap().unwrap(),
+            Frame::SimpleString(b"PONG".to_vec())
+        );
+        assert!(blpop.await.unwrap().is_ok());
+    }
+
+    #[cfg(feature = "resp3")]
+    #[tokio::test]
+    async fn test_out_of_band_pushes_are_not_taken_for_replies() {
+        let connection = connect_in_memory(|mut conn| async move {
+            let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+            while conn.read_frame().await.is_ok() {
+                let invalidate =
+                    Frame::Push(vec![bulk("invalidate"), Frame::Array(vec![bulk("k")])]);
+                let pong = Frame::SimpleString(b"PONG".to_vec());
+                if conn.write_frame(&invalidate).await.is_err()
+                    || conn.write_frame(&pong).await.is_err()
+                {
+                    return;
+                }
+            }
+        });
+
+        for _ in 0..2 {
+            assert_eq!(
+                connection.send_cmd(&command::ping()).await.unwrap(),
+                Frame::SimpleString(b"PONG".to_vec())
+            );
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_keepalive_ping_deadline_applies_when_nothing_is_ahead() {
+        let connection =
+            connect_in_memory(|mut conn| async move { while conn.read_frame().await.is_ok() {} });
+
+        let started = tokio::time::Instant::now();
+        let reply = connection.keepalive_ping(Duration::from_millis(30)).await;
+        assert!(reply.is_none());
+        assert_eq!(started.elapsed(), Duration::from_millis(30));
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::Stream;
+use tokio::sync::mpsc;
+
+use crate::core::multiplexed::MultiplexedConnection;
+use crate::core::retry::RetryPolicy;
+use crate::core::value::FromRedisValue;
+use crate::proto::error::{Error, Result};
+use crate::proto::frame::Frame;
+
+/// Opens a new connection to the node a subscription was made on.
+pub(crate) type Connector =
+    Arc<dyn Fn() -> BoxFuture<'static, Result<MultiplexedConnection>> + Send + Sync>;
+
+/// Receiver of the frames pushed on a subscribed connection.
+type PushReceiver = mpsc::Receiver<Result<Frame>>;
+
+/// A message received on a subscribed channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PubSubMessage {
+    /// The channel the message was published to.
+    pub channel: String,
+    /// The message payload.
+    pub payload: Bytes,
+}
+
+impl PubSubMessage {
+    /// Converts the payload to `T`, e.g. a number, a `String` or (with the `json`
+    /// feature) a `Json<T>` document.
+    ///
+    /// The raw payload stays available in [`payload`](Self::payload).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Protocol`] if the payload cannot be converted.
+    pub fn payload_as<T: FromRedisValue>(&self) -> Result<T> {
+        T::from_redis_value(Frame::BulkString(Some(self.payload.clone())))
+    }
+}
+
+/// Emitted when a subscription was restored on a new connection.
+///
+/// Messages published while the connection was down are lost, so this marks a
+/// possible gap in the message stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ReconnectEvent {
+    /// The channels that were subscribed again.
+    pub channels: Vec<String>,
+}
+
+/// An event on a [`PubSub`] subscription.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PubSubEvent {
+    /// A message was published to a subscribed channel.
+    Message(PubSubMessage),
+    /// The connection dropped and the subscriptions were restored.
+    Reconnected(ReconnectEvent),
+}
+
+/// A subscription to one or more Pub/Sub channels.
+///
+/// Created by [`Client::subscribe`] or `ClusterClient::subscribe`. The underlying
+/// connection only receives messages; dropping the `PubSub` closes it.
+///
+/// By default the subscription ends when the connection drops. With
+/// [`with_auto_resubscribe`](Self::with_auto_resubscribe) it reconnects and
+/// subscribes to the same channels again instead.
+///
+/// [`Client::subscribe`]: crate::Client::subscribe
+pub struct PubSub {
+    receiver: PushReceiver,
+    channels: Vec<String>,
+    connector: Connector,
+    auto_resubscribe: bool,
+    reconnecting: Option<BoxFuture<'static, Result<PushReceiver>>>,
+}
+
+impl PubSub {
+    pub(crate) fn new(receiver: PushReceiver, channels: Vec<String>, connector: Connector) -> Self {
+        Self {
+            receiver,
+            channels,
+            connector,
+            auto_resubscribe: false,
+            reconnecting: None,
+        }
+    }
+
+    /// Enables or disables re-subscribing after the connection drops.
+    ///
+    /// When enabled, a lost connection is replaced by a new one (retrying with
+    /// the default [`RetryPolicy`]) that subscribes to the same channels, and a
+    /// [`PubSubEvent::Reconnected`] is emitted by [`next_event`](Self::next_event).
+    /// If reconnecting fails, the error is returned and the next call tries again.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use muxis::{Client, PubSubEvent};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::connect("redis://127.0.0.1:6379").await?;
+    /// let mut pubsub = client.subscribe(&["news"]).await?.with_auto_resubscribe(true);
+    /// while let Some(event) = pubsub.next_event().await? {
+    ///     match event {
+    ///         PubSubEvent::Message(message) => println!("{:?}", message.payload),
+    ///         PubSubEvent::Reconnected(_) => println!("messages may have been missed"),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_auto_resubscribe(mut self, enabled: bool) -> Self {
+        self.auto_resubscribe = enabled;
+        self
+    }
+
+    /// Returns the subscribed channels.
+    pub fn channels(&self) -> &[String] {
+        &self.channels
+    }
+
+    /// Waits for the next published message.
+    ///
+    /// Subscription confirmations are skipped, as are reconnections; use
+    /// [`next_event`](Self::next_event) to observe those. Returns `Ok(None)` once
+    /// the connection has closed.
+    pub async fn next_message(&mut self) -> Result<Option<PubSubMessage>> {
+        while let Some(event) = self.next_event().await? {
+            if let PubSubEvent::Message(message) = event {
+                return Ok(Some(message));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Waits for the next message or reconnection.
+    ///
+    /// Returns `Ok(None)` once the connection has closed and auto re-subscription
+    /// is disabled.
+    pub async fn next_event(&mut self) -> Result<Option<PubSubEvent>> {
+        futures::future::poll_fn(|cx| self.poll_event(cx))
+            .await
+            .transpose()
+    }
+
+    fn poll_event(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<PubSubEvent>>> {
+        loop {
+            if let Some(reconnecting) = self.reconnecting.as_mut() {
+                let result = match reconnecting.as_mut().poll(cx) {
+                    Poll::Ready(result) => result,
+                    Poll::Pending => return Poll::Pending,
+                };
+                self.reconnecting = None;
+                return match result {
+                    Ok(receiver) => {
+                        self.receiver = receiver;
+                        let channels = self.channels.clone();
+                        Poll::Ready(Some(Ok(PubSubEvent::Reconnected(ReconnectEvent {
+                            channels,
+                        }))))
+                    }
+                    Err(e) => Poll::Ready(Some(Err(e))),
+                };
+            }
+
+            match self.receiver.poll_recv(cx) {
+                Poll::Ready(Some(Ok(frame))) => match into_message(frame) {
+                    Ok(Some(message)) => {
+                        return Poll::Ready(Some(Ok(PubSubEvent::Message(message))))
+                    }
+                    Ok(None) => continue,
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                },
+                // A read error ends the push stream, so the connection is gone
+                Poll::Ready(Some(Err(e))) if self.auto_resubscribe => {
+                    tracing::debug!(error = %e, "Pub/Sub connection lost, re-subscribing");
+                    self.start_reconnect();
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) if self.auto_resubscribe => {
+                    tracing::debug!("Pub/Sub connection closed, re-subscribing");
+                    self.start_reconnect();
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    fn start_reconnect(&mut self) {
+        let connector = self.connector.clone();
+        let channels = self.channels.clone();
+        self.reconnecting = Some(Box::pin(async move {
+            let policy = RetryPolicy::default();
+            let mut attempt = 0;
+            loop {
+                attempt += 1;
+                let result = match connector().await {
+                    Ok(connection) => connection.subscribe(channels.clone()).await,
+                    Err(e) => Err(e),
+                };
+                match result {
+                    Ok(receiver) => return Ok(receiver),
+                    Err(Error::Io { source }) if attempt < policy.attempts => {
+                        tracing::debug!(attempt, error = %source, "re-subscribe failed, retrying");
+                        tokio::time::sleep(policy.backoff(attempt)).await;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }));
+    }
+}
+
+impl fmt::Debug for PubSub {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PubSub")
+            .field("channels", &self.channels)
+            .field("auto_resubscribe", &self.auto_resubscribe)
+            .field("reconnecting", &self.reconnecting.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for PubSub {
+    type Item = Result<PubSubMessage>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match self.poll_event(cx) {
+                Poll::Ready(Some(Ok(PubSubEvent::Message(message)))) => {
+                    return Poll::Ready(Some(Ok(message)))
+                }
+                Poll::Ready(Some(Ok(PubSubEvent::Reconnected(_)))) => continue,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Converts a pushed frame to a message, or `None` for subscription bookkeeping.
+fn into_message(frame: Frame) -> Result<Option<PubSubMessage>> {
+    let mut items = match frame {
+        Frame::Array(items) => items,
+        Frame::Error(e) => {
+            return Err(Error::Server {
+                message: String::from_utf8_lossy(&e).into_owned(),
+            })
+        }
+        _ => {
+            return Err(Error::Protocol {
+                message: "unexpected frame in Pub/Sub stream".to_string(),
+            })
+        }
+    };
+
+    match items.first().and_then(Frame::as_bytes) {
+        Some(b"message") if items.len() == 3 => {
+            let payload = match items.pop() {
+                Some(Frame::BulkString(Some(payload))) => payload,
+                _ => Bytes::new(),
+            };
+            let channel = items[1].as_str().unwrap_or_default().into_owned();
+            Ok(Some(PubSubMessage { channel, payload }))
+        }
+        Some(b"subscribe") | Some(b"unsubscribe") => Ok(None),
+        _ => Err(Error::Protocol {
+            message: "unexpected Pub/Sub message kind".to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_as() {
+        let message = |payload: &'static str| PubSubMessage {
+            channel: "events".to_string(),
+            payload: Bytes::from(payload),
+        };
+
+        assert_eq!(message("1234").payload_as::<i64>().unwrap(), 1234);
+        assert_eq!(
+            message("déjà vu").payload_as::<String>().unwrap(),
+            "déjà vu"
+        );
+        assert!(message("not a number").payload_as::<i64>().is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt;
+use std::net::SocketAddr;
+
+use futures::future::BoxFuture;
+
+use crate::Result;
+
+/// Resolves a Redis host and port to the socket address to connect to.
+///
+/// Install a custom resolver with
+/// [`ClientBuilder::resolver`](crate::ClientBuilder::resolver) to connect to a
+/// pre-resolved IP, consult a service registry, or route through a proxy. The
+/// original hostname is still used for TLS server name verification (SNI).
+///
+/// When no resolver is configured, the standard system lookup is used.
+///
+/// # Example
+///
+/// ```
+/// use std::net::SocketAddr;
+/// use futures::future::BoxFuture;
+/// use muxis::{AddressResolver, Result};
+///
+/// #[derive(Debug)]
+/// struct StaticResolver(SocketAddr);
+///
+/// impl AddressResolver for StaticResolver {
+///     fn resolve<'a>(&'a self, _host: &'a str, _port: u16) -> BoxFuture<'a, Result<SocketAddr>> {
+///         Box::pin(async move { Ok(self.0) })
+///     }
+/// }
+/// ```
+pub trait AddressResolver: Send + Sync + fmt::Debug {
+    /// Resolves `host` and `port` to a socket address.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - Hostname or IP literal taken from the connection URL
+    /// * `port` - Port taken from the connection URL (6379 if omitted)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be resolved.
+    fn resolve<'a>(&'a self, host: &'a str, port: u16) -> BoxFuture<'a, Result<SocketAddr>>;
+}
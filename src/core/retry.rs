@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+/// Retry policy for establishing a connection.
+///
+/// Used by [`Client::connect_with_retry`](crate::Client::connect_with_retry) to
+/// retry transient connection failures with exponential backoff.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use muxis::RetryPolicy;
+///
+/// let policy = RetryPolicy {
+///     attempts: 5,
+///     base_delay: Duration::from_millis(50),
+///     max_delay: Duration::from_secs(1),
+///     timeout: Some(Duration::from_secs(5)),
+/// };
+/// assert_eq!(policy.backoff(1), Duration::from_millis(50));
+/// assert_eq!(policy.backoff(2), Duration::from_millis(100));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of connection attempts (including the first one).
+    pub attempts: u32,
+    /// Delay before the first retry. Doubled for every subsequent retry.
+    pub base_delay: Duration,
+    /// Upper bound for the delay between two attempts.
+    pub max_delay: Duration,
+    /// Overall time budget for all attempts. `None` means no limit.
+    pub timeout: Option<Duration>,
+}
+
+impl RetryPolicy {
+    /// Returns the delay to wait after the given failed attempt (1-based).
+    ///
+    /// The delay grows exponentially from `base_delay` and is capped at `max_delay`.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - Number of attempts that have failed so far
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        self.base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(2),
+            timeout: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_policy_default() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.attempts, 3);
+        assert_eq!(policy.base_delay, Duration::from_millis(100));
+        assert_eq!(policy.max_delay, Duration::from_secs(2));
+        assert_eq!(policy.timeout, None);
+    }
+
+    #[test]
+    fn test_retry_policy_backoff_exponential() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_retry_policy_backoff_capped() {
+        let policy = RetryPolicy {
+            max_delay: Duration::from_millis(300),
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+        assert_eq!(policy.backoff(100), Duration::from_millis(300));
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::OnceLock;
+
+use bytes::Bytes;
+
+use crate::core::Client;
+use crate::proto::error::{Error, Result};
+use crate::proto::frame::Frame;
+
+/// A Lua script invoked by its SHA1 digest.
+///
+/// The script is loaded with `SCRIPT LOAD` on first use and the returned digest is
+/// cached, so later invocations only send `EVALSHA`. If the server no longer knows
+/// the script (`NOSCRIPT`, e.g. after a restart or `SCRIPT FLUSH`), it is loaded
+/// again and the call retried once.
+///
+/// # Example
+///
+/// ```no_run
+/// # use muxis::{Client, Script};
+/// # use bytes::Bytes;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut client = Client::connect("redis://127.0.0.1:6379").await?;
+/// let script = Script::new("return redis.call('SET', KEYS[1], ARGV[1])");
+/// script.invoke(&mut client, &["key"], &[Bytes::from("value")]).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Script {
+    code: String,
+    hash: OnceLock<String>,
+}
+
+impl Script {
+    /// Creates a script from its Lua source. Nothing is sent until it is invoked.
+    pub fn new(code: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            hash: OnceLock::new(),
+        }
+    }
+
+    /// Returns the Lua source of the script.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Returns the SHA1 digest of the script, once it has been loaded.
+    pub fn hash(&self) -> Option<&str> {
+        self.hash.get().map(String::as_str)
+    }
+
+    /// Runs the script with EVALSHA, loading it first if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to run the script on.
+    /// * `keys` - The keys the script accesses, available as `KEYS`.
+    /// * `args` - Additional arguments, available as `ARGV`.
+    ///
+    /// # Returns
+    ///
+    /// The raw reply of the script.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Command`] wrapping the server error if loading fails or
+    /// the script raises an error.
+    pub async fn invoke(
+        &self,
+        client: &mut Client,
+        keys: &[&str],
+        args: &[Bytes],
+    ) -> Result<Frame> {
+        let hash = match self.hash.get() {
+            Some(hash) => hash,
+            None => self.load(client).await?,
+        };
+
+        match client.evalsha(hash, keys, args).await {
+            Err(e) if matches!(e.inner(), Error::Server { message } if message.starts_with("NOSCRIPT")) =>
+            {
+                tracing::debug!(sha = %hash, "script missing on server, loading it again");
+                let hash = self.load(client).await?;
+                client.evalsha(hash, keys, args).await
+            }
+            result => result,
+        }
+    }
+
+    /// Loads the script and caches its digest.
+    async fn load(&self, client: &mut Client) -> Result<&str> {
+        let hash = client.script_load(&self.code).await?;
+        Ok(self.hash.get_or_init(|| hash))
+    }
+}
@@ -1,7 +1,40 @@
+use std::io;
 use std::sync::Arc;
-use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{self, ClientConfig, RootCertStore};
 use tokio_rustls::TlsConnector;
 
+use crate::Error;
+
+/// Validates `host` as the server name sent via SNI and checked against the certificate.
+///
+/// # Errors
+///
+/// Returns [`Error::Tls`] if `host` is neither a valid DNS name nor an IP address.
+pub fn server_name(host: &str) -> crate::Result<ServerName<'static>> {
+    ServerName::try_from(host)
+        .map(|name| name.to_owned())
+        .map_err(|e| Error::Tls {
+            message: format!("invalid server name {:?}: {}", host, e),
+        })
+}
+
+/// Converts a failed handshake into an error, keeping the rustls detail.
+///
+/// Handshake failures reported by rustls become [`Error::Tls`]; plain IO failures
+/// (e.g. the connection was reset) stay [`Error::Io`].
+pub fn handshake_error(error: io::Error) -> Error {
+    match error
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<rustls::Error>())
+    {
+        Some(e) => Error::Tls {
+            message: e.to_string(),
+        },
+        None => Error::Io { source: error },
+    }
+}
+
 /// Internal TLS connector wrapper using rustls.
 #[derive(Clone)]
 pub struct TlsConnectorInner {
@@ -12,13 +45,18 @@ impl TlsConnectorInner {
     /// Creates a new TLS connector with default secure configuration.
     ///
     /// Uses `webpki-roots` for Mozilla's root certificates and `ring` as the crypto provider.
-    pub fn new() -> crate::Result<Self> {
+    ///
+    /// # Arguments
+    ///
+    /// * `alpn_protocols` - ALPN protocols to offer during the handshake; empty disables ALPN
+    pub fn new(alpn_protocols: &[Vec<u8>]) -> crate::Result<Self> {
         let mut root_store = RootCertStore::empty();
         root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
 
-        let config = ClientConfig::builder()
+        let mut config = ClientConfig::builder()
             .with_root_certificates(root_store)
             .with_no_client_auth();
+        config.alpn_protocols = alpn_protocols.to_vec();
 
         Ok(Self {
             connector: TlsConnector::from(Arc::new(config)),
@@ -30,3 +68,37 @@ impl TlsConnectorInner {
         self.connector.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_name_accepts_dns_names_and_ips() {
+        assert!(server_name("cache.example.com").is_ok());
+        assert!(server_name("127.0.0.1").is_ok());
+    }
+
+    #[test]
+    fn test_server_name_rejects_invalid_names() {
+        match server_name("-bad..example") {
+            Err(Error::Tls { message }) => assert!(message.contains("-bad..example")),
+            other => panic!("expected Error::Tls, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_handshake_error_keeps_rustls_detail() {
+        let error = io::Error::new(
+            io::ErrorKind::InvalidData,
+            rustls::Error::InvalidCertificate(rustls::CertificateError::UnknownIssuer),
+        );
+        match handshake_error(error) {
+            Error::Tls { message } => assert!(message.contains("UnknownIssuer")),
+            other => panic!("expected Error::Tls, got {:?}", other),
+        }
+
+        let reset = io::Error::new(io::ErrorKind::ConnectionReset, "reset");
+        assert!(matches!(handshake_error(reset), Error::Io { .. }));
+    }
+}
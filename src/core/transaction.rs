@@ -0,0 +1,89 @@
+use bytes::Bytes;
+
+use crate::core::command::{self, Cmd};
+
+/// The commands queued for one attempt of a [`Client::transaction`].
+///
+/// Passed to the closure given to [`Client::transaction`], which queues the
+/// commands to run between MULTI and EXEC. Nothing is sent until the closure
+/// returns.
+///
+/// [`Client::transaction`]: crate::Client::transaction
+#[derive(Debug, Default)]
+pub struct TxnBuilder {
+    cmds: Vec<Cmd>,
+}
+
+impl TxnBuilder {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues an arbitrary command.
+    pub fn add(&mut self, cmd: Cmd) -> &mut Self {
+        self.cmds.push(cmd);
+        self
+    }
+
+    /// Queues a GET.
+    pub fn get(&mut self, key: &str) -> &mut Self {
+        self.add(command::get(key.to_string()))
+    }
+
+    /// Queues a SET.
+    pub fn set(&mut self, key: &str, value: Bytes) -> &mut Self {
+        self.add(command::set(key.to_string(), value))
+    }
+
+    /// Queues a DEL.
+    pub fn del(&mut self, key: &str) -> &mut Self {
+        self.add(command::del(key.to_string()))
+    }
+
+    /// Queues an INCR.
+    pub fn incr(&mut self, key: &str) -> &mut Self {
+        self.add(command::incr(key.to_string()))
+    }
+
+    /// Returns the number of queued commands.
+    pub fn len(&self) -> usize {
+        self.cmds.len()
+    }
+
+    /// Returns true if no command has been queued.
+    pub fn is_empty(&self) -> bool {
+        self.cmds.is_empty()
+    }
+
+    /// Wraps the queued commands in MULTI and EXEC.
+    pub(crate) fn into_cmds(self) -> Vec<Cmd> {
+        let mut cmds = Vec::with_capacity(self.cmds.len() + 2);
+        cmds.push(command::multi());
+        cmds.extend(self.cmds);
+        cmds.push(command::exec());
+        cmds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_cmds_wraps_in_multi_exec() {
+        let mut txn = TxnBuilder::new();
+        txn.set("key", Bytes::from("value")).incr("counter");
+        assert_eq!(txn.len(), 2);
+
+        let names: Vec<_> = txn.into_cmds().iter().map(|c| c.name().to_vec()).collect();
+        assert_eq!(
+            names,
+            vec![
+                b"MULTI".to_vec(),
+                b"SET".to_vec(),
+                b"INCR".to_vec(),
+                b"EXEC".to_vec()
+            ]
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use std::fmt;
+use std::time::Duration;
+
+/// The type of the value stored at a key, as reported by `TYPE`.
+///
+/// # Example
+///
+/// ```
+/// use muxis::KeyType;
+///
+/// assert_eq!(KeyType::from("zset"), KeyType::ZSet);
+/// assert_eq!(KeyType::ZSet.as_str(), "zset");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum KeyType {
+    /// A string value.
+    String,
+    /// A list.
+    List,
+    /// A set.
+    Set,
+    /// A sorted set.
+    ZSet,
+    /// A hash.
+    Hash,
+    /// A stream.
+    Stream,
+    /// The key does not exist.
+    None,
+    /// A type not known to this client, such as a module type (e.g., `ReJSON-RL`).
+    Other(String),
+}
+
+impl KeyType {
+    /// Returns the name Redis uses for this type.
+    pub fn as_str(&self) -> &str {
+        match self {
+            KeyType::String => "string",
+            KeyType::List => "list",
+            KeyType::Set => "set",
+            KeyType::ZSet => "zset",
+            KeyType::Hash => "hash",
+            KeyType::Stream => "stream",
+            KeyType::None => "none",
+            KeyType::Other(name) => name,
+        }
+    }
+}
+
+impl From<&str> for KeyType {
+    fn from(s: &str) -> Self {
+        match s {
+            "string" => KeyType::String,
+            "list" => KeyType::List,
+            "set" => KeyType::Set,
+            "zset" => KeyType::ZSet,
+            "hash" => KeyType::Hash,
+            "stream" => KeyType::Stream,
+            "none" => KeyType::None,
+            other => KeyType::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for KeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A condition restricting which members `ZADD` may touch.
+///
+/// # Example
+///
+/// ```
+/// use muxis::ZAddCondition;
+///
+/// assert_eq!(ZAddCondition::Xx.as_str(), "XX");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZAddCondition {
+    /// Only add new members; never update existing ones (`NX`).
+    Nx,
+    /// Only update existing members; never add new ones (`XX`).
+    Xx,
+}
+
+impl ZAddCondition {
+    /// Returns the `ZADD` flag for this condition.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ZAddCondition::Nx => "NX",
+            ZAddCondition::Xx => "XX",
+        }
+    }
+}
+
+/// How `ZUNION`/`ZINTER` combine the scores of a member present in several sets.
+///
+/// # Example
+///
+/// ```
+/// use muxis::Aggregate;
+///
+/// assert_eq!(Aggregate::Max.as_str(), "MAX");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Aggregate {
+    /// Add the weighted scores (`SUM`), the default.
+    Sum,
+    /// Keep the lowest weighted score (`MIN`).
+    Min,
+    /// Keep the highest weighted score (`MAX`).
+    Max,
+}
+
+impl Aggregate {
+    /// Returns the `AGGREGATE` keyword for this mode.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Aggregate::Sum => "SUM",
+            Aggregate::Min => "MIN",
+            Aggregate::Max => "MAX",
+        }
+    }
+}
+
+/// Options for range commands using the unified `ZRANGE` syntax (Redis 6.2+).
+///
+/// By default the bounds are indexes. With [`by_score`](Self::by_score) or
+/// [`by_lex`](Self::by_lex) they are scores (e.g. `"(1"`, `"+inf"`) or lexicographic
+/// bounds (e.g. `"[a"`, `"-"`), and only then may [`limit`](Self::limit) be used.
+///
+/// # Example
+///
+/// ```
+/// use muxis::ZRangeOptions;
+///
+/// let top_ten = ZRangeOptions::new().by_score().rev().limit(0, 10);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ZRangeOptions {
+    pub(crate) by: Option<&'static str>,
+    pub(crate) rev: bool,
+    pub(crate) limit: Option<(i64, i64)>,
+}
+
+impl ZRangeOptions {
+    /// Creates options for an index range in ascending order.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interprets the bounds as scores (`BYSCORE`).
+    pub fn by_score(mut self) -> Self {
+        self.by = Some("BYSCORE");
+        self
+    }
+
+    /// Interprets the bounds as lexicographic ranges (`BYLEX`).
+    pub fn by_lex(mut self) -> Self {
+        self.by = Some("BYLEX");
+        self
+    }
+
+    /// Walks the range in descending order (`REV`).
+    pub fn rev(mut self) -> Self {
+        self.rev = true;
+        self
+    }
+
+    /// Skips `offset` elements and returns at most `count` (`LIMIT`); a negative
+    /// count returns all remaining elements.
+    pub fn limit(mut self, offset: i64, count: i64) -> Self {
+        self.limit = Some((offset, count));
+        self
+    }
+}
+
+/// When a key written with `SET` expires.
+///
+/// Relative expiries count from when the command runs; absolute ones are Unix
+/// timestamps. A [`Duration`] converts to [`Expiry::Seconds`] when it is a whole
+/// number of seconds and to [`Expiry::Millis`] otherwise.
+///
+/// # Example
+///
+/// ```
+/// use muxis::Expiry;
+/// use std::time::Duration;
+///
+/// assert_eq!(Expiry::from(Duration::from_secs(5)), Expiry::Seconds(5));
+/// assert_eq!(Expiry::from(Duration::from_millis(1500)), Expiry::Millis(1500));
+/// assert_eq!(Expiry::UnixMillis(1_700_000_000_000).as_str(), "PXAT");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Expiry {
+    /// Expire after the given number of seconds (`EX`).
+    Seconds(u64),
+    /// Expire after the given number of milliseconds (`PX`).
+    Millis(u64),
+    /// Expire at the given Unix time, in seconds (`EXAT`).
+    UnixSeconds(u64),
+    /// Expire at the given Unix time, in milliseconds (`PXAT`).
+    UnixMillis(u64),
+}
+
+impl Expiry {
+    /// Returns the `SET` option for this expiry.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Expiry::Seconds(_) => "EX",
+            Expiry::Millis(_) => "PX",
+            Expiry::UnixSeconds(_) => "EXAT",
+            Expiry::UnixMillis(_) => "PXAT",
+        }
+    }
+
+    /// Returns the number of seconds or milliseconds sent with the option.
+    pub fn value(&self) -> u64 {
+        match *self {
+            Expiry::Seconds(v)
+            | Expiry::Millis(v)
+            | Expiry::UnixSeconds(v)
+            | Expiry::UnixMillis(v) => v,
+        }
+    }
+}
+
+impl From<Duration> for Expiry {
+    fn from(ttl: Duration) -> Self {
+        if ttl.subsec_nanos() == 0 {
+            Expiry::Seconds(ttl.as_secs())
+        } else {
+            Expiry::Millis(u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX))
+        }
+    }
+}
+
+/// The unit of a `BITCOUNT` or `BITPOS` range (Redis 7.0+).
+///
+/// # Example
+///
+/// ```
+/// use muxis::BitUnit;
+///
+/// assert_eq!(BitUnit::Bit.as_str(), "BIT");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitUnit {
+    /// The range is in bytes (`BYTE`), the default.
+    Byte,
+    /// The range is in bits (`BIT`).
+    Bit,
+}
+
+impl BitUnit {
+    /// Returns the keyword for this unit.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BitUnit::Byte => "BYTE",
+            BitUnit::Bit => "BIT",
+        }
+    }
+}
+
+/// The remaining time to live of a key, as reported by `TTL`.
+///
+/// Makes the special `-1` and `-2` replies explicit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ttl {
+    /// The key exists but has no expiration (`-1`).
+    NoExpiry,
+    /// The key does not exist (`-2`).
+    Missing,
+    /// The key expires in the given number of seconds.
+    Seconds(u64),
+}
+
+/// How `FLUSHALL` and `FLUSHDB` free memory.
+///
+/// # Example
+///
+/// ```
+/// use muxis::FlushMode;
+///
+/// assert_eq!(FlushMode::Async.as_str(), "ASYNC");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlushMode {
+    /// Free memory in a background thread (`ASYNC`).
+    Async,
+    /// Free memory before replying (`SYNC`).
+    Sync,
+}
+
+impl FlushMode {
+    /// Returns the keyword for this mode.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlushMode::Async => "ASYNC",
+            FlushMode::Sync => "SYNC",
+        }
+    }
+}
+
+/// A user's ACL rules, as reported by `ACL GETUSER`.
+///
+/// Key and channel patterns are normalized to one pattern per entry, whether the
+/// server reports them as a list (Redis 6) or a space-separated string (Redis 7).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AclUser {
+    /// User flags, such as `on`, `off` or `nopass`.
+    pub flags: Vec<String>,
+    /// SHA-256 hashes of the user's passwords.
+    pub passwords: Vec<String>,
+    /// Command rules, such as `+@all -debug`.
+    pub commands: String,
+    /// Key patterns, such as `~*` or `%R~cache:*`.
+    pub keys: Vec<String>,
+    /// Pub/Sub channel patterns, such as `&*`.
+    pub channels: Vec<String>,
+    /// Additional rule sets (Redis 7.0+), each as field/value pairs.
+    pub selectors: Vec<Vec<(String, String)>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_type_from_str() {
+        assert_eq!(KeyType::from("string"), KeyType::String);
+        assert_eq!(KeyType::from("list"), KeyType::List);
+        assert_eq!(KeyType::from("set"), KeyType::Set);
+        assert_eq!(KeyType::from("zset"), KeyType::ZSet);
+        assert_eq!(KeyType::from("hash"), KeyType::Hash);
+        assert_eq!(KeyType::from("stream"), KeyType::Stream);
+        assert_eq!(KeyType::from("none"), KeyType::None);
+    }
+
+    #[test]
+    fn test_key_type_unknown() {
+        let key_type = KeyType::from("ReJSON-RL");
+        assert_eq!(key_type, KeyType::Other("ReJSON-RL".to_string()));
+        assert_eq!(key_type.to_string(), "ReJSON-RL");
+    }
+
+    #[test]
+    fn test_key_type_round_trip() {
+        for name in ["string", "list", "set", "zset", "hash", "stream", "none"] {
+            assert_eq!(KeyType::from(name).as_str(), name);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use bytes::Bytes;
+
+use crate::proto::error::{Error, Result};
+use crate::proto::frame::Frame;
+
+/// A type that can be built from a reply frame.
+///
+/// Implemented for raw frames, byte and text payloads, numbers and `Option`s
+/// of those (nil becomes `None`). Numbers are also parsed from string payloads,
+/// as Redis often sends them that way.
+pub trait FromRedisValue: Sized {
+    /// Converts a frame, failing with [`Error::Protocol`] if it has the wrong shape.
+    fn from_redis_value(frame: Frame) -> Result<Self>;
+}
+
+/// Turns an error reply into [`Error::Server`] and any other frame into a
+/// type mismatch naming the `expected` type.
+fn unexpected(frame: Frame, expected: &str) -> Error {
+    match frame {
+        Frame::Error(e) => Error::Server {
+            message: String::from_utf8_lossy(&e).into_owned(),
+        },
+        frame => Error::Protocol {
+            message: format!("cannot convert {:?} to {}", frame, expected),
+        },
+    }
+}
+
+/// Parses a number sent as a string payload.
+fn parse_number<T: std::str::FromStr>(frame: Frame, expected: &str) -> Result<T> {
+    match frame.as_str().and_then(|s| s.trim().parse().ok()) {
+        Some(n) => Ok(n),
+        None => Err(unexpected(frame, expected)),
+    }
+}
+
+impl FromRedisValue for Frame {
+    fn from_redis_value(frame: Frame) -> Result<Self> {
+        Ok(frame)
+    }
+}
+
+impl FromRedisValue for Bytes {
+    fn from_redis_value(frame: Frame) -> Result<Self> {
+        match frame {
+            Frame::BulkString(Some(b)) => Ok(b),
+            Frame::SimpleString(s) => Ok(Bytes::from(s)),
+            frame => Err(unexpected(frame, "bytes")),
+        }
+    }
+}
+
+impl FromRedisValue for Vec<u8> {
+    fn from_redis_value(frame: Frame) -> Result<Self> {
+        Bytes::from_redis_value(frame).map(Vec::from)
+    }
+}
+
+impl FromRedisValue for String {
+    fn from_redis_value(frame: Frame) -> Result<Self> {
+        let bytes = Vec::<u8>::from_redis_value(frame)?;
+        String::from_utf8(bytes).map_err(|_| Error::Protocol {
+            message: "reply is not valid UTF-8".to_string(),
+        })
+    }
+}
+
+impl FromRedisValue for i64 {
+    fn from_redis_value(frame: Frame) -> Result<Self> {
+        match frame {
+            Frame::Integer(n) => Ok(n),
+            frame => parse_number(frame, "i64"),
+        }
+    }
+}
+
+impl FromRedisValue for u64 {
+    fn from_redis_value(frame: Frame) -> Result<Self> {
+        match frame {
+            Frame::Integer(n) if n >= 0 => Ok(n as u64),
+            frame => parse_number(frame, "u64"),
+        }
+    }
+}
+
+impl FromRedisValue for f64 {
+    fn from_redis_value(frame: Frame) -> Result<Self> {
+        match frame {
+            #[cfg(feature = "resp3")]
+            Frame::Double(n) => Ok(n),
+            Frame::Integer(n) => Ok(n as f64),
+            frame => parse_number(frame, "f64"),
+        }
+    }
+}
+
+impl<T: FromRedisValue> FromRedisValue for Option<T> {
+    fn from_redis_value(frame: Frame) -> Result<Self> {
+        match frame {
+            Frame::Null | Frame::BulkString(None) => Ok(None),
+            frame => T::from_redis_value(frame).map(Some),
+        }
+    }
+}
+
+/// The whole value stored at a key, as returned by [`Client::fetch_any`].
+///
+/// [`Client::fetch_any`]: crate::Client::fetch_any
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum RedisValue {
+    /// A string value.
+    String(Bytes),
+    /// The elements of a list, from head to tail.
+    List(Vec<Bytes>),
+    /// The members of a set, in no particular order.
+    Set(Vec<Bytes>),
+    /// The `(field, value)` pairs of a hash.
+    Hash(Vec<(Bytes, Bytes)>),
+    /// The `(member, score)` pairs of a sorted set, by ascending score.
+    ZSet(Vec<(Bytes, f64)>),
+    /// The key does not exist.
+    None,
+}
+
+/// A JSON document deserialized from a string payload.
+///
+/// # Example
+///
+/// ```no_run
+/// # use muxis::{Client, Json};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::connect("redis://127.0.0.1:6379").await?;
+/// let mut pubsub = client.subscribe(&["events"]).await?;
+/// if let Some(message) = pubsub.next_message().await? {
+///     let Json(event) = message.payload_as::<Json<serde_json::Value>>()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+#[cfg(feature = "json")]
+impl<T: serde::de::DeserializeOwned> FromRedisValue for Json<T> {
+    fn from_redis_value(frame: Frame) -> Result<Self> {
+        let bytes = Bytes::from_redis_value(frame)?;
+        serde_json::from_slice(&bytes)
+            .map(Json)
+            .map_err(|e| Error::Protocol {
+                message: format!("invalid JSON payload: {}", e),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> Frame {
+        Frame::BulkString(Some(Bytes::from(s.to_string())))
+    }
+
+    #[test]
+    fn test_numbers_from_integer_and_string() {
+        assert_eq!(i64::from_redis_value(Frame::Integer(-3)).unwrap(), -3);
+        assert_eq!(i64::from_redis_value(bulk("42")).unwrap(), 42);
+        assert_eq!(u64::from_redis_value(bulk("7")).unwrap(), 7);
+        assert_eq!(f64::from_redis_value(bulk("1.5")).unwrap(), 1.5);
+        assert!(matches!(
+            i64::from_redis_value(bulk("forty")),
+            Err(Error::Protocol { .. })
+        ));
+        assert!(u64::from_redis_value(Frame::Integer(-1)).is_err());
+    }
+
+    #[test]
+    fn test_text_and_nil() {
+        assert_eq!(String::from_redis_value(bulk("héllo")).unwrap(), "héllo");
+        assert!(
+            String::from_redis_value(Frame::BulkString(Some(Bytes::from_static(b"\xff")))).is_err()
+        );
+        assert_eq!(
+            Option::<String>::from_redis_value(Frame::Null).unwrap(),
+            None
+        );
+        assert!(matches!(
+            String::from_redis_value(Frame::Error(b"ERR boom".to_vec())),
+            Err(Error::Server { .. })
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_payload() {
+        let Json(value) = Json::<serde_json::Value>::from_redis_value(bulk(r#"{"id":7}"#)).unwrap();
+        assert_eq!(value["id"], 7);
+    }
+}
@@ -41,9 +41,25 @@ pub mod testing;
 
 // Re-export high-level client types for convenience
 pub use crate::core::builder::ClientBuilder;
-pub use crate::core::{Client, Error, Result};
+pub use crate::core::command::Cmd;
+pub use crate::core::{
+    timeout, AclUser, AddressResolver, Aggregate, BitUnit, Client, Error, Expiry, FlushMode,
+    FromRedisValue, KeyType, MonitorStream, MuxMetrics, PubSub, PubSubEvent, PubSubMessage,
+    ReconnectEvent, RedisValue, Result, RetryPolicy, Script, Ttl, TxnBuilder, ZAddCondition,
+    ZRangeOptions, TRANSACTION_ATTEMPTS,
+};
+pub use crate::proto::frame::Frame;
+
+#[cfg(feature = "json")]
+pub use crate::core::Json;
 
 #[cfg(feature = "cluster")]
 pub use crate::cluster::key_slot;
 #[cfg(feature = "cluster")]
-pub use crate::cluster::ClusterClient;
+pub use crate::cluster::ClusterPipeline;
+#[cfg(feature = "cluster")]
+pub use crate::cluster::PoolStats;
+#[cfg(feature = "cluster")]
+pub use crate::cluster::{ClusterClient, ClusterStats};
+#[cfg(feature = "cluster")]
+pub use crate::cluster::{ClusterTopology, NodeFlags, NodeId, NodeInfo, SlotRange};
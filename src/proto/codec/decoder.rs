@@ -1,19 +1,57 @@
+use std::fmt;
+
 use bytes::Buf;
 use bytes::BytesMut;
 
+use crate::proto::error::Error;
 use crate::proto::frame::Frame;
 
 const DEFAULT_MAX_FRAME_SIZE: usize = 512 * 1024 * 1024; // 512 MB default
 
+/// Number of bytes shown on each side of a decode error position.
+const ERROR_CONTEXT_BYTES: usize = 8;
+
+/// The start of a reply decoded by [`Decoder::decode_stream_start`].
+#[derive(Debug, PartialEq)]
+pub enum StreamStart {
+    /// The header of an array with this many elements. The elements follow and
+    /// are decoded one at a time with [`Decoder::decode`].
+    Array(usize),
+    /// Any reply that is not a non-nil array, decoded as a whole.
+    Frame(Frame),
+}
+
 /// A RESP decoder that converts bytes to [`Frame`] types.
 ///
 /// The decoder handles streaming input and can decode frames incrementally.
 /// Call [`append`](Decoder::append) to add data, then [`decode`](Decoder::decode)
 /// to parse frames. Returns `Ok(None)` when more data is needed.
+///
+/// Malformed input yields [`Error::Protocol`] with the byte offset in the stream
+/// and a hex dump of the surrounding bytes.
 #[derive(Debug)]
 pub struct Decoder {
     buf: BytesMut,
     max_frame_size: usize,
+    /// Total number of bytes consumed since the decoder was created.
+    consumed: usize,
+    /// How far the incomplete frame at the front of the buffer has been scanned.
+    scan: Option<ScanState>,
+    /// Number of frames scanned or parsed, to check decoding stays linear.
+    #[cfg(test)]
+    steps: std::cell::Cell<usize>,
+}
+
+/// Progress of the scan for the end of an incomplete frame.
+///
+/// Kept across [`Decoder::decode`] calls so each byte of a frame that arrives in
+/// many reads is scanned once, not once per read.
+#[derive(Debug, Default)]
+struct ScanState {
+    /// Position of the next frame header to scan.
+    pos: usize,
+    /// Number of child frames still expected by each open aggregate, innermost last.
+    pending: Vec<usize>,
 }
 
 impl Decoder {
@@ -31,6 +69,10 @@ impl Decoder {
         Self {
             buf: BytesMut::new(),
             max_frame_size,
+            consumed: 0,
+            scan: None,
+            #[cfg(test)]
+            steps: std::cell::Cell::new(0),
         }
     }
 
@@ -56,151 +98,497 @@ impl Decoder {
     /// Returns `Ok(None)` if more data is needed.
     /// Returns `Err(...)` if the data is malformed.
     ///
+    /// Input is only consumed once a whole frame is available, so a frame split
+    /// across several [`append`](Decoder::append) calls decodes the same as one
+    /// appended at once, wherever the split falls. Until then the buffered bytes
+    /// are only scanned, resuming where the previous call stopped, and the frame
+    /// is built once.
+    ///
     /// # Returns
     ///
     /// Decoded frame, None if incomplete, or error
-    pub fn decode(&mut self) -> Result<Option<Frame>, String> {
+    pub fn decode(&mut self) -> Result<Option<Frame>, Error> {
         if self.buf.is_empty() {
             return Ok(None);
         }
 
         // Check if buffer size exceeds max allowed frame size
         if self.buf.len() > self.max_frame_size {
-            return Err("Buffer size exceeded maximum frame size".to_string());
+            return Err(self.error_at(0, "Buffer size exceeded maximum frame size"));
+        }
+
+        if self.scan_frame_end()?.is_none() {
+            return Ok(None);
+        }
+        match self.parse_frame(0)? {
+            Some((frame, next)) => {
+                self.advance(next);
+                Ok(Some(frame))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Finds the end of the frame at the front of the buffer without building it.
+    ///
+    /// Resumes from the state saved by the previous call if that frame was still
+    /// incomplete.
+    ///
+    /// # Returns
+    ///
+    /// The position just past the frame, None if incomplete, or error
+    fn scan_frame_end(&mut self) -> Result<Option<usize>, Error> {
+        let mut state = self.scan.take().unwrap_or_default();
+        loop {
+            let (next, children) = match self.skip_frame(state.pos)? {
+                Some(skipped) => skipped,
+                None => {
+                    self.scan = Some(state);
+                    return Ok(None);
+                }
+            };
+            state.pos = next;
+            if let Some(remaining) = state.pending.last_mut() {
+                *remaining -= 1;
+            }
+            if children > 0 {
+                state.pending.push(children);
+            }
+            while state.pending.last() == Some(&0) {
+                state.pending.pop();
+            }
+            if state.pending.is_empty() {
+                return Ok(Some(state.pos));
+            }
         }
+    }
 
-        let frame = match self.buf[0] {
-            b'+' => self.decode_simple_string(),
-            b'-' => self.decode_error(),
-            b':' => self.decode_integer(),
-            b'$' => self.decode_bulk_string(),
-            b'*' => self.decode_array(),
-            _ => Err(format!("unknown frame type: {}", self.buf[0] as char)),
+    /// Skips the scalar frame or aggregate header at `pos`.
+    ///
+    /// # Returns
+    ///
+    /// The position just past it and the number of child frames that follow,
+    /// None if incomplete, or error
+    fn skip_frame(&self, pos: usize) -> Result<Option<(usize, usize)>, Error> {
+        #[cfg(test)]
+        self.steps.set(self.steps.get() + 1);
+
+        let kind = match self.buf.get(pos) {
+            Some(kind) => *kind,
+            None => return Ok(None),
+        };
+        match kind {
+            b'+' | b'-' | b':' | b'$' | b'*' => {}
+            #[cfg(feature = "resp3")]
+            b'_' | b'#' | b',' | b'(' | b'=' | b'%' | b'~' | b'>' => {}
+            byte => return Err(self.error_at(pos, format!("unknown frame type: {}", byte as char))),
+        }
+        let end = match self.find_crlf(pos) {
+            Some(end) => end,
+            None => return Ok(None),
         };
 
-        match frame {
-            Ok(Some(frame)) => Ok(Some(frame)),
-            Ok(None) => Ok(None),
-            Err(e) => Err(e),
+        match kind {
+            b'$' => Ok(self.bulk_end(pos, end)?.map(|next| (next, 0))),
+            #[cfg(feature = "resp3")]
+            b'=' => Ok(self.bulk_end(pos, end)?.map(|next| (next, 0))),
+            b'*' => Ok(Some((end + 2, self.array_len(pos, end)?.unwrap_or(0)))),
+            #[cfg(feature = "resp3")]
+            b'%' => Ok(self
+                .parse_aggregate_len(pos, 2)?
+                .map(|(len, next)| (next, len * 2))),
+            #[cfg(feature = "resp3")]
+            b'~' | b'>' => Ok(self
+                .parse_aggregate_len(pos, 1)?
+                .map(|(len, next)| (next, len))),
+            _ => Ok(Some((end + 2, 0))),
+        }
+    }
+
+    /// Returns the position just past the bulk string whose header ends at `end`,
+    /// or None if its data has not fully arrived.
+    fn bulk_end(&self, pos: usize, end: usize) -> Result<Option<usize>, Error> {
+        let data_start = end + 2;
+        match self.bulk_len(pos, end)? {
+            Some(len) if self.buf.len() < data_start + len + 2 => Ok(None),
+            Some(len) => Ok(Some(data_start + len + 2)),
+            None => Ok(Some(data_start)),
+        }
+    }
+
+    /// Decodes every complete frame currently in the buffer.
+    ///
+    /// Stops at the first incomplete frame, whose bytes stay buffered until more
+    /// data is [`append`](Decoder::append)ed. Useful when several pipelined
+    /// replies arrive in one read.
+    ///
+    /// # Returns
+    ///
+    /// The decoded frames in order, possibly none, or the first decode error
+    pub fn decode_all(&mut self) -> Result<Vec<Frame>, Error> {
+        let mut frames = Vec::new();
+        while let Some(frame) = self.decode()? {
+            frames.push(frame);
         }
+        Ok(frames)
     }
 
-    fn decode_simple_string(&mut self) -> Result<Option<Frame>, String> {
-        let end = match self.find_crlf() {
+    /// Attempts to decode the start of a reply whose array elements are streamed.
+    ///
+    /// For an array, only the header is consumed and its length returned; the
+    /// elements can then be decoded one by one, so the whole array never has to be
+    /// held in memory. Any other reply (including a nil array) is decoded whole.
+    ///
+    /// # Returns
+    ///
+    /// The reply start, None if incomplete, or error
+    pub fn decode_stream_start(&mut self) -> Result<Option<StreamStart>, Error> {
+        if self.buf.first() != Some(&b'*') {
+            return Ok(self.decode()?.map(StreamStart::Frame));
+        }
+
+        let end = match self.find_crlf(0) {
             Some(end) => end,
             None => return Ok(None),
         };
-        if end == 1 {
-            return Ok(Some(Frame::SimpleString(Vec::new())));
+        let len = self.parse_number::<isize>(0, end)?;
+        if len == -1 {
+            self.advance(end + 2);
+            return Ok(Some(StreamStart::Frame(Frame::Null)));
         }
-        let data = self.buf[1..end].to_vec();
-        self.buf.advance(end + 2);
-        Ok(Some(Frame::SimpleString(data)))
+
+        let len = usize::try_from(len)
+            .ok()
+            .filter(|len| *len <= self.max_frame_size / 16)
+            .ok_or_else(|| self.error_at(0, "Array length exceeds reasonable maximum"))?;
+        self.advance(end + 2);
+        Ok(Some(StreamStart::Array(len)))
     }
 
-    fn decode_error(&mut self) -> Result<Option<Frame>, String> {
-        let end = match self.find_crlf() {
+    /// Parses the frame starting at `pos` without consuming any input.
+    ///
+    /// Returns the frame and the position just past it, or `None` if the frame
+    /// is incomplete.
+    fn parse_frame(&self, pos: usize) -> Result<Option<(Frame, usize)>, Error> {
+        #[cfg(test)]
+        self.steps.set(self.steps.get() + 1);
+
+        if pos >= self.buf.len() {
+            return Ok(None);
+        }
+
+        match self.buf[pos] {
+            b'+' => self.parse_simple_string(pos),
+            b'-' => self.parse_error(pos),
+            b':' => self.parse_integer(pos),
+            b'$' => self.parse_bulk_string(pos),
+            b'*' => self.parse_array(pos),
+            #[cfg(feature = "resp3")]
+            b'_' => self.parse_line(pos, |_| Ok(Frame::Null)),
+            #[cfg(feature = "resp3")]
+            b'#' => self.parse_line(pos, |line| match line {
+                b"t" => Ok(Frame::Boolean(true)),
+                b"f" => Ok(Frame::Boolean(false)),
+                _ => Err("invalid boolean".to_string()),
+            }),
+            #[cfg(feature = "resp3")]
+            b',' => self.parse_line(pos, parse_double),
+            #[cfg(feature = "resp3")]
+            b'(' => self.parse_line(pos, |digits| Ok(Frame::BigNumber(digits.to_vec()))),
+            #[cfg(feature = "resp3")]
+            b'=' => self.parse_verbatim_string(pos),
+            #[cfg(feature = "resp3")]
+            b'%' => self.parse_map(pos),
+            #[cfg(feature = "resp3")]
+            b'~' => self.parse_aggregate(pos, Frame::Set),
+            #[cfg(feature = "resp3")]
+            b'>' => self.parse_aggregate(pos, Frame::Push),
+            byte => Err(self.error_at(pos, format!("unknown frame type: {}", byte as char))),
+        }
+    }
+
+    fn parse_simple_string(&self, pos: usize) -> Result<Option<(Frame, usize)>, Error> {
+        let end = match self.find_crlf(pos) {
+            Some(end) => end,
+            None => return Ok(None),
+        };
+        let data = self.buf[pos + 1..end].to_vec();
+        Ok(Some((Frame::SimpleString(data), end + 2)))
+    }
+
+    fn parse_error(&self, pos: usize) -> Result<Option<(Frame, usize)>, Error> {
+        let end = match self.find_crlf(pos) {
             Some(end) => end,
             None => return Ok(None),
         };
-        let data = self.buf[1..end].to_vec();
-        self.buf.advance(end + 2);
-        Ok(Some(Frame::Error(data)))
+        let data = self.buf[pos + 1..end].to_vec();
+        Ok(Some((Frame::Error(data), end + 2)))
     }
 
-    fn decode_integer(&mut self) -> Result<Option<Frame>, String> {
-        let end = match self.find_crlf() {
+    fn parse_integer(&self, pos: usize) -> Result<Option<(Frame, usize)>, Error> {
+        let end = match self.find_crlf(pos) {
             Some(end) => end,
             None => return Ok(None),
         };
-        let data = self.buf[1..end].to_vec();
-        let num = String::from_utf8(data)
-            .map_err(|e| e.to_string())?
-            .parse::<i64>()
-            .map_err(|e| e.to_string())?;
-        self.buf.advance(end + 2);
-        Ok(Some(Frame::Integer(num)))
+        let num = self.parse_number::<i64>(pos, end)?;
+        Ok(Some((Frame::Integer(num), end + 2)))
     }
 
-    fn decode_bulk_string(&mut self) -> Result<Option<Frame>, String> {
-        let end = match self.find_crlf() {
+    fn parse_bulk_string(&self, pos: usize) -> Result<Option<(Frame, usize)>, Error> {
+        let end = match self.find_crlf(pos) {
             Some(end) => end,
             None => return Ok(None),
         };
-        let len_str = String::from_utf8(self.buf[1..end].to_vec()).map_err(|e| e.to_string())?;
-        let len: isize = len_str.parse::<isize>().map_err(|e| e.to_string())?;
-        self.buf.advance(end + 2);
+        let data_start = end + 2;
+        let len = match self.bulk_len(pos, end)? {
+            Some(len) => len,
+            None => return Ok(Some((Frame::BulkString(None), data_start))),
+        };
+
+        let data_end = data_start + len;
+        if self.buf.len() < data_end + 2 {
+            return Ok(None);
+        }
+
+        if &self.buf[data_end..data_end + 2] != b"\r\n" {
+            return Err(self.error_at(data_end, "bulk string not terminated by CRLF"));
+        }
+
+        let data = self.buf[data_start..data_end].to_vec().into();
+        Ok(Some((Frame::BulkString(Some(data)), data_end + 2)))
+    }
 
+    /// Parses the length of the bulk string at `pos`; None for a nil bulk string.
+    fn bulk_len(&self, pos: usize, end: usize) -> Result<Option<usize>, Error> {
+        let len = self.parse_number::<isize>(pos, end)?;
         if len == -1 {
-            return Ok(Some(Frame::BulkString(None)));
+            return Ok(None);
         }
 
-        let len = len as usize;
+        let len = usize::try_from(len)
+            .map_err(|_| self.error_at(pos, "negative bulk string length other than -1"))?;
 
         // Check if the declared length exceeds our max frame size
         if len > self.max_frame_size {
-            return Err("Bulk string length exceeds maximum frame size".to_string());
+            return Err(self.error_at(pos, "Bulk string length exceeds maximum frame size"));
         }
+        Ok(Some(len))
+    }
 
-        if self.buf.len() < len + 2 {
+    /// Parses the length of the array at `pos`; None for a nil array.
+    fn array_len(&self, pos: usize, end: usize) -> Result<Option<usize>, Error> {
+        let len = self.parse_number::<isize>(pos, end)?;
+        if len == -1 {
             return Ok(None);
         }
 
-        let data = self.buf[..len].to_vec().into();
-        self.buf.advance(len + 2);
-        Ok(Some(Frame::BulkString(Some(data))))
+        let len = usize::try_from(len)
+            .map_err(|_| self.error_at(pos, "negative array length other than -1"))?;
+
+        // Check if the array length is reasonable
+        if len > self.max_frame_size / 16 {
+            // Assume minimum 16 bytes per item
+            return Err(self.error_at(pos, "Array length exceeds reasonable maximum"));
+        }
+        Ok(Some(len))
     }
 
-    fn decode_array(&mut self) -> Result<Option<Frame>, String> {
-        let end = match self.find_crlf() {
+    fn parse_array(&self, pos: usize) -> Result<Option<(Frame, usize)>, Error> {
+        let end = match self.find_crlf(pos) {
             Some(end) => end,
             None => return Ok(None),
         };
-        let len_str = String::from_utf8(self.buf[1..end].to_vec()).map_err(|e| e.to_string())?;
-        let len: isize = len_str.parse::<isize>().map_err(|e| e.to_string())?;
-        self.buf.advance(end + 2);
+        let mut next = end + 2;
+        let len = match self.array_len(pos, end)? {
+            Some(len) => len,
+            None => return Ok(Some((Frame::Null, next))),
+        };
 
-        if len == -1 {
-            return Ok(Some(Frame::Null));
+        let mut items = Vec::with_capacity(len);
+        for _ in 0..len {
+            match self.parse_frame(next)? {
+                Some((frame, after)) => {
+                    items.push(frame);
+                    next = after;
+                }
+                None => return Ok(None),
+            }
         }
 
-        let len = len as usize;
+        Ok(Some((Frame::Array(items), next)))
+    }
 
-        // Check if the array length is reasonable
-        if len > self.max_frame_size / 16 {
-            // Assume minimum 16 bytes per item
-            return Err("Array length exceeds reasonable maximum".to_string());
+    /// Parses a single-line RESP3 frame whose body is converted by `convert`.
+    #[cfg(feature = "resp3")]
+    fn parse_line<F>(&self, pos: usize, convert: F) -> Result<Option<(Frame, usize)>, Error>
+    where
+        F: FnOnce(&[u8]) -> Result<Frame, String>,
+    {
+        let end = match self.find_crlf(pos) {
+            Some(end) => end,
+            None => return Ok(None),
+        };
+        let frame = convert(&self.buf[pos + 1..end]).map_err(|e| self.error_at(pos + 1, e))?;
+        Ok(Some((frame, end + 2)))
+    }
+
+    #[cfg(feature = "resp3")]
+    fn parse_verbatim_string(&self, pos: usize) -> Result<Option<(Frame, usize)>, Error> {
+        let (text, next) = match self.parse_bulk_string(pos)? {
+            Some((Frame::BulkString(Some(text)), next)) => (text, next),
+            Some(_) => return Err(self.error_at(pos, "verbatim string cannot be null")),
+            None => return Ok(None),
+        };
+        if text.len() < 4 || text[3] != b':' {
+            return Err(self.error_at(pos, "verbatim string missing format prefix"));
         }
+        let format = [text[0], text[1], text[2]];
+        let frame = Frame::VerbatimString {
+            format,
+            text: text.slice(4..),
+        };
+        Ok(Some((frame, next)))
+    }
+
+    /// Parses the element count of an aggregate frame, checking it against the limit.
+    #[cfg(feature = "resp3")]
+    fn parse_aggregate_len(
+        &self,
+        pos: usize,
+        elements_per_entry: usize,
+    ) -> Result<Option<(usize, usize)>, Error> {
+        let end = match self.find_crlf(pos) {
+            Some(end) => end,
+            None => return Ok(None),
+        };
+        let len = self.parse_number::<usize>(pos, end)?;
+        if len.saturating_mul(elements_per_entry) > self.max_frame_size / 16 {
+            return Err(self.error_at(pos, "Aggregate length exceeds reasonable maximum"));
+        }
+        Ok(Some((len, end + 2)))
+    }
+
+    /// Parses a set or push frame, built from its elements by `build`.
+    #[cfg(feature = "resp3")]
+    fn parse_aggregate(
+        &self,
+        pos: usize,
+        build: fn(Vec<Frame>) -> Frame,
+    ) -> Result<Option<(Frame, usize)>, Error> {
+        let (len, mut next) = match self.parse_aggregate_len(pos, 1)? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
 
         let mut items = Vec::with_capacity(len);
         for _ in 0..len {
-            match self.decode()? {
-                Some(frame) => items.push(frame),
+            match self.parse_frame(next)? {
+                Some((frame, after)) => {
+                    items.push(frame);
+                    next = after;
+                }
                 None => return Ok(None),
             }
         }
 
-        Ok(Some(Frame::Array(items)))
+        Ok(Some((build(items), next)))
+    }
+
+    #[cfg(feature = "resp3")]
+    fn parse_map(&self, pos: usize) -> Result<Option<(Frame, usize)>, Error> {
+        let (len, mut next) = match self.parse_aggregate_len(pos, 2)? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+
+        let mut pairs = Vec::with_capacity(len);
+        for _ in 0..len {
+            let (key, after_key) = match self.parse_frame(next)? {
+                Some(parsed) => parsed,
+                None => return Ok(None),
+            };
+            let (value, after_value) = match self.parse_frame(after_key)? {
+                Some(parsed) => parsed,
+                None => return Ok(None),
+            };
+            pairs.push((key, value));
+            next = after_value;
+        }
+
+        Ok(Some((Frame::Map(pairs), next)))
+    }
+
+    /// Parses the number between the type byte at `pos` and the CRLF at `end`.
+    fn parse_number<T>(&self, pos: usize, end: usize) -> Result<T, Error>
+    where
+        T: std::str::FromStr,
+        T::Err: fmt::Display,
+    {
+        std::str::from_utf8(&self.buf[pos + 1..end])
+            .map_err(|e| self.error_at(pos + 1, e))?
+            .parse::<T>()
+            .map_err(|e| self.error_at(pos + 1, e))
+    }
+
+    /// Discards `n` bytes from the front of the buffer.
+    fn advance(&mut self, n: usize) {
+        self.buf.advance(n);
+        self.consumed += n;
+        self.scan = None;
+    }
+
+    /// Builds a protocol error for the byte at `pos` in the current buffer.
+    ///
+    /// The message carries the offset from the start of the stream and a hex dump
+    /// of the bytes around it.
+    fn error_at(&self, pos: usize, reason: impl fmt::Display) -> Error {
+        let start = pos.saturating_sub(ERROR_CONTEXT_BYTES).min(self.buf.len());
+        let end = (pos + ERROR_CONTEXT_BYTES).min(self.buf.len());
+        let snippet = self.buf[start..end]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(" ");
+        Error::Protocol {
+            message: format!(
+                "{} at byte offset {} (near: {})",
+                reason,
+                self.consumed + pos,
+                snippet
+            ),
+        }
     }
 
-    /// Searches for the next CRLF sequence in the buffer.
+    /// Searches for the next CRLF sequence after the type byte at `pos`.
     ///
     /// # Returns
     ///
-    /// Some(index) if found, None if not enough data
-    fn find_crlf(&self) -> Option<usize> {
-        if self.buf.len() < 2 {
+    /// Some(index) of the `\r` if found, None if not enough data
+    fn find_crlf(&self, pos: usize) -> Option<usize> {
+        let from = pos + 1;
+        if self.buf.len() < from + 2 {
             return None;
         }
-        for i in 1..self.buf.len() {
-            if self.buf[i - 1] == b'\r' && self.buf[i] == b'\n' {
-                return Some(i - 1);
-            }
-        }
-        None
+        self.buf[from..]
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .map(|i| from + i)
     }
 }
 
+/// Parses the body of a RESP3 double, including `inf`, `-inf` and `nan`.
+#[cfg(feature = "resp3")]
+fn parse_double(line: &[u8]) -> Result<Frame, String> {
+    let text = std::str::from_utf8(line).map_err(|e| e.to_string())?;
+    let value = match text {
+        "inf" => f64::INFINITY,
+        "-inf" => f64::NEG_INFINITY,
+        "nan" => f64::NAN,
+        _ => text.parse::<f64>().map_err(|e| e.to_string())?,
+    };
+    Ok(Frame::Double(value))
+}
+
 impl Default for Decoder {
     fn default() -> Self {
         Self::new()
@@ -275,6 +663,29 @@ mod tests {
         assert_eq!(frame, Frame::Null);
     }
 
+    #[test]
+    fn test_decode_legacy_nils_keep_their_kind() {
+        let mut decoder = Decoder::new();
+        decoder.append(b"$-1\r\n*-1\r\n*0\r\n$0\r\n\r\n");
+        assert_eq!(decoder.decode().unwrap().unwrap(), Frame::BulkString(None));
+        assert_eq!(decoder.decode().unwrap().unwrap(), Frame::Null);
+        assert_eq!(decoder.decode().unwrap().unwrap(), Frame::Array(vec![]));
+        assert_eq!(
+            decoder.decode().unwrap().unwrap(),
+            Frame::BulkString(Some(Bytes::new()))
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_other_negative_lengths() {
+        for input in [&b"$-2\r\n"[..], b"*-5\r\n"] {
+            let mut decoder = Decoder::new();
+            decoder.append(input);
+            let err = decoder.decode().unwrap_err();
+            assert!(err.to_string().contains("negative"), "{}", err);
+        }
+    }
+
     #[test]
     fn test_decode_partial() {
         let mut decoder = Decoder::new();
@@ -300,6 +711,7 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
+            .to_string()
             .contains("Bulk string length exceeds maximum"));
     }
 
@@ -314,6 +726,7 @@ mod tests {
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
+            .to_string()
             .contains("Array length exceeds reasonable maximum"));
     }
 
@@ -328,6 +741,196 @@ mod tests {
         // Should detect overflow during decode
         let result = decoder.decode();
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Buffer size exceeded maximum"));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Buffer size exceeded maximum"));
+    }
+
+    #[test]
+    fn test_decode_stream_start_yields_elements_one_by_one() {
+        let mut decoder = Decoder::new();
+        decoder.append(b"*3\r\n$1\r\na\r\n$1\r");
+        assert_eq!(
+            decoder.decode_stream_start().unwrap(),
+            Some(StreamStart::Array(3))
+        );
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(Frame::BulkString(Some("a".into())))
+        );
+        assert_eq!(decoder.decode().unwrap(), None);
+        decoder.append(b"\nb\r\n:3\r\n+OK\r\n");
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(Frame::BulkString(Some("b".into())))
+        );
+        assert_eq!(decoder.decode().unwrap(), Some(Frame::Integer(3)));
+        assert_eq!(
+            decoder.decode_stream_start().unwrap(),
+            Some(StreamStart::Frame(Frame::SimpleString(b"OK".to_vec())))
+        );
+
+        decoder.append(b"*-1\r\n*0\r\n-ERR x\r\n*2");
+        assert_eq!(
+            decoder.decode_stream_start().unwrap(),
+            Some(StreamStart::Frame(Frame::Null))
+        );
+        assert_eq!(
+            decoder.decode_stream_start().unwrap(),
+            Some(StreamStart::Array(0))
+        );
+        assert_eq!(
+            decoder.decode_stream_start().unwrap(),
+            Some(StreamStart::Frame(Frame::Error(b"ERR x".to_vec())))
+        );
+        assert_eq!(decoder.decode_stream_start().unwrap(), None);
+    }
+
+    #[test]
+    fn test_decode_error_reports_offset() {
+        let mut decoder = Decoder::new();
+        decoder.append(b"+OK\r\n$3\r\nfooXY");
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(Frame::SimpleString(b"OK".to_vec()))
+        );
+
+        // The terminator after "foo" sits at stream offset 5 + 4 + 3 = 12
+        let message = match decoder.decode() {
+            Err(Error::Protocol { message }) => message,
+            other => panic!("expected protocol error, got {:?}", other),
+        };
+        assert!(message.contains("not terminated by CRLF"), "{}", message);
+        assert!(message.contains("at byte offset 12"), "{}", message);
+        assert!(message.contains("66 6f 6f 58 59"), "{}", message);
+    }
+
+    #[test]
+    fn test_decode_invalid_length_reports_offset() {
+        let mut decoder = Decoder::new();
+        decoder.append(b"*1\r\n$x3\r\nfoo\r\n");
+        let message = decoder.decode().unwrap_err().to_string();
+        assert!(message.contains("at byte offset 5"), "{}", message);
+    }
+
+    #[test]
+    fn test_decode_all_keeps_partial_frame() {
+        let mut decoder = Decoder::new();
+        decoder.append(b"+OK\r\n:42\r\n$5\r\nhel");
+
+        let frames = decoder.decode_all().unwrap();
+        assert_eq!(
+            frames,
+            vec![Frame::SimpleString(b"OK".to_vec()), Frame::Integer(42)]
+        );
+        assert!(decoder.decode_all().unwrap().is_empty());
+
+        decoder.append(b"lo\r\n");
+        assert_eq!(
+            decoder.decode_all().unwrap(),
+            vec![Frame::BulkString(Some(Bytes::from("hello")))]
+        );
+    }
+
+    #[test]
+    fn test_decode_large_array_in_chunks_is_linear() {
+        const ELEMENTS: usize = 100_000;
+        let mut input = format!("*{}\r\n", ELEMENTS).into_bytes();
+        for i in 0..ELEMENTS {
+            let element = format!("value-{}", i);
+            input.extend_from_slice(format!("${}\r\n{}\r\n", element.len(), element).as_bytes());
+        }
+
+        let mut decoder = Decoder::new();
+        let mut frame = None;
+        let chunks = input.chunks(4096).count();
+        for chunk in input.chunks(4096) {
+            assert!(frame.is_none());
+            decoder.append(chunk);
+            frame = decoder.decode().unwrap();
+        }
+
+        match frame {
+            Some(Frame::Array(items)) => {
+                assert_eq!(items.len(), ELEMENTS);
+                assert_eq!(
+                    items[ELEMENTS - 1],
+                    Frame::BulkString(Some(Bytes::from("value-99999")))
+                );
+            }
+            other => panic!("unexpected frame: {:?}", other),
+        }
+        // Each element is scanned once, plus one retry per read, and parsed once
+        let bound = 2 * (ELEMENTS + 1) + chunks;
+        assert!(
+            decoder.steps.get() <= bound,
+            "{} > {}",
+            decoder.steps.get(),
+            bound
+        );
+    }
+
+    #[test]
+    fn test_decode_split_bulk_string_in_array() {
+        let mut decoder = Decoder::new();
+        decoder.append(b"*2\r\n$3\r\nfo");
+        assert!(decoder.decode().unwrap().is_none());
+        decoder.append(b"o\r\n$3\r\nbar\r\n");
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(Frame::Array(vec![
+                Frame::BulkString(Some(Bytes::from("foo"))),
+                Frame::BulkString(Some(Bytes::from("bar"))),
+            ]))
+        );
+        assert!(decoder.decode().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_decode_nested_array_one_byte_at_a_time() {
+        let mut input = Vec::new();
+        input.extend_from_slice(b"*4\r\n");
+        input.extend_from_slice(b"*3\r\n$5\r\nhello\r\n:-42\r\n$-1\r\n");
+        input.extend_from_slice(b"*2\r\n*1\r\n+OK\r\n*0\r\n");
+        input.extend_from_slice(b"-ERR boom\r\n");
+        input.extend_from_slice(b"$12\r\nsplit\r\nbytes\r\n");
+        input.extend_from_slice(b"+\r\n");
+
+        let mut whole = Decoder::new();
+        whole.append(&input);
+        let expected = whole.decode().unwrap().unwrap();
+        assert_eq!(
+            whole.decode().unwrap(),
+            Some(Frame::SimpleString(Vec::new()))
+        );
+        assert!(whole.decode().unwrap().is_none());
+
+        let mut decoder = Decoder::new();
+        let mut frames = Vec::new();
+        for byte in &input {
+            decoder.append(&[*byte]);
+            while let Some(frame) = decoder.decode().unwrap() {
+                frames.push(frame);
+            }
+        }
+        assert_eq!(frames, vec![expected, Frame::SimpleString(Vec::new())]);
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_decode_resp3_null_and_split_map() {
+        let mut decoder = Decoder::new();
+        decoder.append(b"_\r\n%1\r\n+key\r\n,2.");
+        assert_eq!(decoder.decode().unwrap(), Some(Frame::Null));
+        assert!(decoder.decode().unwrap().is_none());
+        decoder.append(b"5\r\n");
+        assert_eq!(
+            decoder.decode().unwrap(),
+            Some(Frame::Map(vec![(
+                Frame::SimpleString(b"key".to_vec()),
+                Frame::Double(2.5)
+            )]))
+        );
     }
 }
@@ -2,10 +2,18 @@ use bytes::{BufMut, BytesMut};
 
 use crate::proto::frame::Frame;
 
+/// Largest buffer [`Encoder::reset`] keeps around between frames.
+const MAX_RETAINED_CAPACITY: usize = 64 * 1024;
+
 /// A RESP encoder that converts [`Frame`] types to bytes.
 ///
 /// The encoder accumulates data in an internal buffer and can be used
 /// to encode multiple frames sequentially.
+///
+/// The buffer stays owned by the encoder and is reused across frames: callers
+/// read the encoded bytes with [`buffer`](Encoder::buffer), write them out and
+/// then call [`reset`](Encoder::reset), which keeps the allocation for the next
+/// frame instead of allocating a new buffer each time.
 pub struct Encoder {
     buf: BytesMut,
 }
@@ -63,19 +71,106 @@ impl Encoder {
             Frame::Null => {
                 self.buf.extend_from_slice(b"$-1\r\n");
             }
+            #[cfg(feature = "resp3")]
+            Frame::Map(pairs) => {
+                self.encode_header(b'%', pairs.len());
+                for (key, value) in pairs {
+                    self.encode(key);
+                    self.encode(value);
+                }
+            }
+            #[cfg(feature = "resp3")]
+            Frame::Set(items) => {
+                self.encode_header(b'~', items.len());
+                for item in items {
+                    self.encode(item);
+                }
+            }
+            #[cfg(feature = "resp3")]
+            Frame::Double(d) => {
+                self.buf.put_u8(b',');
+                if d.is_nan() {
+                    self.buf.extend_from_slice(b"nan");
+                } else if d.is_infinite() {
+                    self.buf
+                        .extend_from_slice(if *d > 0.0 { b"inf" } else { b"-inf" });
+                } else {
+                    self.buf.extend_from_slice(d.to_string().as_bytes());
+                }
+                self.buf.extend_from_slice(b"\r\n");
+            }
+            #[cfg(feature = "resp3")]
+            Frame::Boolean(b) => {
+                self.buf
+                    .extend_from_slice(if *b { b"#t\r\n" } else { b"#f\r\n" });
+            }
+            #[cfg(feature = "resp3")]
+            Frame::BigNumber(digits) => {
+                self.buf.put_u8(b'(');
+                self.buf.extend_from_slice(digits);
+                self.buf.extend_from_slice(b"\r\n");
+            }
+            #[cfg(feature = "resp3")]
+            Frame::VerbatimString { format, text } => {
+                self.encode_header(b'=', format.len() + 1 + text.len());
+                self.buf.extend_from_slice(format);
+                self.buf.put_u8(b':');
+                self.buf.extend_from_slice(text);
+                self.buf.extend_from_slice(b"\r\n");
+            }
+            #[cfg(feature = "resp3")]
+            Frame::Push(items) => {
+                self.encode_header(b'>', items.len());
+                for item in items {
+                    self.encode(item);
+                }
+            }
         }
     }
 
+    /// Writes a type prefix followed by a length and CRLF.
+    #[cfg(feature = "resp3")]
+    fn encode_header(&mut self, prefix: u8, len: usize) {
+        self.buf.put_u8(prefix);
+        self.buf.extend_from_slice(len.to_string().as_bytes());
+        self.buf.extend_from_slice(b"\r\n");
+    }
+
     /// Takes the encoded data from the buffer, leaving it empty.
     ///
-    /// Unlike [`finish`](Encoder::finish), this method allows reusing the encoder.
+    /// The encoder can still be used afterwards, but has to allocate a new buffer.
     ///
     /// # Returns
     ///
     /// The accumulated bytes
+    #[cfg(test)]
     pub fn take(&mut self) -> BytesMut {
         std::mem::replace(&mut self.buf, BytesMut::new())
     }
+
+    /// Returns the encoded data without taking it out of the encoder.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Clears the buffer, keeping its allocation for the next frames.
+    ///
+    /// A buffer that grew past [`MAX_RETAINED_CAPACITY`] while encoding a large
+    /// frame is released instead, so one big value does not pin its memory for
+    /// the lifetime of the connection.
+    pub fn reset(&mut self) {
+        if self.buf.capacity() > MAX_RETAINED_CAPACITY {
+            self.buf = BytesMut::new();
+        } else {
+            self.buf.clear();
+        }
+    }
+
+    /// Returns the number of bytes the buffer can hold without reallocating.
+    #[cfg(test)]
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
 }
 
 impl Default for Encoder {
@@ -96,6 +191,41 @@ mod tests {
         assert_eq!(encoder.take().freeze().as_ref(), b"+OK\r\n");
     }
 
+    #[test]
+    fn test_reset_reuses_buffer() {
+        let mut encoder = Encoder::new();
+        let frame = Frame::Array(vec![
+            Frame::BulkString(Some(Bytes::from("GET"))),
+            Frame::BulkString(Some(Bytes::from("key"))),
+        ]);
+
+        for _ in 0..100 {
+            encoder.encode(&frame);
+            encoder.reset();
+        }
+        let capacity = encoder.capacity();
+        assert!(capacity > 0);
+
+        for _ in 0..10_000 {
+            encoder.encode(&frame);
+            assert_eq!(encoder.buffer(), b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n");
+            encoder.reset();
+            assert_eq!(encoder.capacity(), capacity);
+        }
+    }
+
+    #[test]
+    fn test_reset_releases_large_buffer() {
+        let mut encoder = Encoder::new();
+        encoder.encode(&Frame::BulkString(Some(Bytes::from(vec![
+            b'x';
+            MAX_RETAINED_CAPACITY
+        ]))));
+        encoder.reset();
+        assert!(encoder.buffer().is_empty());
+        assert!(encoder.capacity() <= MAX_RETAINED_CAPACITY);
+    }
+
     #[test]
     fn test_encode_error() {
         let mut encoder = Encoder::new();
@@ -143,4 +273,96 @@ mod tests {
         encoder.encode(&Frame::Null);
         assert_eq!(encoder.take().freeze().as_ref(), b"$-1\r\n");
     }
+
+    #[cfg(feature = "resp3")]
+    fn round_trip(frame: Frame, wire: &[u8]) {
+        let mut encoder = Encoder::new();
+        encoder.encode(&frame);
+        let encoded = encoder.take();
+        assert_eq!(encoded.as_ref(), wire);
+
+        let mut decoder = crate::proto::codec::Decoder::new();
+        decoder.append(&encoded);
+        assert_eq!(decoder.decode().unwrap(), Some(frame));
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_round_trip_map() {
+        round_trip(
+            Frame::Map(vec![
+                (Frame::SimpleString(b"first".to_vec()), Frame::Integer(1)),
+                (
+                    Frame::BulkString(Some(Bytes::from("second"))),
+                    Frame::Boolean(false),
+                ),
+            ]),
+            b"%2\r\n+first\r\n:1\r\n$6\r\nsecond\r\n#f\r\n",
+        );
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_round_trip_set() {
+        round_trip(
+            Frame::Set(vec![Frame::SimpleString(b"a".to_vec()), Frame::Integer(2)]),
+            b"~2\r\n+a\r\n:2\r\n",
+        );
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_round_trip_double() {
+        round_trip(Frame::Double(1.5), b",1.5\r\n");
+        round_trip(Frame::Double(-3.0), b",-3\r\n");
+        round_trip(Frame::Double(f64::INFINITY), b",inf\r\n");
+        round_trip(Frame::Double(f64::NEG_INFINITY), b",-inf\r\n");
+
+        let mut encoder = Encoder::new();
+        encoder.encode(&Frame::Double(f64::NAN));
+        let mut decoder = crate::proto::codec::Decoder::new();
+        decoder.append(&encoder.take());
+        assert!(matches!(decoder.decode().unwrap(), Some(Frame::Double(d)) if d.is_nan()));
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_round_trip_boolean() {
+        round_trip(Frame::Boolean(true), b"#t\r\n");
+        round_trip(Frame::Boolean(false), b"#f\r\n");
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_round_trip_big_number() {
+        round_trip(
+            Frame::BigNumber(b"3492890328409238509324850943850943825024385".to_vec()),
+            b"(3492890328409238509324850943850943825024385\r\n",
+        );
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_round_trip_verbatim_string() {
+        round_trip(
+            Frame::VerbatimString {
+                format: *b"txt",
+                text: Bytes::from("Some string"),
+            },
+            b"=15\r\ntxt:Some string\r\n",
+        );
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_round_trip_push() {
+        round_trip(
+            Frame::Push(vec![
+                Frame::BulkString(Some(Bytes::from("message"))),
+                Frame::BulkString(Some(Bytes::from("news"))),
+                Frame::BulkString(Some(Bytes::from("hi"))),
+            ]),
+            b">3\r\n$7\r\nmessage\r\n$4\r\nnews\r\n$2\r\nhi\r\n",
+        );
+    }
 }
@@ -13,5 +13,5 @@ pub mod decoder;
 /// Frame encoder.
 pub mod encoder;
 
-pub use decoder::Decoder;
+pub use decoder::{Decoder, StreamStart};
 pub use encoder::Encoder;
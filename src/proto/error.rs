@@ -42,6 +42,84 @@ pub enum Error {
         message: String,
     },
 
+    /// The operation did not complete within the allotted time.
+    #[error("operation timed out")]
+    Timeout,
+
+    /// Establishing a TLS session failed.
+    ///
+    /// Covers invalid server names and handshake failures such as an untrusted
+    /// certificate or a name mismatch; the message carries the rustls detail.
+    #[cfg(feature = "tls")]
+    #[error("TLS error: {message}")]
+    Tls {
+        /// Description of the failure.
+        message: String,
+    },
+
+    /// A write command was sent to a read-only replica.
+    ///
+    /// In Redis Cluster this typically happens right after a failover, before
+    /// the client has learned about the new master.
+    #[error("READONLY write command sent to a read-only replica")]
+    ReadOnly,
+
+    /// The server is still loading its dataset into memory (`LOADING`).
+    ///
+    /// Common right after a restart; the command succeeds once loading completes.
+    #[error("LOADING server is loading the dataset in memory")]
+    Loading,
+
+    /// A replica lost its link to the master and refuses to serve stale data
+    /// (`MASTERDOWN`).
+    #[error("MASTERDOWN link with the master is down")]
+    MasterDown,
+
+    /// An increment or decrement would overflow the 64-bit integer stored at the
+    /// key (`ERR increment or decrement would overflow`).
+    ///
+    /// The stored value is left unchanged.
+    #[error("increment or decrement would overflow")]
+    Overflow,
+
+    /// A command was refused client-side because the key holds another type.
+    ///
+    /// Only returned with strict type checking enabled, based on key types
+    /// remembered from earlier replies.
+    #[error("WRONGTYPE key {key} is known to hold a {actual} value")]
+    WrongType {
+        /// The key the command was issued on.
+        key: String,
+        /// The type the key was last seen holding (e.g. "string").
+        actual: String,
+    },
+
+    /// The request queue of the connection is full.
+    ///
+    /// Only returned when backpressure is disabled; otherwise callers wait for
+    /// room in the queue.
+    #[error("request queue is full")]
+    QueueFull,
+
+    /// A transaction was aborted on every attempt because a watched key changed.
+    #[error("transaction aborted after {attempts} attempts: watched keys kept changing")]
+    TransactionAborted {
+        /// The number of attempts made.
+        attempts: usize,
+    },
+
+    /// A write was acknowledged by fewer replicas than requested (`WAIT`).
+    ///
+    /// The value was written to the master regardless and may still be lost on
+    /// failover. Not retryable: writing it again does not make replicas catch up.
+    #[error("write acknowledged by {acked} of {required} replicas")]
+    NotReplicated {
+        /// The number of replicas that acknowledged the write.
+        acked: u64,
+        /// The number of replicas that were asked to acknowledge it.
+        required: u64,
+    },
+
     /// Encoding failed.
     #[error("encode error: {source}")]
     Encode {
@@ -87,6 +165,9 @@ pub enum Error {
     },
 
     /// Redis Cluster is down or unavailable.
+    ///
+    /// Usually transient (e.g. while a failover elects a new master), so it is
+    /// retryable.
     #[cfg(feature = "cluster")]
     #[error("CLUSTERDOWN cluster is down")]
     ClusterDown,
@@ -99,6 +180,132 @@ pub enum Error {
     #[cfg(feature = "cluster")]
     #[error("CROSSSLOT keys in multi-key operation map to different slots")]
     CrossSlot,
+
+    /// A multi-key command touched keys split across a slot being migrated.
+    ///
+    /// Redis returns `TRYAGAIN` while resharding is in progress; the command
+    /// succeeds once the migration of the keys completes.
+    #[cfg(feature = "cluster")]
+    #[error("TRYAGAIN multi-key command during slot migration")]
+    TryAgain,
+
+    /// A command failed; names the command alongside the underlying error.
+    ///
+    /// Added by [`Client`](crate::Client) when the server rejects a command, so
+    /// generic code can tell which command failed. Use [`Error::inner`] to match on
+    /// the underlying error.
+    #[error("{name} failed: {source}")]
+    Command {
+        /// The name of the failed command (e.g. "SET").
+        name: String,
+        /// The underlying error.
+        #[source]
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Attaches the name of the command that failed to this error.
+    ///
+    /// An error that already names a command is returned unchanged.
+    pub fn with_command(self, name: &str) -> Self {
+        match self {
+            Error::Command { .. } => self,
+            source => Error::Command {
+                name: name.to_string(),
+                source: Box::new(source),
+            },
+        }
+    }
+
+    /// Returns the name of the failed command, if known.
+    pub fn command(&self) -> Option<&str> {
+        match self {
+            Error::Command { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying error, without the command context.
+    pub fn inner(&self) -> &Error {
+        match self {
+            Error::Command { source, .. } => source.inner(),
+            _ => self,
+        }
+    }
+
+    /// Returns true if the operation may succeed when retried.
+    ///
+    /// Transient failures are connection resets, broken pipes and IO timeouts,
+    /// [`Error::Timeout`], [`Error::ReadOnly`] (the topology is likely stale),
+    /// [`Error::Loading`] and [`Error::MasterDown`] (the server is not ready yet),
+    /// [`Error::QueueFull`] (the connection is momentarily saturated) and,
+    /// in cluster mode, `Error::ClusterDown` (a failover is likely in progress) and
+    /// `Error::TryAgain` (a slot migration is in progress).
+    /// Configuration, protocol and server errors are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self.inner() {
+            Error::Io { source } => matches!(
+                source.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::TimedOut
+            ),
+            Error::Timeout
+            | Error::ReadOnly
+            | Error::Loading
+            | Error::MasterDown
+            | Error::QueueFull => true,
+            #[cfg(feature = "cluster")]
+            Error::ClusterDown | Error::TryAgain => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    /// Converts to an IO error for interop with IO-based APIs.
+    ///
+    /// IO errors are unwrapped; other errors are wrapped with the closest kind.
+    fn from(error: Error) -> Self {
+        let kind = match error.inner() {
+            Error::Timeout => io::ErrorKind::TimedOut,
+            Error::InvalidArgument { .. } => io::ErrorKind::InvalidInput,
+            Error::Protocol { .. } | Error::Decode { .. } => io::ErrorKind::InvalidData,
+            #[cfg(feature = "tls")]
+            Error::Tls { .. } => io::ErrorKind::InvalidData,
+            Error::Auth => io::ErrorKind::PermissionDenied,
+            _ => io::ErrorKind::Other,
+        };
+        match error {
+            Error::Io { source } => source,
+            error => io::Error::new(kind, error),
+        }
+    }
+}
+
+/// Error reply of `INCR`, `DECR`, `INCRBY`, `HINCRBY` and friends when the result
+/// does not fit in an i64.
+const OVERFLOW_MESSAGE: &str = "ERR increment or decrement would overflow";
+
+/// Converts an error reply to a typed error.
+///
+/// `READONLY`, `LOADING`, `MASTERDOWN` and integer overflows map to their
+/// dedicated variants; any other reply becomes [`Error::Server`] carrying the
+/// message.
+pub(crate) fn parse_server_error(message: &[u8]) -> Error {
+    let message = String::from_utf8_lossy(message);
+    let code = message.split(' ').next().unwrap_or_default();
+    match code {
+        "READONLY" => Error::ReadOnly,
+        "LOADING" => Error::Loading,
+        "MASTERDOWN" => Error::MasterDown,
+        "ERR" if message == OVERFLOW_MESSAGE => Error::Overflow,
+        _ => Error::Server {
+            message: message.into_owned(),
+        },
+    }
 }
 
 /// Error returned when frame encoding fails.
@@ -140,6 +347,40 @@ mod tests {
         assert!(error.to_string().contains("IO error"));
     }
 
+    #[test]
+    fn test_into_io_error() {
+        let source = io::Error::new(io::ErrorKind::ConnectionReset, "reset");
+        let converted: io::Error = Error::Io { source }.into();
+        assert_eq!(converted.kind(), io::ErrorKind::ConnectionReset);
+        assert_eq!(converted.to_string(), "reset");
+
+        let converted: io::Error = Error::Timeout.into();
+        assert_eq!(converted.kind(), io::ErrorKind::TimedOut);
+
+        let converted: io::Error = Error::Server {
+            message: "ERR boom".to_string(),
+        }
+        .with_command("SET")
+        .into();
+        assert_eq!(converted.kind(), io::ErrorKind::Other);
+        assert!(converted
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<Error>())
+            .is_some_and(|e| e.command() == Some("SET")));
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    fn test_error_display_tls() {
+        let error = Error::Tls {
+            message: "invalid peer certificate: UnknownIssuer".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "TLS error: invalid peer certificate: UnknownIssuer"
+        );
+    }
+
     #[test]
     fn test_error_display_protocol() {
         let error = Error::Protocol {
@@ -162,6 +403,12 @@ mod tests {
         assert_eq!(error.to_string(), "authentication failed");
     }
 
+    #[test]
+    fn test_error_display_timeout() {
+        let error = Error::Timeout;
+        assert_eq!(error.to_string(), "operation timed out");
+    }
+
     #[test]
     fn test_error_display_invalid_argument() {
         let error = Error::InvalidArgument {
@@ -173,6 +420,120 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_is_retryable_io_kinds() {
+        for kind in [
+            io::ErrorKind::ConnectionReset,
+            io::ErrorKind::ConnectionAborted,
+            io::ErrorKind::BrokenPipe,
+            io::ErrorKind::TimedOut,
+        ] {
+            let error = Error::Io {
+                source: io::Error::new(kind, "transient"),
+            };
+            assert!(error.is_retryable(), "{:?} should be retryable", kind);
+        }
+
+        for kind in [
+            io::ErrorKind::ConnectionRefused,
+            io::ErrorKind::InvalidInput,
+            io::ErrorKind::PermissionDenied,
+            io::ErrorKind::Other,
+        ] {
+            let error = Error::Io {
+                source: io::Error::new(kind, "fatal"),
+            };
+            assert!(!error.is_retryable(), "{:?} should not be retryable", kind);
+        }
+    }
+
+    #[test]
+    fn test_parse_server_error_loading() {
+        let error = parse_server_error(b"LOADING Redis is loading the dataset in memory");
+        assert!(matches!(error, Error::Loading));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_parse_server_error_masterdown() {
+        let error = parse_server_error(
+            b"MASTERDOWN Link with MASTER is down and replica-serve-stale-data is set to 'no'.",
+        );
+        assert!(matches!(error, Error::MasterDown));
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_parse_server_error_overflow() {
+        let error = parse_server_error(b"ERR increment or decrement would overflow");
+        assert!(matches!(error, Error::Overflow));
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_parse_server_error_generic() {
+        let error = parse_server_error(b"ERR LOADING is not a command");
+        assert!(
+            matches!(&error, Error::Server { message } if message == "ERR LOADING is not a command")
+        );
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_error_is_retryable_variants() {
+        assert!(Error::Timeout.is_retryable());
+        assert!(Error::ReadOnly.is_retryable());
+        assert!(Error::QueueFull.is_retryable());
+
+        assert!(!Error::Auth.is_retryable());
+        assert!(!Error::Protocol {
+            message: "bad frame".to_string()
+        }
+        .is_retryable());
+        assert!(!Error::Server {
+            message: "ERR wrong type".to_string()
+        }
+        .is_retryable());
+        assert!(!Error::InvalidArgument {
+            message: "bad address".to_string()
+        }
+        .is_retryable());
+    }
+
+    #[cfg(feature = "cluster")]
+    #[test]
+    fn test_error_is_retryable_cluster_variants() {
+        assert!(Error::ClusterDown.is_retryable());
+        assert!(Error::TryAgain.is_retryable());
+        assert!(!Error::CrossSlot.is_retryable());
+        assert!(!Error::Moved {
+            slot: 1,
+            address: "127.0.0.1:7000".to_string()
+        }
+        .is_retryable());
+    }
+
+    #[test]
+    fn test_error_with_command() {
+        let error = Error::Server {
+            message: "ERR wrong type".to_string(),
+        }
+        .with_command("SET");
+        assert_eq!(error.command(), Some("SET"));
+        assert!(matches!(error.inner(), Error::Server { message } if message == "ERR wrong type"));
+        assert_eq!(
+            error.to_string(),
+            "SET failed: server error: ERR wrong type"
+        );
+
+        // The first command name wins
+        let error = error.with_command("GET");
+        assert_eq!(error.command(), Some("SET"));
+        assert_eq!(Error::Auth.command(), None);
+
+        assert!(Error::Timeout.with_command("GET").is_retryable());
+    }
+
     #[test]
     fn test_encode_error_new() {
         let io_err = io::Error::new(io::ErrorKind::Other, "encode failed");
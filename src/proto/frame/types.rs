@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use bytes::Bytes;
 
 /// A RESP (Redis Serialization Protocol) frame.
@@ -9,7 +11,13 @@ use bytes::Bytes;
 /// - BulkString: Binary-safe string data
 /// - Array: Command arguments and array responses
 /// - Null: NULL value
+///
+/// With the `resp3` feature, the RESP3 types (maps, sets, doubles, booleans, big
+/// numbers, verbatim strings and pushes) are available as well. Since enabling a
+/// feature adds variants, the enum is `#[non_exhaustive]`: matches outside this
+/// crate need a wildcard arm.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum Frame {
     /// Simple string (+OK).
     SimpleString(Vec<u8>),
@@ -21,8 +29,226 @@ pub enum Frame {
     BulkString(Option<Bytes>),
     /// Array (*2\r\n...).
     Array(Vec<Frame>),
-    /// Null ($-1 or *-1).
+    /// Null ($-1 or *-1, and _ in RESP3).
     Null,
+    /// Map of key/value pairs (%2\r\n...), in wire order.
+    #[cfg(feature = "resp3")]
+    Map(Vec<(Frame, Frame)>),
+    /// Set (~2\r\n...).
+    #[cfg(feature = "resp3")]
+    Set(Vec<Frame>),
+    /// Double (,1.23).
+    #[cfg(feature = "resp3")]
+    Double(f64),
+    /// Boolean (#t or #f).
+    #[cfg(feature = "resp3")]
+    Boolean(bool),
+    /// Big number ((3492890328409238509324850943850943825024385), kept as its digits.
+    #[cfg(feature = "resp3")]
+    BigNumber(Vec<u8>),
+    /// Verbatim string (=15\r\ntxt:Some string) with its three-byte format.
+    #[cfg(feature = "resp3")]
+    VerbatimString {
+        /// The format, such as `txt` or `mkd`.
+        format: [u8; 3],
+        /// The string contents.
+        text: Bytes,
+    },
+    /// Out-of-band push data (>2\r\n...), e.g. Pub/Sub messages.
+    #[cfg(feature = "resp3")]
+    Push(Vec<Frame>),
+}
+
+impl Frame {
+    /// Returns the payload of a simple or bulk string.
+    ///
+    /// # Returns
+    ///
+    /// `None` for other variants and for a nil bulk string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use muxis::Frame;
+    ///
+    /// let frame = Frame::SimpleString(b"OK".to_vec());
+    /// assert_eq!(frame.as_bytes(), Some(&b"OK"[..]));
+    /// assert_eq!(Frame::Integer(1).as_bytes(), None);
+    /// ```
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Frame::SimpleString(s) => Some(s),
+            Frame::BulkString(Some(b)) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Returns the payload of a simple or bulk string as text.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`; valid payloads are borrowed.
+    ///
+    /// # Returns
+    ///
+    /// `None` for other variants and for a nil bulk string.
+    pub fn as_str(&self) -> Option<Cow<'_, str>> {
+        self.as_bytes().map(String::from_utf8_lossy)
+    }
+
+    /// Returns the value of an integer frame.
+    ///
+    /// # Returns
+    ///
+    /// `None` for other variants; numeric strings are not parsed.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Frame::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of an array frame.
+    ///
+    /// # Returns
+    ///
+    /// `None` for other variants.
+    pub fn as_array(&self) -> Option<&[Frame]> {
+        match self {
+            Frame::Array(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this frame is a nil reply ([`Frame::Null`] or a nil bulk string).
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Frame::Null | Frame::BulkString(None))
+    }
+
+    /// Looks up the value for `key` in a map reply.
+    ///
+    /// Works on a RESP3 map as well as on its RESP2 form, a flat array of alternating
+    /// keys and values (as returned by `HELLO` or `CONFIG GET`), so callers need not
+    /// care which protocol version is in use. Keys are compared as simple or bulk
+    /// strings.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the key is absent or the frame is not a map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use muxis::Frame;
+    ///
+    /// let reply = Frame::Array(vec![
+    ///     Frame::BulkString(Some(Bytes::from("server"))),
+    ///     Frame::BulkString(Some(Bytes::from("redis"))),
+    ///     Frame::BulkString(Some(Bytes::from("proto"))),
+    ///     Frame::Integer(2),
+    /// ]);
+    /// assert_eq!(reply.map_get("proto"), Some(&Frame::Integer(2)));
+    /// assert_eq!(reply.map_get("missing"), None);
+    /// ```
+    pub fn map_get(&self, key: &str) -> Option<&Frame> {
+        let key = key.as_bytes();
+        match self {
+            Frame::Array(items) => items
+                .chunks_exact(2)
+                .find(|pair| pair[0].as_bytes() == Some(key))
+                .map(|pair| &pair[1]),
+            #[cfg(feature = "resp3")]
+            Frame::Map(pairs) => pairs
+                .iter()
+                .find(|(k, _)| k.as_bytes() == Some(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Estimates the memory held by this frame, in bytes.
+    ///
+    /// Counts the frame itself plus its payload: string contents and, recursively,
+    /// the elements of aggregates. Allocator overhead, spare vector capacity and the
+    /// sharing of [`Bytes`] buffers are ignored, so this is an approximation suited to
+    /// budgeting (e.g. bounding a cache by size), not an exact measurement.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use muxis::Frame;
+    ///
+    /// let frame = Frame::BulkString(Some(Bytes::from(vec![0u8; 1024])));
+    /// assert!(frame.memory_usage() >= 1024);
+    /// ```
+    pub fn memory_usage(&self) -> usize {
+        let payload = match self {
+            Frame::SimpleString(s) | Frame::Error(s) => s.len(),
+            Frame::BulkString(Some(b)) => b.len(),
+            Frame::BulkString(None) | Frame::Integer(_) | Frame::Null => 0,
+            Frame::Array(items) => items.iter().map(Frame::memory_usage).sum(),
+            #[cfg(feature = "resp3")]
+            Frame::Set(items) | Frame::Push(items) => items.iter().map(Frame::memory_usage).sum(),
+            #[cfg(feature = "resp3")]
+            Frame::Map(pairs) => pairs
+                .iter()
+                .map(|(k, v)| k.memory_usage() + v.memory_usage())
+                .sum(),
+            #[cfg(feature = "resp3")]
+            Frame::BigNumber(digits) => digits.len(),
+            #[cfg(feature = "resp3")]
+            Frame::VerbatimString { text, .. } => text.len(),
+            #[cfg(feature = "resp3")]
+            Frame::Double(_) | Frame::Boolean(_) => 0,
+        };
+        std::mem::size_of::<Frame>() + payload
+    }
+
+    /// Returns true if this frame is an out-of-band RESP3 push.
+    ///
+    /// Always false without the `resp3` feature.
+    pub(crate) fn is_push(&self) -> bool {
+        #[cfg(feature = "resp3")]
+        {
+            matches!(self, Frame::Push(_))
+        }
+        #[cfg(not(feature = "resp3"))]
+        {
+            false
+        }
+    }
+
+    /// Converts the RESP3 types into the frames a server sends on protocol 2.
+    ///
+    /// Maps become flat key/value arrays, sets and pushes arrays, doubles, big
+    /// numbers and verbatim strings bulk strings, and booleans the integers 1 and
+    /// 0. Nested frames are converted too. Without the `resp3` feature every frame
+    /// is a RESP2 frame already and is returned unchanged.
+    pub(crate) fn into_resp2(self) -> Frame {
+        #[cfg(feature = "resp3")]
+        {
+            let bulk = |data: Vec<u8>| Frame::BulkString(Some(Bytes::from(data)));
+            let array = |items: Vec<Frame>| {
+                Frame::Array(items.into_iter().map(Frame::into_resp2).collect())
+            };
+            match self {
+                Frame::Array(items) | Frame::Set(items) | Frame::Push(items) => array(items),
+                Frame::Map(pairs) => Frame::Array(
+                    pairs
+                        .into_iter()
+                        .flat_map(|(key, value)| [key.into_resp2(), value.into_resp2()])
+                        .collect(),
+                ),
+                Frame::Double(n) => bulk(n.to_string().into_bytes()),
+                Frame::Boolean(b) => Frame::Integer(i64::from(b)),
+                Frame::BigNumber(digits) => bulk(digits),
+                Frame::VerbatimString { text, .. } => Frame::BulkString(Some(text)),
+                other => other,
+            }
+        }
+        #[cfg(not(feature = "resp3"))]
+        self
+    }
 }
 
 #[cfg(test)]
@@ -48,6 +274,8 @@ impl Frame {
                     .join(", ")
             )),
             Frame::Null => Some("nil".to_string()),
+            #[cfg(feature = "resp3")]
+            _ => None,
         }
     }
 
@@ -97,6 +325,123 @@ impl Frame {
 mod tests {
     use super::*;
 
+    /// The same HELLO-style reply as a flat RESP2 array.
+    fn hello_reply_array() -> Frame {
+        Frame::Array(vec![
+            Frame::BulkString(Some(Bytes::from("server"))),
+            Frame::BulkString(Some(Bytes::from("redis"))),
+            Frame::SimpleString(b"proto".to_vec()),
+            Frame::Integer(3),
+            Frame::BulkString(Some(Bytes::from("modules"))),
+            Frame::Array(vec![]),
+        ])
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_into_resp2_flattens_resp3_types() {
+        let bulk = |s: &str| Frame::BulkString(Some(Bytes::from(s.to_string())));
+        let frame = Frame::Map(vec![
+            (bulk("score"), Frame::Double(1.5)),
+            (
+                bulk("flags"),
+                Frame::Set(vec![Frame::Boolean(true), Frame::Boolean(false)]),
+            ),
+            (
+                bulk("big"),
+                Frame::BigNumber(b"12345678901234567890".to_vec()),
+            ),
+            (
+                bulk("text"),
+                Frame::VerbatimString {
+                    format: *b"txt",
+                    text: Bytes::from("hi"),
+                },
+            ),
+            (bulk("nothing"), Frame::Null),
+        ]);
+        assert_eq!(
+            frame.into_resp2(),
+            Frame::Array(vec![
+                bulk("score"),
+                bulk("1.5"),
+                bulk("flags"),
+                Frame::Array(vec![Frame::Integer(1), Frame::Integer(0)]),
+                bulk("big"),
+                bulk("12345678901234567890"),
+                bulk("text"),
+                bulk("hi"),
+                bulk("nothing"),
+                Frame::Null,
+            ])
+        );
+        assert_eq!(
+            Frame::Push(vec![bulk("message"), bulk("news"), bulk("hello")]).into_resp2(),
+            Frame::Array(vec![bulk("message"), bulk("news"), bulk("hello")])
+        );
+    }
+
+    #[test]
+    fn test_memory_usage_bulk_string() {
+        let frame = Frame::BulkString(Some(Bytes::from(vec![b'x'; 4096])));
+        let usage = frame.memory_usage();
+        assert!(usage >= 4096);
+        assert!(usage < 4096 * 2);
+        assert_eq!(
+            Frame::Integer(1).memory_usage(),
+            std::mem::size_of::<Frame>()
+        );
+    }
+
+    #[test]
+    fn test_memory_usage_nested_arrays() {
+        let inner = || {
+            Frame::Array(
+                (0..100)
+                    .map(|_| Frame::BulkString(Some(Bytes::from(vec![b'x'; 64]))))
+                    .collect(),
+            )
+        };
+        let frame = Frame::Array((0..10).map(|_| inner()).collect());
+
+        // 1 outer + 10 inner + 1000 leaf frames, plus 64,000 payload bytes
+        let allocated = 1011 * std::mem::size_of::<Frame>() + 64_000;
+        let usage = frame.memory_usage();
+        assert!(usage * 2 >= allocated, "{} vs {}", usage, allocated);
+        assert!(usage <= allocated * 2, "{} vs {}", usage, allocated);
+        assert!(usage > inner().memory_usage() * 10);
+    }
+
+    #[test]
+    fn test_map_get_flat_array() {
+        let reply = hello_reply_array();
+        assert_eq!(
+            reply.map_get("server"),
+            Some(&Frame::BulkString(Some(Bytes::from("redis"))))
+        );
+        assert_eq!(reply.map_get("proto"), Some(&Frame::Integer(3)));
+        assert_eq!(reply.map_get("modules"), Some(&Frame::Array(vec![])));
+        assert_eq!(reply.map_get("redis"), None);
+        assert_eq!(reply.map_get("missing"), None);
+        assert_eq!(Frame::Integer(1).map_get("server"), None);
+    }
+
+    #[cfg(feature = "resp3")]
+    #[test]
+    fn test_map_get_matches_between_map_and_array() {
+        let array = hello_reply_array();
+        let items = array.as_array().unwrap();
+        let map = Frame::Map(
+            items
+                .chunks_exact(2)
+                .map(|pair| (pair[0].clone(), pair[1].clone()))
+                .collect(),
+        );
+        for key in ["server", "proto", "modules", "missing"] {
+            assert_eq!(map.map_get(key), array.map_get(key), "key {}", key);
+        }
+    }
+
     #[test]
     fn test_frame_to_string() {
         let frame = Frame::SimpleString(b"OK".to_vec());
@@ -144,6 +489,67 @@ mod tests {
         assert!(!Frame::Integer(42).is_null());
     }
 
+    #[test]
+    fn test_frame_as_bytes() {
+        assert_eq!(
+            Frame::SimpleString(b"OK".to_vec()).as_bytes(),
+            Some(&b"OK"[..])
+        );
+        assert_eq!(
+            Frame::BulkString(Some(Bytes::from("data"))).as_bytes(),
+            Some(&b"data"[..])
+        );
+        assert_eq!(Frame::BulkString(None).as_bytes(), None);
+        assert_eq!(Frame::Error(b"ERR".to_vec()).as_bytes(), None);
+        assert_eq!(Frame::Integer(1).as_bytes(), None);
+        assert_eq!(Frame::Array(vec![]).as_bytes(), None);
+        assert_eq!(Frame::Null.as_bytes(), None);
+    }
+
+    #[test]
+    fn test_frame_as_str() {
+        let frame = Frame::SimpleString(b"OK".to_vec());
+        assert!(matches!(frame.as_str(), Some(Cow::Borrowed("OK"))));
+
+        let frame = Frame::BulkString(Some(Bytes::from_static(b"caf\xff")));
+        assert_eq!(frame.as_str().as_deref(), Some("caf\u{fffd}"));
+
+        assert_eq!(Frame::BulkString(None).as_str(), None);
+        assert_eq!(Frame::Error(b"ERR".to_vec()).as_str(), None);
+        assert_eq!(Frame::Integer(1).as_str(), None);
+        assert_eq!(Frame::Null.as_str(), None);
+    }
+
+    #[test]
+    fn test_frame_as_i64() {
+        assert_eq!(Frame::Integer(-7).as_i64(), Some(-7));
+        assert_eq!(Frame::BulkString(Some(Bytes::from("7"))).as_i64(), None);
+        assert_eq!(Frame::SimpleString(b"7".to_vec()).as_i64(), None);
+        assert_eq!(Frame::Array(vec![]).as_i64(), None);
+        assert_eq!(Frame::Null.as_i64(), None);
+    }
+
+    #[test]
+    fn test_frame_as_array() {
+        let items = vec![Frame::Integer(1), Frame::Null];
+        let frame = Frame::Array(items.clone());
+        assert_eq!(frame.as_array(), Some(&items[..]));
+        assert_eq!(Frame::Array(vec![]).as_array(), Some(&[][..]));
+        assert_eq!(Frame::Integer(1).as_array(), None);
+        assert_eq!(Frame::Null.as_array(), None);
+    }
+
+    #[test]
+    fn test_frame_is_nil() {
+        assert!(Frame::Null.is_nil());
+        assert!(Frame::BulkString(None).is_nil());
+        assert!(!Frame::BulkString(Some(Bytes::new())).is_nil());
+        assert!(!Frame::SimpleString(Vec::new()).is_nil());
+        assert!(!Frame::Integer(0).is_nil());
+        assert!(!Frame::Array(vec![]).is_nil());
+        assert!(!Frame::Error(Vec::new()).is_nil());
+    }
+
     #[test]
     fn test_frame_array_to_string() {
         let frames = vec![
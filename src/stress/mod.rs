@@ -35,7 +35,10 @@ async fn test_multiplexing_stress() {
 
                     decoder.append(&buf[..n]);
 
-                    while let Ok(Some(frame)) = decoder.decode() {
+                    let Ok(frames) = decoder.decode_all() else {
+                        return;
+                    };
+                    for frame in frames {
                         let response = match frame {
                             Frame::Array(ref args) => {
                                 if let Some(Frame::BulkString(Some(cmd))) = args.first() {
@@ -52,10 +55,10 @@ async fn test_multiplexing_stress() {
                         };
 
                         encoder.encode(&response);
-                        let data = encoder.take();
-                        if socket.write_all(&data).await.is_err() {
+                        if socket.write_all(encoder.buffer()).await.is_err() {
                             return;
                         }
+                        encoder.reset();
                     }
                 }
             });
@@ -83,3 +86,79 @@ async fn test_multiplexing_stress() {
         handle.await.unwrap();
     }
 }
+
+/// Spawns a server answering ECHO with its argument, reading in small chunks so
+/// that frames written together arrive split across reads.
+async fn spawn_echo_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut decoder = Decoder::new();
+                let mut encoder = Encoder::new();
+                let mut buf = [0u8; 512];
+
+                loop {
+                    let n = match socket.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => n,
+                    };
+                    decoder.append(&buf[..n]);
+
+                    let Ok(frames) = decoder.decode_all() else {
+                        return;
+                    };
+                    for frame in frames {
+                        let response = match frame {
+                            Frame::Array(mut args) if args.len() == 2 => args.remove(1),
+                            _ => Frame::Error(b"ERR format".to_vec()),
+                        };
+                        encoder.encode(&response);
+                    }
+                    if socket.write_all(encoder.buffer()).await.is_err() {
+                        return;
+                    }
+                    encoder.reset();
+                }
+            });
+        }
+    });
+
+    format!("redis://{}", addr)
+}
+
+async fn assert_echo_order(write_buffer: usize) {
+    let client = ClientBuilder::new()
+        .address(spawn_echo_server().await)
+        .queue_size(10000)
+        .write_buffer(write_buffer)
+        .build()
+        .await
+        .expect("Failed to connect");
+
+    let mut handles = Vec::new();
+    for task in 0..50 {
+        let mut client = client.clone();
+        handles.push(tokio::spawn(async move {
+            for i in 0..100 {
+                let msg = format!("task-{}-msg-{}-{}", task, i, "x".repeat(i % 37));
+                let reply = client.echo(&msg).await.unwrap();
+                assert_eq!(reply, msg.as_bytes());
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_write_buffer_preserves_order() {
+    assert_echo_order(0).await;
+    assert_echo_order(16 * 1024).await;
+    // Smaller than many frames, forcing mid-batch flushes
+    assert_echo_order(16).await;
+}
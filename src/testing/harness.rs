@@ -1 +1,89 @@
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage};
 
+use crate::{Client, Error, Result};
+
+/// Docker image started by [`redis_container`].
+pub const REDIS_IMAGE: &str = "redis";
+
+/// Tag of [`REDIS_IMAGE`] started by [`redis_container`].
+pub const REDIS_TAG: &str = "7.2-alpine";
+
+/// Port Redis listens on inside the container.
+const REDIS_PORT: u16 = 6379;
+
+/// A Redis server running in a Docker container.
+///
+/// The container is stopped and removed when this is dropped, so keep it alive
+/// for as long as the test talks to the server.
+#[derive(Debug)]
+pub struct RedisContainer {
+    container: ContainerAsync<GenericImage>,
+    url: String,
+}
+
+impl RedisContainer {
+    /// Returns the `redis://` URL of the server.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the ID of the Docker container.
+    pub fn id(&self) -> &str {
+        self.container.id()
+    }
+
+    /// Connects a new client to the server.
+    pub async fn client(&self) -> Result<Client> {
+        Client::connect(&self.url).await
+    }
+}
+
+/// Starts a Redis container and connects a client to it.
+///
+/// Requires a reachable Docker daemon. The returned [`RedisContainer`] owns the
+/// container; dropping it stops the server.
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] if Docker is unavailable or the container fails to
+/// start, and any error from [`Client::connect`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use muxis::testing::redis_container;
+/// # use bytes::Bytes;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let (mut client, _redis) = redis_container().await?;
+/// client.set("key", Bytes::from("value")).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn redis_container() -> Result<(Client, RedisContainer)> {
+    let container = GenericImage::new(REDIS_IMAGE, REDIS_TAG)
+        .with_exposed_port(REDIS_PORT.tcp())
+        .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+        .start()
+        .await
+        .map_err(docker_error)?;
+    let host = container.get_host().await.map_err(docker_error)?;
+    let port = container
+        .get_host_port_ipv4(REDIS_PORT)
+        .await
+        .map_err(docker_error)?;
+
+    let redis = RedisContainer {
+        container,
+        url: format!("redis://{}:{}", host, port),
+    };
+    let client = redis.client().await?;
+    Ok((client, redis))
+}
+
+fn docker_error(e: testcontainers::TestcontainersError) -> Error {
+    Error::Io {
+        source: std::io::Error::other(e),
+    }
+}
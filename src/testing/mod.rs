@@ -9,3 +9,5 @@
 
 /// Test harness.
 pub mod harness;
+
+pub use harness::{redis_container, RedisContainer};
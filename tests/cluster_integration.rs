@@ -18,8 +18,8 @@
 #![cfg(feature = "cluster")]
 
 use bytes::Bytes;
-use muxis::ClusterClient;
 use muxis::Result;
+use muxis::{key_slot, ClusterClient};
 
 /// Helper function to create a cluster client for testing.
 async fn create_test_client() -> Result<ClusterClient> {
@@ -340,3 +340,40 @@ async fn test_cluster_special_characters() {
         client.del(key).await.expect("DEL failed");
     }
 }
+
+#[tokio::test]
+#[ignore]
+async fn test_cluster_keyslot_matches_server() {
+    let client = create_test_client().await.expect("failed to connect");
+
+    let keys = [
+        "",
+        "foo",
+        "bar",
+        "123456789",
+        "user1000",
+        "{user1000}.following",
+        "{user1000}.followers",
+        "foo{bar}baz",
+        "foo{}{bar}",
+        "foo{{bar}}zap",
+        "foo{bar}{zap}",
+        "{}",
+        "{",
+        "}",
+        "unicode:\u{e9}t\u{e9}",
+    ];
+
+    for key in keys {
+        let server_slot = client
+            .server_keyslot(key)
+            .await
+            .expect("CLUSTER KEYSLOT failed");
+        assert_eq!(
+            key_slot(key),
+            server_slot,
+            "client slot differs from server slot for key: {:?}",
+            key
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use bytes::Bytes;
-use muxis::Client;
+use muxis::{Client, KeyType};
 
 #[tokio::test]
 #[ignore]
@@ -41,6 +41,25 @@ async fn test_key_type() {
     assert_eq!(nonexistent_type, "none");
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_key_type_enum() {
+    let mut client = Client::connect("redis://127.0.0.1:6379")
+        .await
+        .expect("Failed to connect");
+
+    client
+        .set("typeenumkey", Bytes::from("value"))
+        .await
+        .unwrap();
+
+    let key_type = client.key_type_enum("typeenumkey").await.unwrap();
+    assert_eq!(key_type, KeyType::String);
+
+    let nonexistent_type = client.key_type_enum("nonexistentkey").await.unwrap();
+    assert_eq!(nonexistent_type, KeyType::None);
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_expire_and_ttl() {
@@ -20,6 +20,39 @@ async fn test_lpush_and_rpush() {
     assert_eq!(len2, 3);
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_lpushx_and_rpushx() {
+    let mut client = Client::connect("redis://127.0.0.1:6379")
+        .await
+        .expect("Failed to connect");
+
+    client.del("pushxlist").await.ok();
+
+    let len = client
+        .lpushx("pushxlist", &[Bytes::from("a")])
+        .await
+        .unwrap();
+    assert_eq!(len, 0);
+
+    client
+        .rpush("pushxlist", &[Bytes::from("a")])
+        .await
+        .unwrap();
+
+    let len = client
+        .lpushx("pushxlist", &[Bytes::from("b")])
+        .await
+        .unwrap();
+    assert_eq!(len, 2);
+
+    let len = client
+        .rpushx("pushxlist", &[Bytes::from("c")])
+        .await
+        .unwrap();
+    assert_eq!(len, 3);
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_lpop_and_rpop() {
@@ -2,10 +2,40 @@
 //!
 //! Run with: cargo test --test sorted_set_commands -- --ignored
 //! Requires Redis running at 127.0.0.1:6379
+//!
+//! Tests using `muxis::testing::redis_container` start their own Redis in Docker
+//! instead: cargo test --test sorted_set_commands --features test-utils -- --ignored container
+//! They are ignored too, since they fail if Docker is unavailable.
 
 use bytes::Bytes;
 use muxis::Client;
 
+#[cfg(feature = "test-utils")]
+#[tokio::test]
+#[ignore]
+async fn test_zadd_and_zrange_in_container() {
+    let (mut client, _redis) = muxis::testing::redis_container()
+        .await
+        .expect("Redis container failed to start");
+
+    let key = "test:zset:container";
+    let count = client
+        .zadd(
+            key,
+            &[
+                (1.0, Bytes::from("one")),
+                (2.0, Bytes::from("two")),
+                (3.0, Bytes::from("three")),
+            ],
+        )
+        .await
+        .expect("ZADD failed");
+    assert_eq!(count, 3);
+
+    let members = client.zrange(key, 0, -1).await.expect("ZRANGE failed");
+    assert_eq!(members, vec!["one", "two", "three"]);
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_zadd_and_zrange() {